@@ -5,21 +5,63 @@
 //! lifecycle, including initialization, update loop, and rendering.
 
 use crate::assets::AssetManager;
-use crate::consts::{PIXELS_HEIGHT, PIXELS_WIDTH, TRACK_FILE};
+use crate::audio::{screen_pan, Audio, Sfx};
+use crate::consts::FPS;
+#[cfg(debug_assertions)]
+use crate::game::debug::DebugOverlay;
 #[cfg(debug_assertions)]
 use crate::game::utils::FpsCounter;
+use crate::game::utils::{FrameTimings, PhysicsClock};
 use crate::game::{
+    ai::{AiDriver, Difficulty},
     camera::Camera,
-    input::Inputs, /* TODO: Move from this piece of shit to the handle() func */
-    rendering::Renderer, world::World,
+    input::{InputScheme, Inputs}, /* TODO: Move from this piece of shit to the handle() func */
+    rendering::{PlayerHud, RenderConfig, Renderer, SplitConfig},
+    replay::Recorder,
+    track::{OffTrackSurface, TrackDef},
+    world::{Car, Race, World},
 };
 
-use crate::menu::{MenuAction, MenuRenderer};
-use crate::state::{GameState, MenuState};
-use anyhow::Result;
+use crate::menu::{
+    draw_text, draw_text_scaled, text_metrics, MenuAction, MenuRenderer, TEXT_SCALE,
+};
+use crate::settings::Settings;
+use crate::state::{GameState, MenuState, Transition};
+use anyhow::{anyhow, Result};
+use glam::Vec2;
 use pix_win_loop::winit::event::{Event, WindowEvent};
+use pix_win_loop::winit::window::Fullscreen;
 use pix_win_loop::{App, Context, KeyCode, Pixels};
-use std::time::Instant;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// Velocity lost in a single step below which a collision doesn't shake the
+/// camera, so pushing against a wall stays calm
+const MIN_CRASH_IMPACT: f32 = 30.0;
+
+/// Camera trauma added per unit/s of velocity lost in a crash
+const TRAUMA_PER_IMPACT: f32 = 1.0 / 200.0;
+
+/// File the current race is saved to when pressing F5
+const REPLAY_FILE: &str = "replay.m7r";
+
+/// Seconds "GO!" stays on screen after the countdown
+const GO_SECONDS: f32 = 1.0;
+
+/// Seconds of the fade between the menu and a race
+const FADE_DURATION: f32 = 0.6;
+
+/// Distance of the cameras to the winning car after a race
+const ORBIT_RADIUS: f32 = 120.0;
+
+/// Angular speed of the cameras around the winning car in radians/s
+const ORBIT_SPEED: f32 = 0.4;
+
+/// Slider step of the volume items in percent
+const VOLUME_STEP: i32 = 10;
+
+/// Frames taking longer than this many target frame times get logged
+const LONG_FRAME_FACTOR: f32 = 2.0;
 
 /// TODO: Update docs they are currently wrong
 /// Main game application managing state, rendering, and game loop
@@ -33,8 +75,7 @@ use std::time::Instant;
 /// * Asset loading and resource management
 ///
 /// The game renders in split-screen mode with:
-/// * Player 1's view in the top half
-/// * Player 2's view in the bottom half
+/// * One horizontal strip per player, player 1 at the top
 /// * A separator line between views
 pub struct Application {
     // Game state stuff
@@ -42,16 +83,24 @@ pub struct Application {
     renderer: Renderer,
     /// Game world containing all game entities
     world: World,
-    /// Camera for player 1's view (top screen)
-    camera_player_one: Camera,
-    /// Camera for player 2's view (bottom screen)
-    camera_player_two: Camera,
+    /// Camera of each player's view, indexed by player
+    cameras: Vec<Camera>,
+    /// Car each camera follows, indexed by player
+    camera_targets: Vec<usize>,
+    /// Placement of the player views and separators
+    split: SplitConfig,
+    /// Internal resolution everything is drawn at
+    render_config: RenderConfig,
     /// Input handler for both players
     controls: Inputs,
 
     // Menu stuff
     /// Menu renderer
     menu_renderer: MenuRenderer,
+    /// User settings changed through the menus
+    settings: Settings,
+    /// Engine sound and sound effects
+    audio: Audio,
 
     // Global state and stuff
     /// Menu/game state
@@ -61,8 +110,27 @@ pub struct Application {
     #[cfg(debug_assertions)]
     /// FPS counter for performance monitoring
     fps_counter: FpsCounter,
+    #[cfg(debug_assertions)]
+    /// Overlay for live-tuning camera and physics constants
+    debug_overlay: DebugOverlay,
     /// Timestamp of last update for delta time calculation
     last_update: Instant,
+    /// Phase durations of the current frame for stutter diagnostics
+    frame_timings: FrameTimings,
+    /// Splits real frame time into fixed world steps
+    physics_clock: PhysicsClock,
+    /// Inputs of every world step since the race started
+    recorder: Recorder,
+    /// Computer driver of player 2's car, None when a human drives it
+    ai_driver: Option<AiDriver>,
+    /// Fade currently switching to another state, if any
+    fade: Option<Transition>,
+    /// Seconds "GO!" is still shown after the countdown
+    go_time: f32,
+    /// Race time and finishing order of the current race
+    race: Race,
+    /// Track being raced on
+    track: TrackDef,
 }
 
 impl Application {
@@ -75,6 +143,10 @@ impl Application {
     /// 3. Initializes the game world and entities
     /// 4. Sets up cameras, controls, and timing systems
     ///
+    /// # Arguments
+    ///
+    /// * `render_config` - Internal resolution, must match the pixel buffer
+    ///
     /// # Returns
     ///
     /// * `Ok(Application)` - A fully initialized application ready to run
@@ -84,24 +156,289 @@ impl Application {
     ///
     /// Will return an error if:
     /// * The ground texture file cannot be loaded
-    pub fn new() -> Result<Self> {
+    pub fn new(render_config: RenderConfig) -> Result<Self> {
+        Self::with_track(render_config, TrackDef::default())
+    }
+
+    /// Creates a new game application racing on a custom track
+    ///
+    /// # Arguments
+    ///
+    /// * `render_config` - Internal resolution, must match the pixel buffer
+    /// * `track` - Track to race on, e.g. from [`TrackDef::load`]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// * The track fails [`TrackDef::validate`]
+    /// * The track's ground texture isn't a loaded asset
+    pub fn with_track(render_config: RenderConfig, track: TrackDef) -> Result<Self> {
         let asset_manager = AssetManager::new();
-        let ground_texture = asset_manager.get_texture(TRACK_FILE);
-        let renderer = Renderer::new(PIXELS_WIDTH, PIXELS_HEIGHT / 2, ground_texture.clone());
+        let ground_texture = asset_manager
+            .try_get_texture(&track.texture)
+            .ok_or_else(|| anyhow!("Unknown track texture {}", track.texture))?;
+        let mut world = World::from_track(&track)?;
+        world.set_off_track(Some(OffTrackSurface::grass(ground_texture.clone())));
+        let player_count = world.player_count();
+        let split = SplitConfig::default();
+        let view_height = split.view_height(render_config.height, player_count);
+        let renderer = Renderer::new(render_config.width, view_height, ground_texture.clone());
 
-        Ok(Self {
+        let mut app = Self {
             state: GameState::Menu(MenuState::Main),
-            world: World::new(),
+            cameras: (0..player_count).map(|_| Camera::default()).collect(),
+            camera_targets: (0..player_count).collect(),
+            world,
             renderer,
             asset_manager,
-            camera_player_one: Camera::default(),
-            camera_player_two: Camera::default(),
             controls: Inputs::new(),
             #[cfg(debug_assertions)]
             fps_counter: FpsCounter::new(1.0),
+            #[cfg(debug_assertions)]
+            debug_overlay: DebugOverlay::new(),
             last_update: Instant::now(),
-            menu_renderer: MenuRenderer::new(),
-        })
+            frame_timings: FrameTimings::default(),
+            physics_clock: PhysicsClock::default(),
+            recorder: Recorder::new(),
+            ai_driver: None,
+            fade: None,
+            go_time: 0.0,
+            race: Race::new(track.laps, player_count),
+            menu_renderer: MenuRenderer::new(render_config),
+            settings: Settings::new(),
+            audio: Audio::new(),
+            split,
+            render_config,
+            track,
+        };
+        app.apply_volumes();
+
+        Ok(app)
+    }
+
+    /// Returns the car each camera follows
+    ///
+    /// Targets that no longer exist, e.g. after the player count changed,
+    /// fall back to the camera's own player, or the last car.
+    fn camera_targets(&self) -> Vec<usize> {
+        let car_count = self.world.cars.len();
+        (0..self.cameras.len())
+            .map(|camera| match self.camera_targets.get(camera) {
+                Some(&target) if target < car_count => target,
+                _ => camera.min(car_count.saturating_sub(1)),
+            })
+            .collect()
+    }
+
+    /// Returns the car after `target`, wrapping from the last car back to 0
+    #[cfg(debug_assertions)]
+    fn next_camera_target(target: usize, car_count: usize) -> usize {
+        if car_count == 0 {
+            0
+        } else {
+            (target + 1) % car_count
+        }
+    }
+
+    /// Passes the volume settings on to the audio output
+    fn apply_volumes(&mut self) {
+        let volume = |key| self.settings.get_percent(key).unwrap_or(100) as f32 / 100.0;
+        let (master, sfx) = (volume("master_volume"), volume("sfx_volume"));
+        self.audio.set_master_volume(master);
+        self.audio.set_sfx_volume(sfx);
+    }
+
+    /// Draws a "WRONG WAY" warning at the top center of a player's view
+    ///
+    /// # Arguments
+    ///
+    /// * `view` - RGBA pixel buffer of a single player's view
+    /// * `view_height` - Height of the view in pixels
+    fn draw_wrong_way(&self, view: &mut [u8], view_height: u32) {
+        const TEXT: &str = "WRONG WAY";
+
+        let font = self.asset_manager.get_font();
+        let (width, _) = text_metrics(font, TEXT, TEXT_SCALE);
+        let pos = Vec2::new((self.render_config.width as f32 - width) / 2.0, 16.0);
+        draw_text(
+            view,
+            self.render_config.width,
+            view_height,
+            font,
+            TEXT,
+            pos,
+            [255, 40, 40, 255],
+        );
+    }
+
+    /// Draws a player's speed, lap and race position in the bottom-left
+    /// corner of their view
+    ///
+    /// # Arguments
+    ///
+    /// * `view` - RGBA pixel buffer of the player's view
+    /// * `view_height` - Height of the view in pixels
+    /// * `player` - Index of the player
+    fn draw_hud(&self, view: &mut [u8], view_height: u32, player: usize) {
+        const MARGIN: f32 = 8.0;
+
+        let hud = PlayerHud {
+            speed: self.world.cars[player].speed(),
+            laps_completed: self.world.lap_count(player),
+            laps: self.race.laps(),
+            position: self.world.race_position(player).unwrap_or(player + 1),
+        };
+
+        let font = self.asset_manager.get_font();
+        let lines = hud.lines();
+        let line_height = TEXT_SCALE + 2.0;
+        let top = view_height as f32 - MARGIN - lines.len() as f32 * line_height;
+        for (row, line) in lines.iter().enumerate() {
+            let pos = Vec2::new(MARGIN, top + row as f32 * line_height);
+            draw_text(
+                view,
+                self.render_config.width,
+                view_height,
+                font,
+                line,
+                pos,
+                [255, 255, 255, 255],
+            );
+        }
+    }
+
+    /// Draws the frame rate and each car's speed in the top-right corner
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer of the full screen
+    #[cfg(debug_assertions)]
+    fn draw_fps(&self, frame: &mut [u8]) {
+        const MARGIN: f32 = 4.0;
+
+        let mut lines = vec![format!("{:.0} FPS", self.fps_counter.fps())];
+        lines.extend(
+            self.world
+                .cars
+                .iter()
+                .enumerate()
+                .map(|(i, car)| format!("P{} {:.0}", i + 1, car.speed())),
+        );
+
+        let font = self.asset_manager.get_font();
+        for (row, line) in lines.iter().enumerate() {
+            let (width, _) = text_metrics(font, line, TEXT_SCALE);
+            let pos = Vec2::new(
+                self.render_config.width as f32 - width - MARGIN,
+                MARGIN + row as f32 * (TEXT_SCALE + 2.0),
+            );
+            draw_text(
+                frame,
+                self.render_config.width,
+                self.render_config.height,
+                font,
+                line,
+                pos,
+                [255, 255, 0, 255],
+            );
+        }
+    }
+
+    /// Blends the whole frame toward black
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer of the full screen
+    /// * `alpha` - 0.0 keeps the frame, 1.0 makes it fully black
+    fn draw_fade(frame: &mut [u8], alpha: f32) {
+        let keep = 1.0 - alpha.clamp(0.0, 1.0);
+        for pixel in frame.chunks_exact_mut(4) {
+            for channel in &mut pixel[..3] {
+                *channel = (*channel as f32 * keep) as u8;
+            }
+        }
+    }
+
+    /// Dims the whole frame and draws a centered "PAUSED" label on top
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer of the full screen
+    fn draw_pause_overlay(&self, frame: &mut [u8]) {
+        const DIM: f32 = 0.4;
+
+        Self::draw_fade(frame, 1.0 - DIM);
+        self.draw_banner(frame, "PAUSED");
+    }
+
+    /// Dims the whole frame and lists the winner and everyone's time
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer of the full screen
+    /// * `winner` - Index of the player who finished first
+    fn draw_results(&self, frame: &mut [u8], winner: usize) {
+        const DIM: f32 = 0.3;
+        const LINE_HEIGHT: f32 = TEXT_SCALE + 6.0;
+
+        Self::draw_fade(frame, 1.0 - DIM);
+        self.draw_banner(frame, &format!("PLAYER {} WINS", winner + 1));
+
+        let mut lines: Vec<String> = (0..self.world.player_count())
+            .map(|player| match self.race.finish_time(player) {
+                Some(time) => format!("Player {}: {:.2}s", player + 1, time),
+                None => format!(
+                    "Player {}: {}/{} laps",
+                    player + 1,
+                    self.world.lap_count(player),
+                    self.race.laps()
+                ),
+            })
+            .collect();
+        lines.push("Press Enter".to_string());
+
+        let font = self.asset_manager.get_font();
+        let mut y = self.render_config.height as f32 / 2.0 + TEXT_SCALE * 2.0;
+        for line in &lines {
+            let (width, _) = text_metrics(font, line, TEXT_SCALE);
+            let pos = Vec2::new((self.render_config.width as f32 - width) / 2.0, y);
+            draw_text(
+                frame,
+                self.render_config.width,
+                self.render_config.height,
+                font,
+                line,
+                pos,
+                [255, 255, 255, 255],
+            );
+            y += LINE_HEIGHT;
+        }
+    }
+
+    /// Draws a large white label in the center of the screen
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer of the full screen
+    /// * `text` - Label to draw
+    fn draw_banner(&self, frame: &mut [u8], text: &str) {
+        let font = self.asset_manager.get_font();
+        let size = TEXT_SCALE * 2.0;
+        let (width, ascent) = text_metrics(font, text, size);
+        let pos = Vec2::new(
+            (self.render_config.width as f32 - width) / 2.0,
+            (self.render_config.height as f32 - ascent) / 2.0,
+        );
+        draw_text_scaled(
+            frame,
+            self.render_config.width,
+            self.render_config.height,
+            font,
+            text,
+            pos,
+            size,
+            [255, 255, 255, 255],
+        );
     }
 }
 
@@ -127,9 +464,39 @@ impl App for Application {
     /// Updated menu flow:
     /// [![](https://mermaid.ink/img/pako:eNqVVEtu2zAQvcqAQXYyihZdEUU2ctGVCjnsqlYWtDSShUikwY-BIMk1cpAui54mJylJfeqITpDKG3Lmcea9mQffk1JWSChpFD_s4ce6EDB92u6GaEEy3grIUFj4zo9tw00rRUFOsP7Lsm2ArMDDv-zU1fPTb8g7fgffeI8-wNCYVjTan1OFVWv0gPv14fnpD4VMHhFKq7RUPv5VGFQUGHZYmpuoHaxWVw8FGV8X5MHFTkAoqkKc15NKhYEUMMMN6khLvvW8HVVPIwCVFWK8-xQqKKUwSnYaeGnaIy755Wybc6uxCkp0yQ9I4Rq1HUaxobCxbaQqH0UND7ymnEWFY8z7ZDO7C1uM9bJ5d9OKlsTSGTEu7uaNnpeX8M8yRoI0ezcwPcxaxCsMiwYpgluWgmLUxNEjWfYmdCTrkWm25HiNxiqhPcOZ7gliHvQm8hbLoh28yKfn8yeIsuNar7GG3nUOc6rbrqMXn3b-l2hnrVukF3VdJ6XspArHc-8b587g4rHAR_xc_VcBZ5AX_X2BV58vC_iRTwqiXJ64Ec78ojTLEjensT1JSI_Klarcv9G9xxbEuaZ3s6PuWHF161376HDcGsnuREmoURYToqRt9oTWvNPuZg-Va7ZuuXN9P0cPXPyUcro__gUm3n0i?type=png)](https://mermaid.live/edit#pako:eNqVVEtu2zAQvcqAQXYyihZdEUU2ctGVCjnsqlYWtDSShUikwY-BIMk1cpAui54mJylJfeqITpDKG3Lmcea9mQffk1JWSChpFD_s4ce6EDB92u6GaEEy3grIUFj4zo9tw00rRUFOsP7Lsm2ArMDDv-zU1fPTb8g7fgffeI8-wNCYVjTan1OFVWv0gPv14fnpD4VMHhFKq7RUPv5VGFQUGHZYmpuoHaxWVw8FGV8X5MHFTkAoqkKc15NKhYEUMMMN6khLvvW8HVVPIwCVFWK8-xQqKKUwSnYaeGnaIy755Wybc6uxCkp0yQ9I4Rq1HUaxobCxbaQqH0UND7ymnEWFY8z7ZDO7C1uM9bJ5d9OKlsTSGTEu7uaNnpeX8M8yRoI0ezcwPcxaxCsMiwYpgluWgmLUxNEjWfYmdCTrkWm25HiNxiqhPcOZ7gliHvQm8hbLoh28yKfn8yeIsuNar7GG3nUOc6rbrqMXn3b-l2hnrVukF3VdJ6XspArHc-8b587g4rHAR_xc_VcBZ5AX_X2BV58vC_iRTwqiXJ64Ec78ojTLEjensT1JSI_Klarcv9G9xxbEuaZ3s6PuWHF161376HDcGsnuREmoURYToqRt9oTWvNPuZg-Va7ZuuXN9P0cPXPyUcro__gUm3n0i)
     fn update(&mut self, ctx: &mut Context) -> Result<()> {
+        // Pick up edited textures, the track is copied so it's passed on by hand
+        let reloaded = self.asset_manager.reload_changed();
+        if reloaded.contains(&self.track.texture) {
+            let track = self.asset_manager.get_texture(&self.track.texture).clone();
+            self.world
+                .set_off_track(Some(OffTrackSurface::grass(track.clone())));
+            self.renderer.set_ground_texture(track);
+        }
+
+        // Advance a running fade, switching states at its midpoint
+        if let Some(fade) = &mut self.fade {
+            let switch_to = fade.update(ctx.frame_time().as_secs_f32());
+            let fading_out = !fade.has_switched();
+            if fade.is_finished() {
+                self.fade = None;
+            }
+
+            if let Some(state) = switch_to {
+                log::info!("State change: {:?} -> {:?}", self.state, state);
+                self.state = state;
+                self.last_update = Instant::now();
+                self.physics_clock.reset();
+            }
+
+            // The old state stops reacting to input once it fades out
+            if fading_out {
+                return Ok(());
+            }
+        }
+
         // Calculate dt but only update last_update timestamp when playing
         let now = Instant::now();
-        let dt = if matches!(self.state, GameState::Playing) {
+        let dt = if matches!(self.state, GameState::Playing | GameState::Countdown(_)) {
             let dt = now.duration_since(self.last_update).as_secs_f32();
             self.last_update = now;
             dt
@@ -174,16 +541,60 @@ impl App for Application {
                     }
                 }
 
-                // Handle menu selection/activation
-                if ctx.input.is_physical_key_pressed(KeyCode::Enter) {
-                    match self.menu_renderer.handle_input() {
+                // Handle menu selection/activation and sliders
+                let action = if ctx.input.is_physical_key_pressed(KeyCode::Enter) {
+                    Some(self.menu_renderer.handle_input())
+                } else if ctx.input.is_physical_key_pressed(KeyCode::ArrowLeft) {
+                    Some(self.menu_renderer.adjust_selected(-VOLUME_STEP))
+                } else if ctx.input.is_physical_key_pressed(KeyCode::ArrowRight) {
+                    Some(self.menu_renderer.adjust_selected(VOLUME_STEP))
+                } else {
+                    None
+                };
+
+                if let Some(action) = action {
+                    if action != MenuAction::Nothing {
+                        self.audio.play_sfx(Sfx::MenuSelect);
+                    }
+
+                    match action {
                         MenuAction::Nothing => {
                             log::debug!("Menu: Selected item has no action");
                         }
                         MenuAction::StartGame => {
                             log::info!("Menu: Starting game");
-                            self.state = GameState::Playing;
-                            self.last_update = now;
+                            self.world.reset();
+                            self.recorder.clear();
+                            self.ai_driver = (self.settings.get_bool("cpu_opponent") == Some(true))
+                                .then(|| {
+                                    AiDriver::from_racing_line(
+                                        &self.world.cars[1],
+                                        self.world.racing_line(),
+                                    )
+                                    .with_difficulty(self.settings.difficulty())
+                                });
+                            // Only the computer's car is scaled, a second player drives a stock car
+                            let opponent = if self.ai_driver.is_some() {
+                                self.settings.difficulty()
+                            } else {
+                                Difficulty::Normal
+                            };
+                            opponent.apply(&mut self.world.cars[1], &Car::new(0.0, 0.0));
+                            // Against the computer one player may use either set of keys
+                            self.controls.set_scheme(if self.ai_driver.is_some() {
+                                InputScheme::SinglePlayerBoth
+                            } else {
+                                InputScheme::DualPlayer
+                            });
+                            self.camera_targets = (0..self.cameras.len()).collect();
+                            let targets = self.camera_targets();
+                            for (camera, target) in self.cameras.iter_mut().zip(targets) {
+                                camera.snap_to_car(&self.world.cars[target]);
+                            }
+                            self.go_time = 0.0;
+                            self.race = Race::new(self.track.laps, self.world.player_count());
+                            self.fade =
+                                Some(Transition::new(GameState::countdown(), FADE_DURATION));
                         }
                         MenuAction::OpenSubmenu(submenu) => {
                             log::info!(
@@ -200,13 +611,28 @@ impl App for Application {
                         }
                         MenuAction::ToggleSetting(setting) => {
                             log::info!("Menu: Toggling setting '{}'", setting);
-                            // TODO: Implement actual setting toggle
-                            // Example:
-                            match setting.as_str() {
-                                // "difficulty" => self.toggle_difficulty(),
-                                // "fullscreen" => self.toggle_fullscreen(ctx),
-                                // "vsync" => self.toggle_vsync(ctx),
-                                _ => log::warn!("Unknown setting: {}", setting),
+                            if self.settings.toggle(&setting).is_none() {
+                                log::warn!("Unknown setting: {}", setting);
+                            } else {
+                                if let Some(label) = self.settings.label(&setting) {
+                                    self.menu_renderer.set_selected_text(label);
+                                }
+
+                                // TODO: VSync can only be picked when the surface is created
+                                if setting == "fullscreen" {
+                                    let fullscreen = self.settings.get_bool(&setting) == Some(true);
+                                    ctx.window().set_fullscreen(
+                                        fullscreen.then_some(Fullscreen::Borderless(None)),
+                                    );
+                                }
+                                if setting == "quality" {
+                                    // Low quality projects every other ground row
+                                    let lod = match self.settings.get_choice(&setting) {
+                                        Some("Low") => 2,
+                                        _ => 1,
+                                    };
+                                    self.renderer.set_ground_lod(lod);
+                                }
                             }
                         }
                         MenuAction::SetValue(key, value) => {
@@ -218,17 +644,16 @@ impl App for Application {
                                         ctx.exit();
                                     }
                                 }
-                                "master_volume" => {
-                                    log::info!("Setting master volume to {}%", value);
-                                    // TODO: Implement volume control
-                                }
-                                "music_volume" => {
-                                    log::info!("Setting music volume to {}%", value);
-                                    // TODO: Implement volume control
-                                }
-                                "sfx_volume" => {
-                                    log::info!("Setting SFX volume to {}%", value);
-                                    // TODO: Implement volume control
+                                "master_volume" | "music_volume" | "sfx_volume" => {
+                                    let percent = value.parse().ok().and_then(|percent| {
+                                        self.settings.set_percent(&key, percent)
+                                    });
+                                    if percent.is_none() {
+                                        log::warn!("Invalid volume: {}", value);
+                                    } else if let Some(label) = self.settings.label(&key) {
+                                        self.menu_renderer.set_selected_text(label);
+                                        self.apply_volumes();
+                                    }
                                 }
                                 _ => log::warn!("Unknown setting key: {}", key),
                             }
@@ -247,13 +672,92 @@ impl App for Application {
                     self.menu_renderer.handle_input(); // Simulates pressing "Back"
                 }
             }
+            GameState::Countdown(_) => {
+                // Cars hold still and ignore all controls until the countdown ends
+                let targets = self.camera_targets();
+                for (camera, target) in self.cameras.iter_mut().zip(targets) {
+                    camera.follow_car(&self.world.cars[target], dt);
+                }
+
+                self.state = self.state.tick_countdown(dt);
+                if self.state.is_playing() {
+                    log::info!("State change: Countdown -> Playing");
+                    self.go_time = GO_SECONDS;
+                    self.physics_clock.reset();
+                }
+            }
             GameState::Playing => {
+                self.go_time = (self.go_time - dt).max(0.0);
+
+                #[cfg(debug_assertions)]
+                {
+                    self.debug_overlay
+                        .handle_input(ctx, &mut self.cameras, &mut self.world.cars);
+
+                    if ctx.input.is_physical_key_pressed(KeyCode::F2) {
+                        let draw_grid = !self.renderer.draw_grid();
+                        self.renderer.set_draw_grid(draw_grid);
+                        log::info!("Debug: ground grid = {}", draw_grid);
+                    }
+
+                    // Spectate: cycle the car player 1's camera follows
+                    if ctx.input.is_physical_key_pressed(KeyCode::F3) {
+                        let target = Self::next_camera_target(
+                            self.camera_targets()[0],
+                            self.world.cars.len(),
+                        );
+                        self.camera_targets[0] = target;
+                        log::info!("Debug: camera 1 follows car {}", target + 1);
+                    }
+                }
+
                 self.controls.update(ctx);
-                self.world.update(&self.controls, dt);
-                self.camera_player_one.follow_car(&self.world.cars[0], dt);
-                self.camera_player_two.follow_car(&self.world.cars[1], dt);
+                let mut car_inputs = self.controls.get_car_inputs();
 
-                if ctx.input.is_physical_key_pressed(KeyCode::Escape) {
+                // Step the world in fixed increments, keeping the hardest crash of the frame
+                let update_start = Instant::now();
+                let mut impacts = vec![0.0_f32; self.world.player_count()];
+                for step in self.physics_clock.tick(dt) {
+                    if let Some(ai) = &mut self.ai_driver {
+                        car_inputs[1] = ai.drive(&self.world.cars[1]);
+                    }
+                    self.recorder.record(&car_inputs);
+                    self.world.step(&car_inputs, step);
+                    for (i, impact) in impacts.iter_mut().enumerate() {
+                        *impact = impact.max(self.world.impact(i));
+                    }
+
+                    self.race.record_step(&self.world, step);
+                    if let Some(winner) = self.race.winner() {
+                        log::info!(
+                            "State change: Playing -> Finished, player {} won",
+                            winner + 1
+                        );
+                        self.state = GameState::Finished { winner };
+                        break;
+                    }
+                }
+                self.frame_timings.update = update_start.elapsed();
+
+                let trauma = |impact: f32| (impact - MIN_CRASH_IMPACT).max(0.0) * TRAUMA_PER_IMPACT;
+                let targets = self.camera_targets();
+                for (camera, target) in self.cameras.iter_mut().zip(targets) {
+                    camera.add_trauma(trauma(impacts[target]));
+                    camera.follow_car(&self.world.cars[target], dt);
+                }
+
+                if ctx.input.is_physical_key_pressed(KeyCode::F5) {
+                    match self.recorder.to_replay().save(REPLAY_FILE) {
+                        Ok(()) => log::info!(
+                            "Saved replay of {} steps to {}",
+                            self.recorder.len(),
+                            REPLAY_FILE
+                        ),
+                        Err(err) => log::error!("{:#}", err),
+                    }
+                }
+
+                if self.state.is_playing() && ctx.input.is_physical_key_pressed(KeyCode::Escape) {
                     log::info!("State change: Playing -> Paused");
                     self.state = GameState::Paused;
                 }
@@ -263,12 +767,46 @@ impl App for Application {
                     log::info!("State change: Paused -> Playing");
                     self.state = GameState::Playing;
                     self.last_update = now;
+                    self.physics_clock.reset();
                 }
                 if ctx.input.is_physical_key_pressed(KeyCode::KeyQ) {
-                    log::info!("State change: Paused -> Main Menu");
-                    self.state = GameState::Menu(MenuState::Main);
+                    self.fade = Some(Transition::new(GameState::main(), FADE_DURATION));
                 }
             }
+            GameState::Finished { winner } => {
+                // Every view circles the winner while the results are shown
+                if let Some(car) = self.world.cars.get(winner) {
+                    let target = car.position();
+                    let dt = ctx.frame_time().as_secs_f32();
+                    for camera in &mut self.cameras {
+                        camera.orbit(target, dt, ORBIT_RADIUS, ORBIT_SPEED);
+                    }
+                }
+
+                if ctx.input.is_physical_key_pressed(KeyCode::Enter) {
+                    self.fade = Some(Transition::new(GameState::main(), FADE_DURATION));
+                }
+            }
+        }
+
+        // Only a running race makes engine noise, one engine per player panned
+        // to where their car is in their own view
+        if self.state.is_playing() {
+            let viewport = Vec2::new(
+                self.render_config.width as f32,
+                self.split
+                    .view_height(self.render_config.height, self.cameras.len())
+                    as f32,
+            );
+            for (player, (car, camera)) in self.world.cars.iter().zip(&self.cameras).enumerate() {
+                let pan = camera
+                    .world_to_screen(car.position(), viewport)
+                    .map_or(0.0, |screen| screen_pan(screen.x, viewport.x));
+                self.audio
+                    .set_engine(player, car.speed() / car.max_speed(), pan);
+            }
+        } else {
+            self.audio.pause_engine();
         }
 
         Ok(())
@@ -278,14 +816,13 @@ impl App for Application {
     /// Renders the game scene in split-screen mode
     ///
     /// This method renders the complete game scene, including:
-    /// 1. Top half - Player 1's view from camera1
-    /// 2. Red separator line between views
-    /// 3. Bottom half - Player 2's view from camera2
+    /// 1. One horizontal strip per player, from that player's camera
+    /// 2. Separators between the strips (see `SplitConfig`)
     ///
     /// The rendering process:
-    /// 1. Splits the pixel buffer into top/bottom views
+    /// 1. Splits the pixel buffer into one view per player
     /// 2. Renders each camera view independently
-    /// 3. Draws the separator line
+    /// 3. Draws the separator lines
     /// 4. Sends the final buffer to the display
     ///
     /// # Arguments
@@ -297,51 +834,107 @@ impl App for Application {
     ///
     /// * `Ok(())` - Render completed successfully
     /// * `Err(Error)` - If any rendering step fails
-    fn render(&mut self, pixels: &mut Pixels, _blending_factor: f64) -> Result<()> {
+    fn render(&mut self, pixels: &mut Pixels, blending_factor: f64) -> Result<()> {
+        let render_start = Instant::now();
         let frame = pixels.frame_mut();
 
+        // Draw cars between their last two physics steps, counting both the
+        // time the physics clock still owes and the time since the last update
+        let interpolation = if self.state.is_playing() {
+            let owed = self.physics_clock.leftover() + blending_factor as f32 / FPS;
+            owed / self.physics_clock.step()
+        } else {
+            1.0
+        };
+        self.renderer.set_blending_factor(interpolation);
+
         match self.state {
-            GameState::Playing | GameState::Paused => {
-                let half_height = PIXELS_HEIGHT / 2;
-                let row_size = PIXELS_WIDTH * 4;
-                let view_size = (PIXELS_WIDTH * half_height * 4) as usize;
-
-                // Render player 1's view (top half)
-                let top_view = &mut frame[0..view_size];
-                self.renderer.render(
-                    top_view,
-                    &self.world,
-                    &self.camera_player_one,
-                    &self.asset_manager,
-                );
+            GameState::Countdown(_)
+            | GameState::Playing
+            | GameState::Paused
+            | GameState::Finished { .. } => {
+                let rows = self
+                    .split
+                    .view_rows(self.render_config.height, self.cameras.len());
+                let row_size = (self.render_config.width * 4) as usize;
+                let byte_range =
+                    |rows: Range<u32>| rows.start as usize * row_size..rows.end as usize * row_size;
 
-                // Render player 2's view (bottom half)
-                let bottom_view = &mut frame[view_size..];
-                self.renderer.render(
-                    bottom_view,
-                    &self.world,
-                    &self.camera_player_two,
-                    &self.asset_manager,
-                );
+                let separator = self.split.separator_color();
+                let fill_separator = |frame: &mut [u8], rows| {
+                    for pixel in frame[byte_range(rows)].chunks_exact_mut(4) {
+                        pixel.copy_from_slice(&separator);
+                    }
+                };
+
+                // Render each player's view into its own strip, top to bottom,
+                // and fill the rows above it up to the previous view
+                let mut previous_end = 0;
+                for (player, (view_rows, camera)) in rows.into_iter().zip(&self.cameras).enumerate()
+                {
+                    fill_separator(frame, previous_end..view_rows.start);
+                    previous_end = view_rows.end;
 
-                // Draw red separator line between views
-                let separator_row = view_size - row_size as usize;
-                for x in 0..PIXELS_WIDTH as usize {
-                    let pixel_idx = separator_row + x * 4;
-                    frame[pixel_idx..pixel_idx + 4].copy_from_slice(&[255, 0, 0, 255]);
+                    let view_height = view_rows.len() as u32;
+                    let view = &mut frame[byte_range(view_rows)];
+                    self.renderer
+                        .render(view, &self.world, camera, &self.asset_manager);
+                    if self.world.is_wrong_way(player) {
+                        self.draw_wrong_way(view, view_height);
+                    }
+                    self.draw_hud(view, view_height, player);
                 }
+                fill_separator(frame, previous_end..self.render_config.height);
 
-                if self.state == GameState::Paused {
-                    // TODO: Draw text?? paused
-                    // use menu renderer without clearing background so u can overlay menus/ui is hacky but would work
+                #[cfg(debug_assertions)]
+                self.debug_overlay.render(
+                    frame,
+                    self.render_config.width,
+                    self.render_config.height,
+                    self.asset_manager.get_font(),
+                    &self.cameras[0],
+                    &self.world.cars[0],
+                );
+
+                if let Some(number) = self.state.countdown_number() {
+                    self.draw_banner(frame, &number.to_string());
+                } else if self.state == GameState::Paused {
+                    self.draw_pause_overlay(frame);
+                } else if let GameState::Finished { winner } = self.state {
+                    self.draw_results(frame, winner);
+                } else if self.go_time > 0.0 {
+                    self.draw_banner(frame, "GO!");
                 }
             }
-            GameState::Menu(menu_state) => self.menu_renderer.render(frame, &self.asset_manager)?,
+            GameState::Menu(_) => self.menu_renderer.render(frame, &self.asset_manager)?,
+        }
+
+        if let Some(fade) = &self.fade {
+            Self::draw_fade(frame, fade.alpha());
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            self.fps_counter.update();
+            self.draw_fps(frame);
         }
 
         // Update display
         pixels.render()?;
 
+        // Report which phase was to blame for a long frame
+        self.frame_timings.render = render_start.elapsed();
+        let threshold = Duration::from_secs_f32(LONG_FRAME_FACTOR / FPS);
+        if let Some((phase, duration)) = self.frame_timings.slow_phase(threshold) {
+            log::warn!(
+                "Long frame: {:?} total, {} phase took {:?}",
+                self.frame_timings.total(),
+                phase,
+                duration
+            );
+        }
+        self.frame_timings = FrameTimings::default();
+
         Ok(())
     }
 
@@ -392,3 +985,31 @@ impl App for Application {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn camera_target_cycling_wraps() {
+        assert_eq!(Application::next_camera_target(0, 3), 1);
+        assert_eq!(Application::next_camera_target(2, 3), 0);
+        assert_eq!(Application::next_camera_target(0, 0), 0);
+    }
+
+    #[test]
+    fn pause_overlay_darkens_without_replacing() {
+        let config = RenderConfig::new(64, 48);
+        let app = Application::new(config).unwrap();
+        let mut frame = vec![200; (config.width * config.height * 4) as usize];
+        app.draw_pause_overlay(&mut frame);
+
+        // The corner is away from the label, so it keeps a dimmed copy
+        assert!((70..=90).contains(&frame[0]));
+        assert_eq!(frame[3], 200);
+        assert!(frame.chunks_exact(4).all(|pixel| pixel[0] >= 70));
+        // The label is drawn on top of the dimmed frame
+        assert!(frame.chunks_exact(4).any(|pixel| pixel[0] > 200));
+    }
+}
@@ -0,0 +1,173 @@
+//! Sprite sheet animations
+//!
+//! An animated texture is a regular texture holding every frame of an
+//! animation in a grid, read left to right and top to bottom.
+
+use super::Texture;
+use anyhow::{ensure, Result};
+
+/// A looping animation stored in a sprite sheet
+///
+/// AnimatedTexture provides:
+/// * The sprite sheet texture
+/// * The frame currently shown at a given time
+/// * The source rectangle of that frame within the sheet
+#[derive(Debug, Clone)]
+pub struct AnimatedTexture {
+    /// Sprite sheet holding all frames
+    texture: Texture,
+    /// Width of a single frame in pixels
+    frame_width: u32,
+    /// Height of a single frame in pixels
+    frame_height: u32,
+    /// Number of frames in the animation
+    frame_count: u32,
+    /// Playback speed in frames per second
+    fps: f32,
+}
+
+impl AnimatedTexture {
+    /// Creates an animation from a sprite sheet
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - Sprite sheet with the frames in a grid, row by row
+    /// * `frame_width` - Width of a single frame in pixels
+    /// * `frame_height` - Height of a single frame in pixels
+    /// * `frame_count` - Number of frames, the sheet may hold more
+    /// * `fps` - Playback speed in frames per second, clamped to ≥ 0
+    ///
+    /// # Errors
+    ///
+    /// If a frame is empty, there are no frames or the sheet is too
+    /// small to hold `frame_count` frames
+    pub fn new(
+        texture: Texture,
+        frame_width: u32,
+        frame_height: u32,
+        frame_count: u32,
+        fps: f32,
+    ) -> Result<Self> {
+        ensure!(
+            frame_width > 0 && frame_height > 0,
+            "Animation frames must not be empty, got {}x{}",
+            frame_width,
+            frame_height
+        );
+        ensure!(frame_count > 0, "Animation needs at least one frame");
+
+        let capacity = (texture.width() / frame_width) * (texture.height() / frame_height);
+        ensure!(
+            frame_count <= capacity,
+            "A {}x{} sprite sheet holds {} frames of {}x{}, not {}",
+            texture.width(),
+            texture.height(),
+            capacity,
+            frame_width,
+            frame_height,
+            frame_count
+        );
+
+        Ok(Self {
+            texture,
+            frame_width,
+            frame_height,
+            frame_count,
+            fps: if fps.is_nan() { 0.0 } else { fps.max(0.0) },
+        })
+    }
+
+    /// Returns the sprite sheet
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Returns the number of frames
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Returns the playback speed in frames per second
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+
+    /// Returns the seconds one loop of the animation takes, infinite when paused
+    pub fn cycle_duration(&self) -> f32 {
+        self.frame_count as f32 / self.fps
+    }
+
+    /// Returns the index of the frame shown at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Seconds since the animation started, negative or
+    ///   non-finite times show the first frame
+    pub fn frame_index(&self, time: f32) -> u32 {
+        let frame = (time * self.fps).floor();
+        if !frame.is_finite() || frame < 0.0 {
+            return 0;
+        }
+        (frame as u64 % self.frame_count as u64) as u32
+    }
+
+    /// Returns the source rectangle of the frame shown at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Seconds since the animation started
+    ///
+    /// # Returns
+    ///
+    /// `(x, y, width, height)` of the frame within the sprite sheet in pixels
+    pub fn frame_uv(&self, time: f32) -> (u32, u32, u32, u32) {
+        let columns = self.texture.width() / self.frame_width;
+        let frame = self.frame_index(time);
+        (
+            (frame % columns) * self.frame_width,
+            (frame / columns) * self.frame_height,
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4x2 sheet of 2x1 frames
+    fn sheet() -> Texture {
+        Texture::from_rgba(4, 2, vec![0; 4 * 2 * 4]).unwrap()
+    }
+
+    #[test]
+    fn new_checks_the_sheet() {
+        assert!(AnimatedTexture::new(sheet(), 2, 1, 4, 10.0).is_ok());
+        assert!(AnimatedTexture::new(sheet(), 2, 1, 5, 10.0).is_err());
+        assert!(AnimatedTexture::new(sheet(), 0, 1, 1, 10.0).is_err());
+        assert!(AnimatedTexture::new(sheet(), 2, 1, 0, 10.0).is_err());
+
+        let frozen = AnimatedTexture::new(sheet(), 2, 1, 4, f32::NAN).unwrap();
+        assert_eq!(frozen.fps(), 0.0);
+    }
+
+    #[test]
+    fn frames_loop_row_by_row() {
+        let animation = AnimatedTexture::new(sheet(), 2, 1, 3, 10.0).unwrap();
+        assert_eq!(animation.frame_uv(0.0), (0, 0, 2, 1));
+        assert_eq!(animation.frame_uv(0.1), (2, 0, 2, 1));
+        assert_eq!(animation.frame_uv(0.2), (0, 1, 2, 1));
+        assert_eq!(animation.frame_index(0.3), 0);
+        assert!((animation.cycle_duration() - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bad_times_show_the_first_frame() {
+        let animation = AnimatedTexture::new(sheet(), 2, 1, 4, 10.0).unwrap();
+        assert_eq!(animation.frame_index(-1.0), 0);
+        assert_eq!(animation.frame_index(f32::NAN), 0);
+        assert_eq!(animation.frame_index(f32::INFINITY), 0);
+        assert!(animation.frame_index(1e12) < 4);
+    }
+}
@@ -3,6 +3,8 @@ use crate::consts::{ALL_ASSET_FILES, HALO_DEK_FONT_FILE};
 use include_assets::{include_dir, NamedArchive};
 use rusttype::Font;
 use std::collections::HashMap;
+#[cfg(feature = "dev")]
+use std::{path::PathBuf, time::SystemTime};
 
 /// Asset management system with compile-time loading and constant-time lookups.
 ///
@@ -10,15 +12,32 @@ use std::collections::HashMap;
 /// - Assets are embedded in binary at compile time from `assets` directory
 /// - File names are checked at compile time via build.rs constants
 /// - All operations using generated constants are guaranteed safe
+/// - With the `dev` feature, textures are also loaded from an asset
+///   directory on disk and reloaded when the files change, see
+///   [`AssetManager::reload_changed`]
 pub struct AssetManager {
     textures: HashMap<String, Texture>,
     font: Font<'static>,
+    /// Directory textures are reloaded from
+    #[cfg(feature = "dev")]
+    asset_dir: PathBuf,
+    /// Modification time of every texture file when it was last loaded
+    #[cfg(feature = "dev")]
+    modified: HashMap<String, SystemTime>,
+}
+
+impl Default for AssetManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AssetManager {
     /// Creates a new empty AssetManager instance.
     ///
     /// Initializes the internal archive with assets embedded at compile time.
+    /// With the `dev` feature the textures in the crate's `assets` directory
+    /// on disk replace the embedded ones.
     pub fn new() -> Self {
         let assets = NamedArchive::load(include_dir!("assets"));
 
@@ -43,7 +62,88 @@ impl AssetManager {
         let font =
             Font::try_from_bytes(font_data_static).expect("error constructing a Font from bytes");
 
-        Self { textures, font }
+        let mut manager = Self {
+            textures,
+            font,
+            #[cfg(feature = "dev")]
+            asset_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/assets")),
+            #[cfg(feature = "dev")]
+            modified: HashMap::new(),
+        };
+        manager.reload_changed();
+        manager
+    }
+
+    /// Watches a different directory for textures
+    ///
+    /// Every image in the directory is loaded right away, later changes are
+    /// picked up by [`AssetManager::reload_changed`].
+    ///
+    /// # Arguments
+    /// * `asset_dir` - Directory holding the texture files
+    #[cfg(feature = "dev")]
+    pub fn with_asset_dir(mut self, asset_dir: impl Into<PathBuf>) -> Self {
+        self.asset_dir = asset_dir.into();
+        self.modified.clear();
+        self.reload_changed();
+        self
+    }
+
+    /// Reloads the textures whose files changed on disk.
+    ///
+    /// Only does something with the `dev` feature. Files that are new since
+    /// the last check are loaded too, files that fail to load keep their
+    /// previous texture and are retried once they change again.
+    ///
+    /// # Returns
+    /// - Names of the textures that were (re)loaded, empty without `dev`
+    pub fn reload_changed(&mut self) -> Vec<String> {
+        #[cfg(feature = "dev")]
+        return self.reload_from_disk();
+        #[cfg(not(feature = "dev"))]
+        Vec::new()
+    }
+
+    /// Loads every image in the asset directory that is newer than its texture
+    #[cfg(feature = "dev")]
+    fn reload_from_disk(&mut self) -> Vec<String> {
+        let entries = match std::fs::read_dir(&self.asset_dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::warn!("Can't watch {}: {}", self.asset_dir.display(), err);
+                return Vec::new();
+            }
+        };
+
+        let mut reloaded = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if image::ImageFormat::from_path(&path).is_err() {
+                continue;
+            }
+            let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) else {
+                continue;
+            };
+            if self.modified.get(name) == Some(&modified) {
+                continue;
+            }
+
+            // Remember the time even on failure so a broken file isn't retried every frame
+            self.modified.insert(name.to_string(), modified);
+            match image::open(&path) {
+                Ok(image) => {
+                    log::info!("Reloaded texture {}", name);
+                    self.textures
+                        .insert(name.to_string(), Texture::from_image(image));
+                    reloaded.push(name.to_string());
+                }
+                Err(err) => log::warn!("Failed to reload {}: {}", name, err),
+            }
+        }
+        reloaded
     }
 
     /// Gets a cached texture by name.
@@ -58,6 +158,18 @@ impl AssetManager {
         self.textures.get(name).expect("Texture not found")
     }
 
+    /// Gets a cached texture by name, if there is one.
+    ///
+    /// # Arguments
+    /// * `name` - Asset name, e.g. one chosen at runtime
+    ///
+    /// # Returns
+    /// - Some(Texture) if the asset is loaded
+    /// - None if no texture has that name
+    pub fn try_get_texture(&self, name: &str) -> Option<&Texture> {
+        self.textures.get(name)
+    }
+
     /// Gets a cached font by name.
     ///
     /// # Arguments
@@ -66,7 +178,30 @@ impl AssetManager {
     /// # Returns
     /// - Some(Font) if the asset is loaded
     /// - None if the asset is not loaded
-    pub fn get_font(&self) -> &Font {
+    pub fn get_font(&self) -> &Font<'_> {
         &self.font
     }
 }
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reload_picks_up_new_files() {
+        let dir = std::env::temp_dir().join(format!("modeseven-assets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut manager = AssetManager::new().with_asset_dir(&dir);
+        assert!(manager.try_get_texture("hot.png").is_none());
+
+        // A file written after construction is loaded once, on the next check
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 0, 0, 255]))
+            .save(dir.join("hot.png"))
+            .unwrap();
+        assert_eq!(manager.reload_changed(), vec!["hot.png".to_string()]);
+        assert_eq!(manager.get_texture("hot.png").width(), 3);
+        assert!(manager.reload_changed().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,5 +6,7 @@
 mod manager;
 pub use manager::AssetManager;
 
+pub mod animated_texture;
 pub mod texture;
-pub use texture::Texture;
+pub use animated_texture::AnimatedTexture;
+pub use texture::{SampleMode, Texture, WrapMode};
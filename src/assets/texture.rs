@@ -2,19 +2,46 @@
 //!
 //! Texture provides:
 //! * RGBA pixel storage (4 bytes per pixel)
-//! * Nearest-neighbor and bilinear sampling
+//! * Nearest-neighbor and bilinear sampling, clamped or tiled
+//! * Bilinear resizing
+//! * Tinted and faded copies
 //! * Image file loading with format conversion
-//! * Debug checkerboard pattern generation
+//! * Construction from raw RGBA buffers
+//! * Debug checkerboard pattern generation in any two colors
 
+use anyhow::{ensure, Result};
 use image::GenericImageView as _;
 
+/// How texels are filtered when sampling a texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleMode {
+    /// Pick the texel the coordinate falls in
+    Nearest,
+    /// Blend the four texels around the coordinate
+    #[default]
+    Bilinear,
+}
+
+/// What sampling does outside the texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// Return the background color, edge texels blend with themselves
+    #[default]
+    Clamp,
+    /// Tile the texture infinitely in both directions
+    Repeat,
+}
+
 /// A 2D texture with RGBA pixels and sampling support
 ///
 /// Texture provides:
 /// * RGBA pixel storage (4 bytes per pixel)
 /// * Nearest-neighbor and bilinear sampling
+/// * Bilinear resizing
+/// * Tinted and faded copies
 /// * Image file loading with format conversion
-/// * Debug checkerboard pattern generation
+/// * Construction from raw RGBA buffers
+/// * Debug checkerboard pattern generation in any two colors
 ///
 /// Non-RGBA images are automatically converted during loading.
 #[derive(Debug, Clone)]
@@ -39,6 +66,35 @@ impl Texture {
         }
     }
 
+    /// Creates a texture from a raw RGBA buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Texture width in pixels
+    /// * `height` - Texture height in pixels
+    /// * `pixels` - Row-major RGBA bytes, 4 per pixel
+    ///
+    /// # Errors
+    ///
+    /// If `pixels` doesn't hold exactly `width * height * 4` bytes
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self> {
+        let expected = width as usize * height as usize * 4;
+        ensure!(
+            pixels.len() == expected,
+            "RGBA buffer for a {}x{} texture must be {} bytes, got {}",
+            width,
+            height,
+            expected,
+            pixels.len()
+        );
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
     /// Creates a test checkerboard pattern texture
     ///
     /// # Arguments
@@ -49,15 +105,43 @@ impl Texture {
     ///
     /// # Returns
     ///
-    /// A new black and white checkerboard texture
+    /// A new black and white checkerboard texture, white in the top-left corner
     pub fn checkerboard(width: u32, height: u32, checker_size: u32) -> Self {
+        Self::checkerboard_colored(
+            width,
+            height,
+            checker_size,
+            [255, 255, 255, 255],
+            [0, 0, 0, 255],
+        )
+    }
+
+    /// Creates a checkerboard pattern texture with custom colors
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Texture width in pixels
+    /// * `height` - Texture height in pixels
+    /// * `checker_size` - Size of each checker square
+    /// * `a` - Color of the top-left checker and every other one
+    /// * `b` - Color of the checkers next to the `a` ones
+    ///
+    /// # Returns
+    ///
+    /// A new two-colored checkerboard texture
+    pub fn checkerboard_colored(
+        width: u32,
+        height: u32,
+        checker_size: u32,
+        a: [u8; 4],
+        b: [u8; 4],
+    ) -> Self {
         let mut pixels = Vec::with_capacity((width * height * 4) as usize);
 
         for y in 0..height {
             for x in 0..width {
-                let checker = ((x / checker_size + y / checker_size) % 2) as u8;
-                let color = if checker == 0 { 255 } else { 0 };
-                pixels.extend_from_slice(&[color, color, color, 255]);
+                let checker = (x / checker_size + y / checker_size) % 2;
+                pixels.extend_from_slice(if checker == 0 { &a } else { &b });
             }
         }
 
@@ -80,30 +164,33 @@ impl Texture {
     ///
     /// RGBA color array at the sampled position
     pub fn sample(&self, x: f32, y: f32, bg_color: [u8; 4]) -> [u8; 4] {
-        // Check if coordinates are within bounds
-        if x < 0.0 || x >= self.width as f32 || y < 0.0 || y >= self.height as f32 {
-            return bg_color;
-        }
-
-        // Convert to integers for nearest-neighbor sampling
-        let x = x as u32;
-        let y = y as u32;
-
-        // Calculate pixel index in RGBA array
-        let idx = ((y * self.width + x) * 4) as usize;
+        self.sample_mode(x, y, bg_color, SampleMode::Nearest, WrapMode::Clamp)
+    }
 
-        [
-            self.pixels[idx],
-            self.pixels[idx + 1],
-            self.pixels[idx + 2],
-            self.pixels[idx + 3],
-        ]
+    /// Samples a pixel using nearest-neighbor interpolation, tiling the texture
+    ///
+    /// Coordinates outside the texture wrap around to the opposite edge,
+    /// so the texture repeats infinitely in both directions.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate, any finite value
+    /// * `y` - Y coordinate, any finite value
+    ///
+    /// # Returns
+    ///
+    /// RGBA color array at the sampled position
+    pub fn sample_nearest_wrapped(&self, x: f32, y: f32) -> [u8; 4] {
+        self.sample_mode(x, y, [0; 4], SampleMode::Nearest, WrapMode::Repeat)
     }
 
     /// Samples a pixel using bilinear interpolation
     ///
     /// Smoothly interpolates between four adjacent pixels based
-    /// on the fractional coordinate values.
+    /// on the fractional coordinate values. In the last column and row
+    /// (`width - 1 <= x < width`) the missing neighbor is the edge texel
+    /// itself, so edge samples blend toward the edge color and never
+    /// return `bg_color`.
     ///
     /// # Arguments
     ///
@@ -115,28 +202,113 @@ impl Texture {
     ///
     /// Interpolated RGBA color array
     pub fn sample_bilinear(&self, x: f32, y: f32, bg_color: [u8; 4]) -> [u8; 4] {
-        // Check if we're completely outside the texture
-        if x < 0.0 || x >= self.width as f32 || y < 0.0 || y >= self.height as f32 {
-            return bg_color;
+        self.sample_mode(x, y, bg_color, SampleMode::Bilinear, WrapMode::Clamp)
+    }
+
+    /// Samples a pixel using bilinear interpolation, tiling the texture
+    ///
+    /// Coordinates outside the texture wrap around to the opposite edge,
+    /// so the texture repeats infinitely in both directions. Samples in
+    /// the last column and row blend with the first column and row, so
+    /// the seam between two tiles is as smooth as the texture itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate, any finite value
+    /// * `y` - Y coordinate, any finite value
+    ///
+    /// # Returns
+    ///
+    /// Interpolated RGBA color array
+    pub fn sample_bilinear_wrapped(&self, x: f32, y: f32) -> [u8; 4] {
+        self.sample_mode(x, y, [0; 4], SampleMode::Bilinear, WrapMode::Repeat)
+    }
+
+    /// Samples a pixel with an explicit filter and edge behavior
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate
+    /// * `y` - Y coordinate
+    /// * `bg_color` - Color for out-of-bounds samples, only used with
+    ///   [`WrapMode::Clamp`]
+    /// * `sample` - How texels are filtered
+    /// * `wrap` - What happens outside the texture
+    ///
+    /// # Returns
+    ///
+    /// RGBA color array at the sampled position
+    pub fn sample_mode(
+        &self,
+        x: f32,
+        y: f32,
+        bg_color: [u8; 4],
+        sample: SampleMode,
+        wrap: WrapMode,
+    ) -> [u8; 4] {
+        let (x, y) = match wrap {
+            WrapMode::Clamp => {
+                // Check if we're completely outside the texture
+                if x < 0.0 || x >= self.width as f32 || y < 0.0 || y >= self.height as f32 {
+                    return bg_color;
+                }
+                (x, y)
+            }
+            WrapMode::Repeat => {
+                if self.width == 0 || self.height == 0 {
+                    return bg_color;
+                }
+                (
+                    x.rem_euclid(self.width as f32),
+                    y.rem_euclid(self.height as f32),
+                )
+            }
+        };
+
+        // `rem_euclid` can round up to exactly the size, hence the extra wrap
+        let ix = (x as u32).min(self.width - 1);
+        let iy = (y as u32).min(self.height - 1);
+
+        match sample {
+            SampleMode::Nearest => self.texel(ix, iy),
+            SampleMode::Bilinear => {
+                // The neighbors past the last texels are the edge itself or the opposite edge
+                let (x2, y2) = match wrap {
+                    WrapMode::Clamp => {
+                        ((ix + 1).min(self.width - 1), (iy + 1).min(self.height - 1))
+                    }
+                    WrapMode::Repeat => ((ix + 1) % self.width, (iy + 1) % self.height),
+                };
+                self.bilinear(ix, iy, x2, y2, x - x.floor(), y - y.floor())
+            }
         }
+    }
 
-        // Get integer and fractional parts
-        let ix = x.floor();
-        let iy = y.floor();
-        let fx = x - ix;
-        let fy = y - iy;
+    /// Reads the texel at integer coordinates that are known to be in bounds
+    fn texel(&self, x: u32, y: u32) -> [u8; 4] {
+        // Calculate pixel index in RGBA array
+        let idx = ((y * self.width + x) * 4) as usize;
 
-        // Get the four nearest texel coordinates
-        let x1 = ix as u32;
-        let y1 = iy as u32;
-        let x2 = (x1 + 1).min(self.width - 1);
-        let y2 = (y1 + 1).min(self.height - 1);
+        [
+            self.pixels[idx],
+            self.pixels[idx + 1],
+            self.pixels[idx + 2],
+            self.pixels[idx + 3],
+        ]
+    }
 
-        // Sample the four corners
-        let c00 = self.sample(x1 as f32, y1 as f32, bg_color);
-        let c10 = self.sample(x2 as f32, y1 as f32, bg_color);
-        let c01 = self.sample(x1 as f32, y2 as f32, bg_color);
-        let c11 = self.sample(x2 as f32, y2 as f32, bg_color);
+    /// Blends four in-bounds texels by the fractional sample offset
+    ///
+    /// # Arguments
+    ///
+    /// * `x1`, `y1` - Top-left texel
+    /// * `x2`, `y2` - Bottom-right texel
+    /// * `fx`, `fy` - Offset from the top-left texel, 0.0 to 1.0
+    fn bilinear(&self, x1: u32, y1: u32, x2: u32, y2: u32, fx: f32, fy: f32) -> [u8; 4] {
+        let c00 = self.texel(x1, y1);
+        let c10 = self.texel(x2, y1);
+        let c01 = self.texel(x1, y2);
+        let c11 = self.texel(x2, y2);
 
         // Perform bilinear interpolation for each color channel
         let mut result = [0; 4];
@@ -149,6 +321,69 @@ impl Texture {
         result
     }
 
+    /// Creates a resized copy of the texture
+    ///
+    /// Every new texel samples the original bilinearly at the matching
+    /// texel center, so this works for both up- and downscaling and
+    /// resizing to the same size returns an identical copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_width` - Width of the copy in pixels
+    /// * `new_height` - Height of the copy in pixels
+    ///
+    /// # Returns
+    ///
+    /// A new RGBA texture of `new_width`x`new_height`
+    pub fn resize(&self, new_width: u32, new_height: u32) -> Texture {
+        let scale_x = self.width as f32 / new_width as f32;
+        let scale_y = self.height as f32 / new_height as f32;
+        let mut pixels = Vec::with_capacity(new_width as usize * new_height as usize * 4);
+
+        for y in 0..new_height {
+            // Map the texel center back into the original, staying on the texture
+            let src_y = ((y as f32 + 0.5) * scale_y - 0.5).max(0.0);
+            for x in 0..new_width {
+                let src_x = ((x as f32 + 0.5) * scale_x - 0.5).max(0.0);
+                pixels.extend_from_slice(&self.sample_bilinear(src_x, src_y, [0; 4]));
+            }
+        }
+
+        Texture {
+            width: new_width,
+            height: new_height,
+            pixels,
+        }
+    }
+
+    /// Creates a copy with every texel multiplied by a tint color
+    ///
+    /// Each channel is scaled by `tint / 255`, so white keeps the
+    /// original colors and e.g. `[128, 0, 0, 255]` keeps half the red
+    /// and drops green and blue.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - RGBA tint
+    pub fn tinted(&self, color: [u8; 4]) -> Texture {
+        let mut texture = self.clone();
+        for texel in texture.pixels.chunks_exact_mut(4) {
+            for (channel, tint) in texel.iter_mut().zip(color) {
+                *channel = (*channel as u16 * tint as u16 / 255) as u8;
+            }
+        }
+        texture
+    }
+
+    /// Creates a copy with the alpha channel scaled by `alpha / 255`
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - Opacity of the copy, 255 keeps the original alpha
+    pub fn with_alpha(&self, alpha: u8) -> Texture {
+        self.tinted([255, 255, 255, alpha])
+    }
+
     /// Get the width of the texture
     pub fn width(&self) -> u32 {
         self.width
@@ -159,3 +394,98 @@ impl Texture {
         self.height
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x2 texture whose red channel is `10 * index` and green is 255
+    fn ramp() -> Texture {
+        let pixels = (0..6).flat_map(|i| [i * 10, 255, 0, 255]).collect();
+        Texture::from_rgba(3, 2, pixels).unwrap()
+    }
+
+    #[test]
+    fn from_rgba_checks_the_buffer_size() {
+        assert!(Texture::from_rgba(3, 2, vec![0; 24]).is_ok());
+        assert!(Texture::from_rgba(3, 2, vec![0; 23]).is_err());
+        assert!(Texture::from_rgba(0, 0, Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn non_square_sampling_clamps_to_each_edge() {
+        let texture = ramp();
+        let bg = [1, 2, 3, 4];
+        assert_eq!(texture.sample(2.9, 1.9, bg)[0], 50);
+        assert_eq!(texture.sample(3.0, 0.0, bg), bg);
+        assert_eq!(texture.sample(0.0, 2.0, bg), bg);
+
+        // The last column and row blend with themselves, not the next row
+        assert_eq!(texture.sample_bilinear(2.5, 0.0, bg)[0], 20);
+        assert_eq!(texture.sample_bilinear(0.0, 1.5, bg)[0], 30);
+        assert_eq!(texture.sample_bilinear(0.5, 0.0, bg)[0], 5);
+    }
+
+    #[test]
+    fn wrapped_sampling_tiles() {
+        let texture = ramp();
+        assert_eq!(texture.sample_nearest_wrapped(-0.5, 0.0)[0], 20);
+        assert_eq!(texture.sample_nearest_wrapped(4.0, 3.0)[0], 40);
+        // Bilinear wraps into the opposite edge
+        assert_eq!(texture.sample_bilinear_wrapped(2.5, 0.0)[0], 10);
+
+        let empty = Texture::from_rgba(0, 0, Vec::new()).unwrap();
+        assert_eq!(empty.sample_nearest_wrapped(1.0, 1.0), [0; 4]);
+    }
+
+    #[test]
+    fn resize_keeps_flat_colors() {
+        let texture = Texture::checkerboard_colored(8, 4, 8, [9, 8, 7, 255], [0; 4]);
+        let resized = texture.resize(3, 5);
+        assert_eq!((resized.width(), resized.height()), (3, 5));
+        assert!(resized
+            .pixels
+            .chunks_exact(4)
+            .all(|texel| texel == [9, 8, 7, 255]));
+    }
+
+    #[test]
+    fn checkerboard_alternates_colors() {
+        let a = [255, 0, 0, 255];
+        let b = [0, 0, 255, 255];
+        let texture = Texture::checkerboard_colored(4, 4, 2, a, b);
+        assert_eq!(texture.sample(0.0, 0.0, [0; 4]), a);
+        assert_eq!(texture.sample(2.0, 0.0, [0; 4]), b);
+        assert_eq!(texture.sample(2.0, 2.0, [0; 4]), a);
+    }
+
+    #[test]
+    fn tint_multiplies_channels() {
+        let texture = Texture::from_rgba(1, 1, vec![200, 100, 255, 255]).unwrap();
+        let tinted = texture.tinted([255, 128, 0, 255]);
+        assert_eq!(tinted.pixels, vec![200, 50, 0, 255]);
+        assert_eq!(texture.with_alpha(51).pixels, vec![200, 100, 255, 51]);
+    }
+
+    #[test]
+    fn sample_mode_covers_every_combination() {
+        use SampleMode::*;
+        use WrapMode::*;
+
+        let texture = ramp();
+        let bg = [1, 2, 3, 4];
+        // (sample, wrap, interior, seam at the right edge, just past the seam)
+        let cases = [
+            (Nearest, Clamp, 10, 20, 1),
+            (Nearest, Repeat, 10, 20, 0),
+            (Bilinear, Clamp, 30, 20, 1),
+            (Bilinear, Repeat, 30, 10, 5),
+        ];
+        for (sample, wrap, interior, seam, past) in cases {
+            let red = |x, y| texture.sample_mode(x, y, bg, sample, wrap)[0];
+            assert_eq!(red(1.5, 0.5), interior, "{sample:?} {wrap:?}");
+            assert_eq!(red(2.5, 0.0), seam, "{sample:?} {wrap:?}");
+            assert_eq!(red(3.5, 0.0), past, "{sample:?} {wrap:?}");
+        }
+    }
+}
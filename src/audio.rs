@@ -0,0 +1,336 @@
+//! Sound effects and the engine sounds
+//!
+//! Playback needs the `audio` feature and a working output device. Without
+//! either, `Audio` silently does nothing so the game runs the same.
+
+/// Engine tone frequency while standing still in Hz
+#[cfg(feature = "audio")]
+const ENGINE_FREQUENCY: f32 = 55.0;
+
+/// Engine pitch while standing still
+const ENGINE_IDLE_PITCH: f32 = 1.0;
+
+/// Engine pitch at the car's top speed
+const ENGINE_REDLINE_PITCH: f32 = 2.0;
+
+/// Engine loudness relative to the other sound effects
+#[cfg(feature = "audio")]
+const ENGINE_VOLUME: f32 = 0.15;
+
+/// Maps a car's speed to its engine pitch
+///
+/// # Arguments
+///
+/// * `speed_ratio` - Speed divided by the car's top speed, clamped to
+///   0.0..=1.0 so boosts don't go past the redline
+///
+/// # Returns
+///
+/// Playback speed of the engine tone, 1.0 at idle up to 2.0 at the redline
+pub fn engine_pitch(speed_ratio: f32) -> f32 {
+    let ratio = if speed_ratio.is_nan() {
+        0.0
+    } else {
+        speed_ratio.abs().min(1.0)
+    };
+    ENGINE_IDLE_PITCH + (ENGINE_REDLINE_PITCH - ENGINE_IDLE_PITCH) * ratio
+}
+
+/// Maps a horizontal screen position to a stereo pan
+///
+/// # Arguments
+///
+/// * `screen_x` - Pixel column, 0 being the left edge
+/// * `viewport_width` - Width of the view in pixels
+///
+/// # Returns
+///
+/// -1.0 (left) at the left edge, 0.0 in the center and 1.0 (right) at
+/// the right edge, clamped to that range
+pub fn screen_pan(screen_x: f32, viewport_width: f32) -> f32 {
+    if viewport_width <= 0.0 || screen_x.is_nan() {
+        return 0.0;
+    }
+    (screen_x / viewport_width * 2.0 - 1.0).clamp(-1.0, 1.0)
+}
+
+/// Pitch and pan of one player's engine
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EngineVoice {
+    /// Playback speed of the engine tone, 1.0 at idle
+    pitch: f32,
+    /// Stereo position from -1.0 (left) to 1.0 (right)
+    pan: f32,
+}
+
+/// A one-shot sound effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sfx {
+    /// Short blip when a menu item is activated
+    MenuSelect,
+}
+
+/// Audio output of the game
+///
+/// Audio provides:
+/// * A looping engine sound per player, pitched by speed and panned in stereo
+/// * One-shot sound effects
+/// * Master and effect volumes, both 0.0 to 1.0
+pub struct Audio {
+    /// Volume applied to all sounds
+    master_volume: f32,
+    /// Volume applied to sound effects and the engine
+    sfx_volume: f32,
+    /// Engine of each player, indexed by player
+    engines: Vec<EngineVoice>,
+    /// Open output device, None for the no-op fallback
+    #[cfg(feature = "audio")]
+    output: Option<Output>,
+}
+
+impl Default for Audio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Audio {
+    /// Opens the default output device
+    ///
+    /// Falls back to a silent no-op backend when the `audio` feature is
+    /// disabled or no output device is available.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "audio")]
+            output: Output::new(),
+            ..Self::silent()
+        }
+    }
+
+    /// Creates the no-op backend without touching any output device
+    pub fn silent() -> Self {
+        Self {
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            engines: Vec::new(),
+            #[cfg(feature = "audio")]
+            output: None,
+        }
+    }
+
+    /// Returns whether sounds are actually played
+    pub fn is_enabled(&self) -> bool {
+        #[cfg(feature = "audio")]
+        return self.output.is_some();
+        #[cfg(not(feature = "audio"))]
+        false
+    }
+
+    /// Returns the master volume
+    pub fn master_volume(&self) -> f32 {
+        self.master_volume
+    }
+
+    /// Sets the master volume, clamped to 0.0..=1.0
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.master_volume = Self::clamp_volume(volume);
+        self.apply_volumes();
+    }
+
+    /// Returns the sound effect volume
+    pub fn sfx_volume(&self) -> f32 {
+        self.sfx_volume
+    }
+
+    /// Sets the sound effect volume, clamped to 0.0..=1.0
+    pub fn set_sfx_volume(&mut self, volume: f32) {
+        self.sfx_volume = Self::clamp_volume(volume);
+        self.apply_volumes();
+    }
+
+    /// Returns the engine pitch of a player, 1.0 while standing still
+    pub fn engine_pitch(&self, player: usize) -> f32 {
+        self.engines
+            .get(player)
+            .map_or(ENGINE_IDLE_PITCH, |engine| engine.pitch)
+    }
+
+    /// Returns the stereo pan of a player's engine, 0.0 being centered
+    pub fn engine_pan(&self, player: usize) -> f32 {
+        self.engines.get(player).map_or(0.0, |engine| engine.pan)
+    }
+
+    /// Plays a one-shot sound effect
+    pub fn play_sfx(&self, sfx: Sfx) {
+        #[cfg(feature = "audio")]
+        if let Some(output) = &self.output {
+            output.play_sfx(sfx);
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = sfx;
+    }
+
+    /// Starts a player's engine sound if needed and sets its pitch and pan
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - Index of the player whose car it is
+    /// * `speed_ratio` - Car speed divided by its top speed, see [`engine_pitch`]
+    /// * `pan` - Stereo position from -1.0 (left) to 1.0 (right), see [`screen_pan`]
+    pub fn set_engine(&mut self, player: usize, speed_ratio: f32, pan: f32) {
+        let voice = EngineVoice {
+            pitch: engine_pitch(speed_ratio),
+            pan: if pan.is_nan() {
+                0.0
+            } else {
+                pan.clamp(-1.0, 1.0)
+            },
+        };
+        if self.engines.len() <= player {
+            self.engines.resize(
+                player + 1,
+                EngineVoice {
+                    pitch: ENGINE_IDLE_PITCH,
+                    pan: 0.0,
+                },
+            );
+        }
+        self.engines[player] = voice;
+
+        #[cfg(feature = "audio")]
+        if let Some(output) = &mut self.output {
+            let volume = self.master_volume * self.sfx_volume * ENGINE_VOLUME;
+            output.set_engine(player, voice, volume);
+        }
+    }
+
+    /// Silences all engine sounds until the next `set_engine`
+    pub fn pause_engine(&mut self) {
+        #[cfg(feature = "audio")]
+        if let Some(output) = &self.output {
+            for engine in &output.engines {
+                engine.pause();
+            }
+        }
+    }
+
+    /// Clamps a volume to 0.0..=1.0, treating NaN as silence
+    fn clamp_volume(volume: f32) -> f32 {
+        if volume.is_nan() {
+            0.0
+        } else {
+            volume.clamp(0.0, 1.0)
+        }
+    }
+
+    /// Pushes the current volumes to the output
+    fn apply_volumes(&self) {
+        #[cfg(feature = "audio")]
+        if let Some(output) = &self.output {
+            let volume = self.master_volume * self.sfx_volume;
+            for engine in &output.engines {
+                engine.set_volume(volume * ENGINE_VOLUME);
+            }
+            output.sfx.set_volume(volume);
+        }
+    }
+}
+
+/// Open rodio output stream with one sink per sound kind
+#[cfg(feature = "audio")]
+struct Output {
+    /// Keeps the output device open, dropping it stops all sounds
+    _stream: rodio::OutputStream,
+    /// Handle new engine sinks are opened on
+    handle: rodio::OutputStreamHandle,
+    /// Looping engine tone of each player, paused outside races
+    engines: Vec<rodio::SpatialSink>,
+    /// Queue of one-shot sound effects
+    sfx: rodio::Sink,
+}
+
+#[cfg(feature = "audio")]
+impl Output {
+    /// Position of the left ear
+    const LEFT_EAR: [f32; 3] = [-1.0, 0.0, 0.0];
+
+    /// Position of the right ear
+    const RIGHT_EAR: [f32; 3] = [1.0, 0.0, 0.0];
+
+    /// Opens the default output device
+    ///
+    /// # Returns
+    ///
+    /// None if no output device is available on this system
+    fn new() -> Option<Self> {
+        match Self::open() {
+            Ok(output) => Some(output),
+            Err(err) => {
+                log::warn!("Audio unavailable: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Opens the default output device
+    fn open() -> anyhow::Result<Self> {
+        let (stream, handle) = rodio::OutputStream::try_default()?;
+        let sfx = rodio::Sink::try_new(&handle)?;
+
+        Ok(Self {
+            _stream: stream,
+            handle,
+            engines: Vec::new(),
+            sfx,
+        })
+    }
+
+    /// Updates a player's engine tone, starting it on first use
+    ///
+    /// The tone is placed on the line between the ears, so a pan of
+    /// -1.0 puts it right at the left ear.
+    fn set_engine(&mut self, player: usize, voice: EngineVoice, volume: f32) {
+        use rodio::source::{Function, SignalGenerator};
+
+        while self.engines.len() <= player {
+            let engine = match rodio::SpatialSink::try_new(
+                &self.handle,
+                [0.0, 0.0, 0.0],
+                Self::LEFT_EAR,
+                Self::RIGHT_EAR,
+            ) {
+                Ok(engine) => engine,
+                Err(err) => {
+                    log::warn!("Engine sound unavailable: {}", err);
+                    return;
+                }
+            };
+            engine.set_volume(volume);
+            engine.append(SignalGenerator::new(
+                rodio::cpal::SampleRate(44_100),
+                ENGINE_FREQUENCY,
+                Function::Sawtooth,
+            ));
+            self.engines.push(engine);
+        }
+
+        let engine = &self.engines[player];
+        engine.set_emitter_position([voice.pan, 0.0, 0.0]);
+        engine.set_speed(voice.pitch);
+        engine.play();
+    }
+
+    /// Queues a sound effect
+    fn play_sfx(&self, sfx: Sfx) {
+        use rodio::Source;
+        use std::time::Duration;
+
+        match sfx {
+            Sfx::MenuSelect => self.sfx.append(
+                rodio::source::SineWave::new(880.0)
+                    .take_duration(Duration::from_millis(60))
+                    .amplify(0.3),
+            ),
+        }
+    }
+}
@@ -32,5 +32,8 @@ pub const FPS: f32 = 144.0;
 /// the time step used for physics/game logic updates.
 pub const MAX_LAG_TIME: f32 = 0.1;
 
+/// Time step in seconds used for fixed-step (deterministic) world updates.
+pub const FIXED_DT: f32 = 1.0 / 120.0;
+
 // Include generated constants
 include!(concat!(env!("OUT_DIR"), "/filename_consts.rs"));
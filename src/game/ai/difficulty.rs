@@ -0,0 +1,102 @@
+use crate::game::world::Car;
+
+/// How hard the computer opponent is to beat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// Slower car and sluggish steering
+    Easy,
+    /// Same car as the players
+    #[default]
+    Normal,
+    /// Faster car and sharper steering
+    Hard,
+}
+
+impl Difficulty {
+    /// Parses a difficulty from its menu name, e.g. `"Hard"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Easy" => Some(Difficulty::Easy),
+            "Normal" => Some(Difficulty::Normal),
+            "Hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Returns the name shown in menus
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// Returns the factor on the opponent's top speed
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.85,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.15,
+        }
+    }
+
+    /// Returns the factor on the opponent's acceleration
+    pub fn acceleration_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.85,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.2,
+        }
+    }
+
+    /// Returns the factor on how sharply the opponent steers towards its target
+    pub fn reaction_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.7,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+        }
+    }
+
+    /// Tunes a car for this difficulty
+    ///
+    /// The car's top speed and acceleration are set from `stock` scaled
+    /// by the multipliers, so applying a difficulty again doesn't stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `car` - Car to tune, e.g. the computer opponent's
+    /// * `stock` - Car with the untuned values
+    pub fn apply(&self, car: &mut Car, stock: &Car) {
+        car.set_max_speed(stock.max_speed() * self.speed_multiplier());
+        car.set_acceleration(stock.acceleration() * self.acceleration_multiplier());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            assert_eq!(Difficulty::from_name(difficulty.name()), Some(difficulty));
+        }
+        assert_eq!(Difficulty::from_name("easy"), None);
+    }
+
+    #[test]
+    fn apply_scales_from_the_stock_car() {
+        let stock = Car::new(0.0, 0.0);
+        let mut car = stock.clone();
+        Difficulty::Hard.apply(&mut car, &stock);
+        Difficulty::Hard.apply(&mut car, &stock);
+        assert_eq!(car.max_speed(), stock.max_speed() * 1.15);
+
+        Difficulty::Normal.apply(&mut car, &stock);
+        assert_eq!(car.max_speed(), stock.max_speed());
+        assert_eq!(car.acceleration(), stock.acceleration());
+    }
+}
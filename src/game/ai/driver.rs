@@ -0,0 +1,192 @@
+use super::Difficulty;
+use crate::game::track::RacingLine;
+use crate::game::world::{Car, CarInput};
+use glam::Vec2;
+
+/// Distance at which a waypoint counts as reached
+const WAYPOINT_RADIUS: f32 = 60.0;
+
+/// Speed above which the driver brakes when not lined up with the target
+const CORNER_SPEED: f32 = 90.0;
+
+/// How strongly the heading error is turned into steering by default
+const STEER_GAIN: f32 = 3.0;
+
+/// Minimum `forward · to_target` for the driver to go full throttle
+const ALIGNED: f32 = 0.7;
+
+/// Waypoint-following driver for one car
+///
+/// AiDriver provides:
+/// * Steering towards the current waypoint
+/// * Full throttle when lined up, braking into sharp turns
+/// * Advancing to the next waypoint once the current one is reached or passed
+#[derive(Debug, Clone, PartialEq)]
+pub struct AiDriver {
+    /// Waypoints in driving order, looped
+    waypoints: Vec<Vec2>,
+    /// Index of the waypoint currently driven towards
+    target: usize,
+    /// How strongly the heading error is turned into steering
+    steer_gain: f32,
+}
+
+impl AiDriver {
+    /// Creates a driver heading for the first waypoint
+    ///
+    /// # Arguments
+    ///
+    /// * `waypoints` - Points to drive through in order, looped
+    pub fn new(waypoints: Vec<Vec2>) -> Self {
+        Self {
+            waypoints,
+            target: 0,
+            steer_gain: STEER_GAIN,
+        }
+    }
+
+    /// Creates a driver following a racing line
+    ///
+    /// Starts at the waypoint after the one nearest to the car, so a car
+    /// on the grid doesn't turn around for a point just behind it.
+    pub fn from_racing_line(car: &Car, racing_line: &RacingLine) -> Self {
+        let waypoints = racing_line.points().to_vec();
+        let nearest = waypoints
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let da = a.distance_squared(car.position());
+                let db = b.distance_squared(car.position());
+                da.total_cmp(&db)
+            })
+            .map_or(0, |(i, _)| i);
+
+        Self {
+            target: (nearest + 1) % waypoints.len().max(1),
+            waypoints,
+            steer_gain: STEER_GAIN,
+        }
+    }
+
+    /// Returns how strongly the heading error is turned into steering
+    pub fn steer_gain(&self) -> f32 {
+        self.steer_gain
+    }
+
+    /// Sets how strongly the heading error is turned into steering
+    ///
+    /// Higher values react faster to turns. Negative values are clamped
+    /// to 0.0 (no steering).
+    pub fn set_steer_gain(&mut self, steer_gain: f32) {
+        self.steer_gain = steer_gain.max(0.0);
+    }
+
+    /// Scales the steering reaction by a difficulty, see [`Difficulty::reaction_multiplier`]
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.set_steer_gain(STEER_GAIN * difficulty.reaction_multiplier());
+        self
+    }
+
+    /// Returns the waypoints in driving order
+    pub fn waypoints(&self) -> &[Vec2] {
+        &self.waypoints
+    }
+
+    /// Returns the waypoint currently driven towards, if there are any
+    pub fn target(&self) -> Option<Vec2> {
+        self.waypoints.get(self.target).copied()
+    }
+
+    /// Moves on to the next waypoint once the current one is done
+    ///
+    /// A waypoint is done once the car is within reach of it or has
+    /// driven past it towards the waypoint after.
+    pub fn advance(&mut self, car: &Car) {
+        let count = self.waypoints.len();
+        if count == 0 {
+            return;
+        }
+
+        let target = self.waypoints[self.target];
+        let next = self.waypoints[(self.target + 1) % count];
+        let passed = (car.position() - target).dot(next - target) > 0.0;
+        if target.distance(car.position()) < WAYPOINT_RADIUS || passed {
+            self.target = (self.target + 1) % count;
+        }
+    }
+
+    /// Computes the controls for driving towards the current waypoint
+    ///
+    /// Steers by the side the waypoint is on (`perp_dot`) and goes full
+    /// throttle when lined up with it (`dot`). Not lined up, the driver
+    /// brakes above `CORNER_SPEED` and eases off the throttle below it.
+    ///
+    /// # Returns
+    ///
+    /// Released controls if there are no waypoints
+    pub fn compute_input(&self, car: &Car) -> CarInput {
+        let Some(target) = self.target() else {
+            return CarInput::default();
+        };
+
+        let to_target = (target - car.position()).normalize_or_zero();
+        let turn = (car.forward().perp_dot(to_target) * self.steer_gain).clamp(-1.0, 1.0);
+        let aligned = car.forward().dot(to_target);
+
+        if aligned > ALIGNED {
+            CarInput::new(1.0, turn, 0.0)
+        } else if car.speed() > CORNER_SPEED {
+            CarInput::new(0.0, turn, 1.0)
+        } else {
+            CarInput::new(0.5, turn, 0.0)
+        }
+    }
+
+    /// Advances the waypoint and computes the controls for one step
+    pub fn drive(&mut self, car: &Car) -> CarInput {
+        self.advance(car);
+        self.compute_input(car)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_the_waypoint_after_the_nearest() {
+        let line = RacingLine::default_track();
+        let car = Car::new(850.0, 590.0);
+        let driver = AiDriver::from_racing_line(&car, &line);
+        assert_eq!(driver.target(), Some(line.points()[1]));
+    }
+
+    #[test]
+    fn steers_towards_the_target() {
+        // Facing +y with the target ahead and to the left (-x)
+        let driver = AiDriver::new(vec![Vec2::new(-100.0, 100.0)]);
+        let input = driver.compute_input(&Car::new(0.0, 0.0));
+        assert!(input.turn() > 0.0);
+        assert_eq!(input.throttle(), 1.0);
+
+        let driver = AiDriver::new(vec![Vec2::new(100.0, 100.0)]);
+        assert!(driver.compute_input(&Car::new(0.0, 0.0)).turn() < 0.0);
+    }
+
+    #[test]
+    fn advances_past_reached_waypoints() {
+        let mut driver = AiDriver::new(vec![Vec2::new(0.0, 10.0), Vec2::new(0.0, 500.0)]);
+        driver.drive(&Car::new(0.0, 0.0));
+        assert_eq!(driver.target(), Some(Vec2::new(0.0, 500.0)));
+
+        let mut empty = AiDriver::new(Vec::new());
+        assert_eq!(empty.drive(&Car::new(0.0, 0.0)), CarInput::default());
+    }
+
+    #[test]
+    fn difficulty_scales_the_steering() {
+        let hard = AiDriver::new(Vec::new()).with_difficulty(Difficulty::Hard);
+        let easy = AiDriver::new(Vec::new()).with_difficulty(Difficulty::Easy);
+        assert!(hard.steer_gain() > easy.steer_gain());
+    }
+}
@@ -0,0 +1,10 @@
+//! Computer controlled drivers
+//!
+//! Produces the same `CarInput`s a player would, so AI cars run through
+//! the regular world step and end up in recordings like any other car.
+
+mod difficulty;
+mod driver;
+
+pub use difficulty::Difficulty;
+pub use driver::AiDriver;
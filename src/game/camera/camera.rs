@@ -1,7 +1,69 @@
+use super::CameraConfig;
+use crate::game::utils::{lerp_angle, Rng};
 use crate::game::world::Car;
-use glam::Vec2;
+use glam::{FloatExt, Vec2};
 use std::f32::consts::PI;
 
+/// Horizon of overhead poses, just above the top of the screen
+///
+/// In normalized screen space (-1 is the top row), so overhead poses
+/// show only ground.
+const OVERHEAD_HORIZON: f32 = -1.2;
+
+/// Far plane used by overview modes so the whole track stays visible
+const TOP_DOWN_FAR: f32 = 5000.0;
+
+/// Largest pitch, keeping the horizon's `tan` finite
+const MAX_HORIZON_PITCH: f32 = PI / 2.0 - 0.01;
+
+/// Smallest view scale, `scale` divides screen positions
+const MIN_ZOOM: f32 = 0.1;
+
+/// Largest view scale
+const MAX_ZOOM: f32 = 10.0;
+
+/// Smallest pitch reachable through `set_pitch` and `adjust_pitch`
+const MIN_PITCH: f32 = 0.01;
+
+/// Lowest height reachable through `set_height` and `adjust_height`
+const MIN_HEIGHT: f32 = 0.1;
+
+/// Smallest near plane and gap between near and far plane
+const MIN_CLIP: f32 = 0.01;
+
+/// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+
+/// Positional shake at full trauma in world units
+const MAX_SHAKE_OFFSET: f32 = 6.0;
+
+/// Angular shake at full trauma in radians
+const MAX_SHAKE_ANGLE: f32 = 0.08;
+
+/// A temporary camera pose that can be pushed on top of the current one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraMode {
+    /// High overhead view of the current position, e.g. for map thumbnails
+    TopDown {
+        /// Height above the ground
+        height: f32,
+    },
+}
+
+/// Snapshot of the viewing parameters restored by `Camera::pop_mode`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CameraParams {
+    x: f32,
+    y: f32,
+    height: f32,
+    angle: f32,
+    pitch: f32,
+    overhead: f32,
+    near: f32,
+    far: f32,
+    scale: f32,
+}
+
 /// Camera for dynamic car following and view control
 ///
 /// Provides:
@@ -10,23 +72,52 @@ use std::f32::consts::PI;
 /// * Speed-based view angle changes
 /// * Car following behavior
 /// * View frustum control
+///
+/// The setters keep the view valid: `near < far`, `scale > 0`, a height
+/// above the ground and `0 < pitch < π/2`. Overhead poses like
+/// [`CameraMode::TopDown`] keep the pitch and move the horizon above the
+/// screen instead.
 pub struct Camera {
     /// World X position
-    pub x: f32,
+    x: f32,
     /// World Y position
-    pub y: f32,
+    y: f32,
     /// Height above ground
-    pub height: f32,
+    height: f32,
     /// Rotation angle in radians
-    pub angle: f32,
+    angle: f32,
     /// Downward tilt in radians
-    pub pitch: f32,
+    pitch: f32,
+    /// How far the horizon is moved from the pitch's row up to
+    /// `OVERHEAD_HORIZON`, 0.0 normally and 1.0 in overhead poses
+    overhead: f32,
     /// Near clip distance
-    pub near: f32,
+    near: f32,
     /// Far clip distance
-    pub far: f32,
+    far: f32,
     /// View scale factor
-    pub scale: f32,
+    scale: f32,
+    /// View scale `follow_car` eases towards while standing still
+    zoom: f32,
+    /// Follow behavior used by `follow_car`
+    config: CameraConfig,
+    /// Whether the camera has locked onto a car yet
+    following: bool,
+    /// Parameters saved by `push_mode`, restored by `pop_mode`
+    mode_stack: Vec<CameraParams>,
+    /// Shake intensity from 0.0 (calm) to 1.0, decays over time
+    trauma: f32,
+    /// Positional shake currently applied on top of the follow position
+    shake_offset: Vec2,
+    /// Angular shake currently applied on top of the follow angle
+    shake_angle: f32,
+    /// Position on the circle around the target used by `orbit`, in radians
+    orbit_angle: f32,
+    /// Whether the last pose came from `orbit`, otherwise the next orbit
+    /// starts from the current bearing to its target
+    orbiting: bool,
+    /// Source of the shake, seeded so replays shake the same way
+    rng: Rng,
 }
 
 impl Default for Camera {
@@ -43,7 +134,7 @@ impl Camera {
     ///
     /// * `x` - World X coordinate
     /// * `y` - World Y coordinate
-    /// * `height` - Height above ground
+    /// * `height` - Height above ground, see [`Camera::set_height`]
     /// * `angle` - Rotation in radians
     ///
     /// # Returns
@@ -53,80 +144,890 @@ impl Camera {
     /// * 1.0 near plane
     /// * 1000.0 far plane
     /// * 1.0 scale
+    /// * Default `CameraConfig` for following cars
     pub fn new(x: f32, y: f32, height: f32, angle: f32) -> Self {
         Self {
             x,
             y,
-            height,
+            height: if height.is_nan() {
+                MIN_HEIGHT
+            } else {
+                height.max(MIN_HEIGHT)
+            },
             angle,
             pitch: PI / 6.0,
+            overhead: 0.0,
             near: 1.0,
             far: 1000.0,
             scale: 1.0, // Funny to tweak
+            zoom: 1.0,
+            config: CameraConfig::default(),
+            following: false,
+            mode_stack: Vec::new(),
+            trauma: 0.0,
+            shake_offset: Vec2::ZERO,
+            shake_angle: 0.0,
+            orbit_angle: 0.0,
+            orbiting: false,
+            rng: Rng::default(),
+        }
+    }
+
+    /// Temporarily switches the camera to a different pose
+    ///
+    /// The current viewing parameters are saved and restored exactly by
+    /// the matching [`Camera::pop_mode`]. Modes can be nested.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Pose to switch to
+    pub fn push_mode(&mut self, mode: CameraMode) {
+        self.mode_stack.push(self.params());
+        self.orbiting = false;
+
+        match mode {
+            CameraMode::TopDown { height } => {
+                self.set_height(height);
+                self.overhead = 1.0;
+                self.far = self.far.max(TOP_DOWN_FAR);
+            }
+        }
+    }
+
+    /// Restores the parameters saved by the most recent `push_mode`
+    ///
+    /// # Returns
+    ///
+    /// * `true` - A mode was popped
+    /// * `false` - No mode was active, the camera is unchanged
+    pub fn pop_mode(&mut self) -> bool {
+        let Some(params) = self.mode_stack.pop() else {
+            return false;
+        };
+
+        self.x = params.x;
+        self.y = params.y;
+        self.set_height(params.height);
+        self.angle = params.angle;
+        self.set_pitch(params.pitch);
+        self.overhead = params.overhead;
+        self.near = params.near;
+        self.far = params.far;
+        self.set_scale(params.scale);
+        self.orbiting = false;
+        true
+    }
+
+    /// Returns the number of modes currently pushed
+    pub fn mode_depth(&self) -> usize {
+        self.mode_stack.len()
+    }
+
+    /// Captures the current viewing parameters
+    fn params(&self) -> CameraParams {
+        CameraParams {
+            x: self.x,
+            y: self.y,
+            height: self.height,
+            angle: self.angle,
+            pitch: self.pitch,
+            overhead: self.overhead,
+            near: self.near,
+            far: self.far,
+            scale: self.scale,
+        }
+    }
+
+    /// Returns the horizon row in normalized screen space
+    ///
+    /// -1.0 is the top and 1.0 the bottom row, values outside put the
+    /// horizon off-screen. The pitch puts it at `tan(pitch) * 0.5`, overhead
+    /// poses move it up to just above the top row.
+    pub fn horizon(&self) -> f32 {
+        (self.pitch.tan() * 0.5).lerp(OVERHEAD_HORIZON, self.overhead)
+    }
+
+    /// Maps a pixel to the point on the ground it shows
+    ///
+    /// Performs the perspective projection of the ground plane using:
+    /// * Camera height for z-depth calculation
+    /// * Pitch angle for horizon determination
+    /// * View angle for world rotation
+    /// * Scale for world space sizing
+    ///
+    /// # Arguments
+    ///
+    /// * `screen` - Pixel position, (0, 0) being the top-left corner
+    /// * `viewport` - Width and height of the view in pixels
+    ///
+    /// # Returns
+    ///
+    /// `(world, z)` if the pixel shows ground between the near and far
+    /// plane, None for the sky, where `z` is the depth along the view
+    /// direction
+    pub fn screen_to_ground(&self, screen: Vec2, viewport: Vec2) -> Option<(Vec2, f32)> {
+        let x = screen.x / viewport.x * 2.0 - 1.0;
+        let y = screen.y / viewport.y * 2.0 - 1.0;
+
+        let horizon = self.horizon();
+        if y <= horizon {
+            return None;
+        }
+
+        let z = self.height / (y - horizon);
+        if z <= self.near || z >= self.far {
+            return None;
+        }
+
+        let view_x = x * z * self.scale;
+        let (sin_angle, cos_angle) = self.angle.sin_cos();
+        let rotated_x = view_x * cos_angle - z * sin_angle;
+        let rotated_z = view_x * sin_angle + z * cos_angle;
+
+        Some((Vec2::new(rotated_x + self.x, rotated_z + self.y), z))
+    }
+
+    /// Maps a whole row of pixels to the ground at once
+    ///
+    /// Every pixel of a row sees the ground at the same depth, so the
+    /// mapping along the row is affine: pixel `x` shows `start + step * x`,
+    /// matching [`Camera::screen_to_ground`] up to rounding.
+    ///
+    /// # Arguments
+    ///
+    /// * `screen_y` - Pixel row, 0 being the top
+    /// * `viewport` - Width and height of the view in pixels
+    ///
+    /// # Returns
+    ///
+    /// `(start, step, z)` with the ground under the row's first pixel,
+    /// the world offset per pixel and the row's depth. None if the row
+    /// shows sky or lies outside the near and far plane.
+    pub fn ground_scanline(&self, screen_y: f32, viewport: Vec2) -> Option<(Vec2, Vec2, f32)> {
+        let y = screen_y / viewport.y * 2.0 - 1.0;
+
+        let horizon = self.horizon();
+        if y <= horizon {
+            return None;
+        }
+
+        let z = self.height / (y - horizon);
+        if z <= self.near || z >= self.far {
+            return None;
+        }
+
+        // One pixel moves 2 / width in normalized x
+        let (sin_angle, cos_angle) = self.angle.sin_cos();
+        let right = Vec2::new(cos_angle, sin_angle) * z * self.scale;
+        let ahead = Vec2::new(-sin_angle, cos_angle) * z;
+        let start = self.position() + ahead - right;
+        let step = right * (2.0 / viewport.x);
+
+        Some((start, step, z))
+    }
+
+    /// Maps a pixel to the point on the ground it shows
+    ///
+    /// Inverse of [`Camera::world_to_screen`], see [`Camera::screen_to_ground`].
+    ///
+    /// # Arguments
+    ///
+    /// * `screen` - Pixel position, (0, 0) being the top-left corner
+    /// * `viewport` - Width and height of the view in pixels
+    ///
+    /// # Returns
+    ///
+    /// The world position, None if the pixel shows sky
+    pub fn screen_to_world(&self, screen: Vec2, viewport: Vec2) -> Option<Vec2> {
+        self.screen_to_ground(screen, viewport)
+            .map(|(world, _)| world)
+    }
+
+    /// Maps a point on the ground to the pixel showing it
+    ///
+    /// Inverse of [`Camera::screen_to_world`] for every visible point.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - Position in world space
+    /// * `viewport` - Width and height of the view in pixels
+    ///
+    /// # Returns
+    ///
+    /// The pixel position, None if the point is outside the view or
+    /// beyond the near or far plane
+    pub fn world_to_screen(&self, world: Vec2, viewport: Vec2) -> Option<Vec2> {
+        let relative = world - self.position();
+
+        let (sin_angle, cos_angle) = self.angle.sin_cos();
+        let view_x = relative.x * cos_angle + relative.y * sin_angle;
+        let z = -relative.x * sin_angle + relative.y * cos_angle;
+        if z <= self.near || z >= self.far {
+            return None;
+        }
+
+        // Height and depth are both positive here, so the point always
+        // lands below the horizon
+        let x = view_x / (z * self.scale);
+        let y = self.horizon() + self.height / z;
+
+        let screen = Vec2::new((x + 1.0) * viewport.x / 2.0, (y + 1.0) * viewport.y / 2.0);
+        if screen.x < 0.0 || screen.x >= viewport.x || screen.y < 0.0 || screen.y >= viewport.y {
+            return None;
         }
+
+        Some(screen)
+    }
+
+    /// Positions the camera high above a target for an overhead view
+    ///
+    /// Uses the same horizon as [`CameraMode::TopDown`], so only ground is
+    /// visible, and moves the camera back along its heading until the
+    /// target is in the center of the view. Unlike `follow_car` nothing
+    /// depends on speed and nothing is smoothed; the next `follow_car`
+    /// snaps back onto its car.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - World position to center the view on
+    /// * `height` - Height above the ground, see [`Camera::set_height`]
+    pub fn look_at(&mut self, target: Vec2, height: f32) {
+        self.set_height(height);
+        self.overhead = 1.0;
+        self.far = self.far.max(TOP_DOWN_FAR);
+
+        // The screen center sees the ground at depth height / (0 - horizon)
+        let distance = self.height / -self.horizon();
+        let forward = Vec2::new(-self.angle.sin(), self.angle.cos());
+        self.set_position(target - forward * distance);
+
+        self.following = false;
+        self.orbiting = false;
+        self.shake_offset = Vec2::ZERO;
+        self.shake_angle = 0.0;
+    }
+
+    /// Returns the world position as a vector
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Moves the camera to a world position
+    pub fn set_position(&mut self, position: Vec2) {
+        self.x = position.x;
+        self.y = position.y;
+    }
+
+    /// Returns the follow behavior used by `follow_car`
+    pub fn config(&self) -> &CameraConfig {
+        &self.config
+    }
+
+    /// Sets the follow behavior used by `follow_car`
+    pub fn set_config(&mut self, config: CameraConfig) {
+        self.config = config;
+    }
+
+    /// Returns the view scale used while standing still
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Sets the view scale used while standing still
+    ///
+    /// Larger values show more of the world. `follow_car` eases the
+    /// current scale towards it and widens it further with speed. The
+    /// zoom is clamped to 0.1..=10.0 so the scale never reaches 0.
+    pub fn set_zoom(&mut self, scale: f32) {
+        self.zoom = if scale.is_nan() {
+            1.0
+        } else {
+            scale.clamp(MIN_ZOOM, MAX_ZOOM)
+        };
+    }
+
+    /// Returns the height above the ground
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Sets the height above the ground
+    ///
+    /// Clamped to ≥ 0.1 so the ground never passes through the camera,
+    /// NaN is ignored.
+    pub fn set_height(&mut self, height: f32) {
+        if !height.is_nan() {
+            self.height = height.max(MIN_HEIGHT);
+        }
+    }
+
+    /// Returns the rotation angle in radians (counterclockwise from vertical)
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Sets the rotation angle in radians, non-finite angles are ignored
+    pub fn set_angle(&mut self, angle: f32) {
+        if angle.is_finite() {
+            self.angle = angle;
+        }
+    }
+
+    /// Returns the downward tilt in radians
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Sets the downward tilt in radians
+    ///
+    /// Clamped to just inside 0..π/2 so the horizon stays on a finite
+    /// row, NaN is ignored.
+    pub fn set_pitch(&mut self, pitch: f32) {
+        if !pitch.is_nan() {
+            self.pitch = pitch.clamp(MIN_PITCH, MAX_HORIZON_PITCH);
+        }
+    }
+
+    /// Returns the near clip distance
+    pub fn near(&self) -> f32 {
+        self.near
+    }
+
+    /// Sets the near clip distance
+    ///
+    /// Clamped to stay positive and in front of the far plane, NaN is
+    /// ignored.
+    pub fn set_near(&mut self, near: f32) {
+        if !near.is_nan() {
+            let max_near = (self.far - MIN_CLIP).max(MIN_CLIP);
+            self.near = near.clamp(MIN_CLIP, max_near);
+        }
+    }
+
+    /// Returns the far clip distance
+    pub fn far(&self) -> f32 {
+        self.far
+    }
+
+    /// Sets the far clip distance
+    ///
+    /// Clamped to stay behind the near plane, NaN is ignored.
+    pub fn set_far(&mut self, far: f32) {
+        if !far.is_nan() {
+            self.far = far.max(self.near + MIN_CLIP);
+        }
+    }
+
+    /// Returns the view scale, larger values show more of the world
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets the current view scale
+    ///
+    /// Clamped to 0.1..=10.0 like [`Camera::set_zoom`], NaN is ignored.
+    /// `follow_car` eases the scale back towards the zoom.
+    pub fn set_scale(&mut self, scale: f32) {
+        if !scale.is_nan() {
+            self.scale = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+    }
+
+    /// Tilts the camera, keeping the pitch between 0 and π/2 (exclusive)
+    ///
+    /// Meant for live tuning; `follow_car` eases the pitch back towards
+    /// its configured target, so the change sticks only while paused.
+    pub fn adjust_pitch(&mut self, delta: f32) {
+        self.set_pitch(self.pitch + delta);
+    }
+
+    /// Raises or lowers the camera, keeping it above the ground
+    ///
+    /// Meant for live tuning; `follow_car` eases the height back towards
+    /// its configured target, so the change sticks only while paused.
+    pub fn adjust_height(&mut self, delta: f32) {
+        self.set_height(self.height + delta);
+    }
+
+    /// Moves the near clip plane, keeping it in front of the far plane
+    pub fn adjust_near(&mut self, delta: f32) {
+        self.set_near(self.near + delta);
+    }
+
+    /// Moves the far clip plane, keeping it behind the near plane
+    pub fn adjust_far(&mut self, delta: f32) {
+        self.set_far(self.far + delta);
+    }
+
+    /// Changes the view scale and zoom together, see [`Camera::set_zoom`]
+    pub fn adjust_scale(&mut self, delta: f32) {
+        self.set_zoom(self.zoom + delta);
+        self.set_scale(self.scale + delta);
+    }
+
+    /// Returns the smoothing speed used when following a car
+    pub fn follow_lerp(&self) -> f32 {
+        self.config.follow_lerp
+    }
+
+    /// Sets the smoothing speed used when following a car
+    ///
+    /// Negative values are clamped to 0.0 (camera stands still).
+    pub fn set_follow_lerp(&mut self, follow_lerp: f32) {
+        self.config.follow_lerp = follow_lerp.max(0.0);
+    }
+
+    /// Returns the extra height gained per unit of car speed
+    pub fn height_factor(&self) -> f32 {
+        self.config.height_factor
+    }
+
+    /// Sets the extra height gained per unit of car speed
+    ///
+    /// Negative values are clamped to 0.0 (constant height).
+    pub fn set_height_factor(&mut self, height_factor: f32) {
+        self.config.height_factor = height_factor.max(0.0);
+    }
+
+    /// Returns the current shake intensity from 0.0 to 1.0
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Adds screen shake, e.g. after a crash
+    ///
+    /// Trauma accumulates up to 1.0 and decays while following a car.
+    /// The shake grows with trauma², so small bumps stay subtle.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Trauma to add, negative values are ignored
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount.max(0.0)).min(1.0);
+    }
+
+    /// Instantly moves the camera onto a car and matches its heading
+    ///
+    /// Used on the first `follow_car` call so the camera doesn't slowly
+    /// swing around from its default orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `car` - Car to frame
+    pub fn snap_to_car(&mut self, car: &Car) {
+        self.set_position(car.position());
+        self.angle = car.angle();
+        self.following = true;
+        self.shake_offset = Vec2::ZERO;
+        self.shake_angle = 0.0;
+    }
+
+    /// Circles the camera around a target, always looking at it
+    ///
+    /// The camera stops following its car, the next `follow_car` snaps
+    /// back onto it. Each call advances the camera along the circle,
+    /// so a full lap takes `2π / speed` seconds. The first call after any
+    /// other pose starts from the camera's current bearing to the target,
+    /// so the view doesn't jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Center of the circle, e.g. the winning car
+    /// * `dt` - Delta time in seconds
+    /// * `radius` - Distance to the target, kept beyond the near plane
+    ///   so the target stays visible
+    /// * `speed` - Angular speed in radians/s, negative orbits clockwise
+    pub fn orbit(&mut self, target: Vec2, dt: f32, radius: f32, speed: f32) {
+        if !self.orbiting {
+            // The camera looks along its heading, from its position towards the target
+            let offset = self.position() - target;
+            self.orbit_angle = if offset.length_squared() > 0.0 {
+                offset.x.atan2(-offset.y)
+            } else {
+                self.angle
+            };
+            self.orbiting = true;
+        }
+
+        self.orbit_angle = (self.orbit_angle + speed * dt).rem_euclid(2.0 * PI);
+        let radius = radius.max(self.near + MIN_CLIP);
+
+        // Looking along the heading from behind the target keeps it centered
+        self.angle = self.orbit_angle;
+        let forward = Vec2::new(-self.angle.sin(), self.angle.cos());
+        self.set_position(target - forward * radius);
+
+        self.following = false;
+        self.shake_offset = Vec2::ZERO;
+        self.shake_angle = 0.0;
     }
 
-    // TODO: This function sucks when follow distance is anything remote a real number i am gonna use so hard 0.0 for now
     /// Updates camera to follow a car with smooth transitions
     ///
+    /// Uses the camera's own [`CameraConfig`], see
+    /// [`Camera::follow_car_with`].
+    // TODO: wierd bug after game is paused
+    pub fn follow_car(&mut self, car: &Car, dt: f32) {
+        let config = self.config;
+        self.follow_car_with(car, dt, &config);
+    }
+
+    /// Updates camera to follow a car using the given configuration
+    ///
     /// Adjusts camera parameters based on car state:
-    /// * Position tracks behind car
+    /// * Position tracks `follow_distance` behind the car
     /// * Height increases with speed
     /// * Pitch tilts down more at high speeds
+    /// * View scale widens with speed on top of the zoom
     /// * Rotation matches car direction
+    /// * Shakes while there is trauma (see [`Camera::add_trauma`])
     ///
-    /// Uses constant factors:
-    /// * FOLLOW_DISTANCE: 0.0 (centered)
-    /// * CAMERA_LERP: 10.0 (position speed)
-    /// * ANGLE_LERP: 7.0 (rotation speed)
-    // TODO: wierd bug after game is paused
-    pub fn follow_car(&mut self, car: &Car, dt: f32) {
-        const FOLLOW_DISTANCE: f32 = 0.0;
-        const CAMERA_LERP: f32 = 10.0;
-        const ANGLE_LERP: f32 = 7.0;
+    /// # Arguments
+    ///
+    /// * `car` - Car to follow
+    /// * `dt` - Delta time in seconds
+    /// * `config` - Follow behavior
+    pub fn follow_car_with(&mut self, car: &Car, dt: f32, config: &CameraConfig) {
+        // Undo last frame's shake so it never accumulates into the follow position
+        self.set_position(self.position() - self.shake_offset);
+        self.angle -= self.shake_angle;
+
+        self.orbiting = false;
 
         // Calculate target position behind car
-        let car_angle = car.angle();
-        let target_x = car.position().x - FOLLOW_DISTANCE * car_angle.sin();
-        let target_y = car.position().y - FOLLOW_DISTANCE * car_angle.cos();
+        let target = car.position() - car.forward() * config.follow_distance;
+
+        if !self.following {
+            self.snap_to_car(car);
+            self.set_position(target);
+        }
 
         // Smoothly move camera
-        self.x += (target_x - self.x) * CAMERA_LERP * dt;
-        self.y += (target_y - self.y) * CAMERA_LERP * dt;
+        let follow = config.follow_lerp * dt;
+        self.set_position(self.position().lerp(target, follow));
 
-        // Find shortest rotation path
-        let mut angle_diff = car_angle - self.angle;
-        while angle_diff > PI {
-            angle_diff -= 2.0 * PI;
+        // Turn the shortest way towards the car's heading
+        self.angle = lerp_angle(self.angle, car.angle(), config.angle_lerp * dt);
+
+        // Adjust height and pitch with speed and ease out of overhead poses.
+        // Reversing with a low base height can aim below the ground, which
+        // set_height clamps away
+        self.set_height(self.height.lerp(config.target_height(car.speed()), follow));
+        self.set_pitch(self.pitch.lerp(config.target_pitch(car.speed()), follow));
+        self.overhead = self.overhead.lerp(0.0, follow).clamp(0.0, 1.0);
+
+        let target_scale = config
+            .target_scale(self.zoom, car.speed())
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+        self.scale = self
+            .scale
+            .lerp(target_scale, follow)
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+
+        self.apply_shake(dt);
+    }
+
+    /// Decays trauma and applies a fresh random shake on top of the camera
+    fn apply_shake(&mut self, dt: f32) {
+        self.trauma = (self.trauma - TRAUMA_DECAY * dt).max(0.0);
+
+        let shake = self.trauma * self.trauma;
+        if shake > 0.0 {
+            let rng = &mut self.rng;
+            self.shake_offset =
+                Vec2::new(rng.range(-1.0, 1.0), rng.range(-1.0, 1.0)) * MAX_SHAKE_OFFSET * shake;
+            self.shake_angle = rng.range(-1.0, 1.0) * MAX_SHAKE_ANGLE * shake;
+        } else {
+            self.shake_offset = Vec2::ZERO;
+            self.shake_angle = 0.0;
         }
-        while angle_diff < -PI {
-            angle_diff += 2.0 * PI;
+
+        self.set_position(self.position() + self.shake_offset);
+        self.angle += self.shake_angle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Vec2 = Vec2::new(320.0, 240.0);
+
+    #[test]
+    fn setters_keep_the_view_valid() {
+        let mut camera = Camera::new(0.0, 0.0, -5.0, 0.0);
+        assert_eq!(camera.height(), MIN_HEIGHT);
+
+        camera.set_height(0.0);
+        assert_eq!(camera.height(), MIN_HEIGHT);
+        camera.set_height(20.0);
+        camera.set_height(f32::NAN);
+        assert_eq!(camera.height(), 20.0);
+
+        camera.set_pitch(-1.0);
+        assert_eq!(camera.pitch(), MIN_PITCH);
+        camera.set_pitch(PI);
+        assert_eq!(camera.pitch(), MAX_HORIZON_PITCH);
+        assert!(camera.horizon().is_finite());
+
+        camera.set_scale(0.0);
+        assert_eq!(camera.scale(), MIN_ZOOM);
+        camera.set_zoom(f32::NAN);
+        assert_eq!(camera.zoom(), 1.0);
+        camera.set_zoom(100.0);
+        assert_eq!(camera.zoom(), MAX_ZOOM);
+
+        camera.set_angle(f32::INFINITY);
+        assert_eq!(camera.angle(), 0.0);
+    }
+
+    #[test]
+    fn clip_planes_stay_ordered() {
+        let mut camera = Camera::default();
+        camera.set_near(2000.0);
+        assert!(camera.near() < camera.far());
+        camera.set_far(0.0);
+        assert!(camera.near() < camera.far());
+        camera.set_near(-1.0);
+        assert!(camera.near() > 0.0);
+    }
+
+    #[test]
+    fn pop_mode_restores_the_pose() {
+        let mut camera = Camera::new(10.0, 20.0, 15.0, 0.5);
+        let before = camera.params();
+        assert!(!camera.pop_mode());
+
+        camera.push_mode(CameraMode::TopDown { height: 500.0 });
+        assert_eq!(camera.height(), 500.0);
+        assert_eq!(camera.horizon(), OVERHEAD_HORIZON);
+        camera.push_mode(CameraMode::TopDown { height: 800.0 });
+        assert_eq!(camera.mode_depth(), 2);
+
+        assert!(camera.pop_mode());
+        assert_eq!(camera.height(), 500.0);
+        assert_eq!(camera.horizon(), OVERHEAD_HORIZON);
+        assert!(camera.pop_mode());
+        assert_eq!(camera.params(), before);
+    }
+
+    #[test]
+    fn modes_cant_put_the_camera_underground() {
+        let mut camera = Camera::default();
+        camera.push_mode(CameraMode::TopDown { height: -10.0 });
+        assert_eq!(camera.height(), MIN_HEIGHT);
+        camera.push_mode(CameraMode::TopDown { height: f32::NAN });
+        assert_eq!(camera.height(), MIN_HEIGHT);
+    }
+
+    #[test]
+    fn first_follow_frames_the_car_heading() {
+        let mut camera = Camera::default();
+        let car = Car::new_with_angle(100.0, 200.0, 2.0);
+        camera.follow_car(&car, 1.0 / 60.0);
+        assert!((camera.angle() - 2.0).abs() < 1e-5);
+        assert!(camera.position().distance(car.position()) < 1e-3);
+    }
+
+    #[test]
+    fn follow_keeps_the_camera_above_the_ground() {
+        let config = CameraConfig {
+            base_height: -50.0,
+            ..CameraConfig::default()
+        };
+        let mut camera = Camera::default();
+        let car = Car::new(100.0, 100.0);
+        for _ in 0..100 {
+            camera.follow_car_with(&car, 0.1, &config);
         }
-        self.angle += angle_diff * ANGLE_LERP * dt;
+        assert_eq!(camera.height(), MIN_HEIGHT);
+        assert_eq!(camera.position(), car.position());
+    }
 
-        // Adjust height and pitch with speed
-        let target_height = 15.0 + car.speed() * 0.05;
-        self.height += (target_height - self.height) * CAMERA_LERP * dt;
+    #[test]
+    fn follow_eases_out_of_overhead_poses() {
+        let mut camera = Camera::default();
+        let car = Car::new(0.0, 0.0);
+        camera.push_mode(CameraMode::TopDown { height: 500.0 });
+        camera.follow_car(&car, 1.0 / 60.0);
+        assert!(camera.horizon() < 0.0);
 
-        let target_pitch = PI / 6.0 + (car.speed() / 400.0) * (PI / 12.0);
-        self.pitch += (target_pitch - self.pitch) * CAMERA_LERP * dt;
+        for _ in 0..600 {
+            camera.follow_car(&car, 1.0 / 60.0);
+        }
+        let config = CameraConfig::default();
+        assert!((camera.pitch() - config.target_pitch(0.0)).abs() < 1e-3);
+        assert!((camera.horizon() - config.target_pitch(0.0).tan() * 0.5).abs() < 1e-3);
+        assert!((camera.height() - config.target_height(0.0)).abs() < 1e-3);
     }
 
-    /// Converts world coordinates to screen coordinates
-    ///
-    /// # Arguments
-    ///
-    /// * `world_pos` - World coordinates
-    ///
-    /// # Returns
-    ///
-    /// Screen coordinates
-    pub fn world_to_screen(&self, world_pos: Vec2) -> Vec2 {
-        let screen_pos = Vec2::new(
-            world_pos.x * self.scale + self.x,
-            world_pos.y * self.scale + self.y,
-        );
+    #[test]
+    fn overhead_poses_keep_the_pitch_in_range() {
+        let in_range = |camera: &Camera| camera.pitch() > 0.0 && camera.pitch() < PI / 2.0;
+        let config = CameraConfig {
+            min_pitch: -1.0,
+            max_pitch: 3.0,
+            ..CameraConfig::default()
+        };
+        let mut camera = Camera::default();
+        let car = Car::new(0.0, 0.0);
+
+        camera.push_mode(CameraMode::TopDown { height: 500.0 });
+        assert!(in_range(&camera));
+        camera.follow_car_with(&car, 1.0 / 60.0, &config);
+        assert!(in_range(&camera));
+        camera.look_at(Vec2::ZERO, 400.0);
+        assert!(in_range(&camera));
+        camera.pop_mode();
+        assert!(in_range(&camera));
+        for _ in 0..600 {
+            camera.follow_car_with(&car, 1.0 / 60.0, &config);
+            assert!(in_range(&camera));
+        }
+    }
+
+    #[test]
+    fn look_at_centers_the_target() {
+        let mut camera = Camera::new(0.0, 0.0, 20.0, 1.2);
+        let target = Vec2::new(300.0, -40.0);
+        camera.look_at(target, 400.0);
+
+        let center = camera.screen_to_world(VIEWPORT / 2.0, VIEWPORT).unwrap();
+        assert!(center.distance(target) < 1e-2);
+    }
+
+    #[test]
+    fn look_at_stays_above_the_ground() {
+        let mut camera = Camera::default();
+        camera.look_at(Vec2::ZERO, 0.0);
+        assert_eq!(camera.height(), MIN_HEIGHT);
+        assert!(camera.position().is_finite());
+
+        camera.look_at(Vec2::ZERO, f32::NAN);
+        assert_eq!(camera.height(), MIN_HEIGHT);
+        assert!(camera.position().is_finite());
+    }
+
+    #[test]
+    fn orbit_starts_from_the_current_bearing() {
+        let target = Vec2::new(50.0, 50.0);
+        let mut camera = Camera::new(150.0, 50.0, 20.0, 0.0);
+        camera.orbit(target, 0.0, 100.0, 1.0);
+        assert!(camera.position().distance(Vec2::new(150.0, 50.0)) < 1e-3);
+        // Looking from +x towards the target means looking along -x
+        let forward = Vec2::new(-camera.angle().sin(), camera.angle().cos());
+        assert!(forward.distance(Vec2::NEG_X) < 1e-5);
+
+        camera.orbit(target, 0.5, 100.0, 1.0);
+        assert!((camera.position().distance(target) - 100.0).abs() < 1e-3);
+        assert!((camera.angle() - (PI / 2.0 + 0.5)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn orbit_reseeds_after_following() {
+        let target = Vec2::ZERO;
+        let mut camera = Camera::default();
+        camera.orbit(target, 1.0, 100.0, 1.0);
+
+        let car = Car::new(0.0, 200.0);
+        camera.follow_car(&car, 1.0 / 60.0);
+        camera.orbit(target, 0.0, 200.0, 1.0);
+        assert!(camera.position().distance(car.position()) < 1e-3);
+    }
+
+    #[test]
+    fn screen_and_world_round_trip() {
+        let camera = Camera::new(100.0, 50.0, 20.0, 0.7);
+        for screen in [
+            Vec2::new(160.0, 200.0),
+            Vec2::new(10.0, 230.0),
+            Vec2::new(300.0, 180.0),
+        ] {
+            let world = camera.screen_to_world(screen, VIEWPORT).unwrap();
+            let back = camera.world_to_screen(world, VIEWPORT).unwrap();
+            assert!(back.distance(screen) < 1e-2, "{screen} -> {back}");
+        }
+    }
+
+    #[test]
+    fn sky_and_behind_are_not_on_screen() {
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        assert!(camera
+            .screen_to_world(Vec2::new(160.0, 0.0), VIEWPORT)
+            .is_none());
+        assert!(camera
+            .world_to_screen(Vec2::new(0.0, -100.0), VIEWPORT)
+            .is_none());
+    }
+
+    #[test]
+    fn speed_zoom_is_optional() {
+        let mut camera = Camera::default();
+        camera.set_zoom(0.0);
+        assert_eq!(camera.zoom(), MIN_ZOOM);
+        camera.set_zoom(-3.0);
+        assert_eq!(camera.zoom(), MIN_ZOOM);
+
+        // Steady-state scale while following a car at a given speed
+        let scale_at = |speed: f32, config: &CameraConfig| {
+            let mut camera = Camera::default();
+            let mut car = Car::new(0.0, 0.0);
+            car.apply_impulse(Vec2::new(0.0, speed));
+            for _ in 0..600 {
+                camera.follow_car_with(&car, 1.0 / 60.0, config);
+            }
+            camera.scale()
+        };
+
+        let config = CameraConfig::default();
+        assert_eq!(scale_at(0.0, &config), scale_at(150.0, &config));
+
+        let config = CameraConfig {
+            zoom_factor: 0.001,
+            ..config
+        };
+        assert!(scale_at(150.0, &config) > scale_at(50.0, &config));
+        assert!((scale_at(150.0, &config) - config.target_scale(1.0, 150.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn trauma_decays_without_drift() {
+        let mut camera = Camera::default();
+        let car = Car::new_with_angle(100.0, 200.0, 0.5);
+        camera.follow_car(&car, 1.0 / 60.0);
+
+        camera.add_trauma(0.7);
+        camera.add_trauma(0.7);
+        camera.add_trauma(-1.0);
+        assert_eq!(camera.trauma(), 1.0);
+
+        // Full trauma is gone after 1 / TRAUMA_DECAY seconds
+        camera.follow_car(&car, 1.0 / 60.0);
+        assert!(camera.position().distance(car.position()) > 0.0);
+        for _ in 0..40 {
+            camera.follow_car(&car, 1.0 / 60.0);
+        }
+        assert_eq!(camera.trauma(), 0.0);
+        assert!(camera.position().distance(car.position()) < 1e-3);
+        assert!((camera.angle() - car.angle()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn follow_distance_places_the_camera_behind_the_car() {
+        let config = CameraConfig {
+            follow_distance: 300.0,
+            ..CameraConfig::default()
+        };
+        let mut camera = Camera::default();
+        let car = Car::new_with_angle(100.0, 200.0, 1.0);
+        for _ in 0..60 {
+            camera.follow_car_with(&car, 1.0 / 60.0, &config);
+        }
 
-        screen_pos
+        let behind = car.position() - car.forward() * 300.0;
+        assert!(camera.position().distance(behind) < 1e-3);
+        // The car is straight ahead in the middle of the view
+        let screen = camera.world_to_screen(car.position(), VIEWPORT).unwrap();
+        assert!((screen.x - VIEWPORT.x / 2.0).abs() < 1e-2);
     }
 }
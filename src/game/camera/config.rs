@@ -0,0 +1,76 @@
+use std::f32::consts::PI;
+
+/// Tuning constants for `Camera::follow_car_with`
+///
+/// The default matches the original split-screen camera: centered on
+/// the car, a little higher and steeper with speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraConfig {
+    /// Distance behind the car along its `-forward` vector
+    pub follow_distance: f32,
+    /// Position, height and pitch smoothing speed
+    pub follow_lerp: f32,
+    /// Rotation smoothing speed
+    pub angle_lerp: f32,
+    /// Height above the ground while standing still
+    pub base_height: f32,
+    /// Extra height gained per unit of car speed
+    pub height_factor: f32,
+    /// Pitch while standing still in radians
+    pub min_pitch: f32,
+    /// Pitch at `pitch_speed` and above in radians
+    pub max_pitch: f32,
+    /// Car speed at which `max_pitch` is reached
+    pub pitch_speed: f32,
+    /// Extra view scale per unit of car speed, relative to the camera zoom,
+    /// 0.0 turns speed-based zoom off
+    pub zoom_factor: f32,
+}
+
+impl Default for CameraConfig {
+    /// Creates the default follow camera configuration
+    ///
+    /// * 0.0 follow distance (centered)
+    /// * 10.0 follow lerp
+    /// * 7.0 angle lerp
+    /// * 15.0 base height
+    /// * 0.05 height factor
+    /// * 30° to 45° pitch, reached at 400 units/s
+    /// * 0.0 zoom factor (no speed-based zoom, 0.001 widens the view by
+    ///   20% at 200 units/s)
+    fn default() -> Self {
+        Self {
+            follow_distance: 0.0,
+            follow_lerp: 10.0,
+            angle_lerp: 7.0,
+            base_height: 15.0,
+            height_factor: 0.05,
+            min_pitch: PI / 6.0,
+            max_pitch: PI / 6.0 + PI / 12.0,
+            pitch_speed: 400.0,
+            zoom_factor: 0.0,
+        }
+    }
+}
+
+impl CameraConfig {
+    /// Returns the camera height for a car speed
+    pub fn target_height(&self, speed: f32) -> f32 {
+        self.base_height + speed * self.height_factor
+    }
+
+    /// Returns the view scale for a camera zoom and car speed
+    pub fn target_scale(&self, zoom: f32, speed: f32) -> f32 {
+        zoom * (1.0 + speed * self.zoom_factor)
+    }
+
+    /// Returns the camera pitch for a car speed, clamped to the pitch range
+    pub fn target_pitch(&self, speed: f32) -> f32 {
+        let t = if self.pitch_speed > 0.0 {
+            (speed / self.pitch_speed).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.min_pitch + (self.max_pitch - self.min_pitch) * t
+    }
+}
@@ -3,4 +3,6 @@
 //! Provides a dynamic camera system that can smoothly follow cars.
 
 mod camera;
-pub use camera::Camera;
+mod config;
+pub use camera::{Camera, CameraMode};
+pub use config::CameraConfig;
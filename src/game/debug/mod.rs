@@ -0,0 +1,7 @@
+//! Developer tools for tuning the game at runtime
+//!
+//! Only compiled in debug builds. Provides an overlay to tweak
+//! camera and physics constants without recompiling.
+
+mod overlay;
+pub use overlay::{DebugCommand, DebugOverlay, TuningParam};
@@ -0,0 +1,393 @@
+//! Live tuning overlay for camera and physics constants
+
+use crate::game::camera::Camera;
+use crate::game::world::Car;
+use crate::menu::{draw_text, TEXT_SCALE};
+use glam::Vec2;
+use pix_win_loop::{Context, KeyCode};
+use rusttype::Font;
+
+/// A constant that can be tuned through the debug overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TuningParam {
+    /// Camera height gained per unit of car speed
+    CameraHeightFactor,
+    /// Camera position smoothing speed
+    CameraFollowLerp,
+    /// Camera pitch in radians
+    CameraPitch,
+    /// Camera height above the ground
+    CameraHeight,
+    /// Camera near clip distance
+    CameraNear,
+    /// Camera far clip distance
+    CameraFar,
+    /// Camera view scale
+    CameraScale,
+    /// Car acceleration in units/s²
+    CarAcceleration,
+    /// Car top speed in units/s
+    CarMaxSpeed,
+    /// Car turning rate in radians/s
+    CarTurnSpeed,
+    /// Car quadratic drag coefficient
+    CarDrag,
+}
+
+impl TuningParam {
+    /// All tunable parameters in display order
+    pub const ALL: [TuningParam; 11] = [
+        TuningParam::CameraHeightFactor,
+        TuningParam::CameraFollowLerp,
+        TuningParam::CameraPitch,
+        TuningParam::CameraHeight,
+        TuningParam::CameraNear,
+        TuningParam::CameraFar,
+        TuningParam::CameraScale,
+        TuningParam::CarAcceleration,
+        TuningParam::CarMaxSpeed,
+        TuningParam::CarTurnSpeed,
+        TuningParam::CarDrag,
+    ];
+
+    /// Human readable name shown in the overlay
+    pub fn label(&self) -> &'static str {
+        match self {
+            TuningParam::CameraHeightFactor => "Cam height factor",
+            TuningParam::CameraFollowLerp => "Cam follow lerp",
+            TuningParam::CameraPitch => "Cam pitch",
+            TuningParam::CameraHeight => "Cam height",
+            TuningParam::CameraNear => "Cam near",
+            TuningParam::CameraFar => "Cam far",
+            TuningParam::CameraScale => "Cam scale",
+            TuningParam::CarAcceleration => "Car acceleration",
+            TuningParam::CarMaxSpeed => "Car max speed",
+            TuningParam::CarTurnSpeed => "Car turn speed",
+            TuningParam::CarDrag => "Car drag",
+        }
+    }
+
+    /// Amount a single nudge changes the parameter by
+    pub fn step(&self) -> f32 {
+        match self {
+            TuningParam::CameraHeightFactor => 0.01,
+            TuningParam::CameraFollowLerp => 0.5,
+            TuningParam::CameraPitch => 0.02,
+            TuningParam::CameraHeight => 1.0,
+            TuningParam::CameraNear => 0.5,
+            TuningParam::CameraFar => 50.0,
+            TuningParam::CameraScale => 0.05,
+            TuningParam::CarAcceleration => 25.0,
+            TuningParam::CarMaxSpeed => 10.0,
+            TuningParam::CarTurnSpeed => 0.25,
+            TuningParam::CarDrag => 0.0005,
+        }
+    }
+
+    /// Reads the current value from the given camera and car
+    pub fn value(&self, camera: &Camera, car: &Car) -> f32 {
+        match self {
+            TuningParam::CameraHeightFactor => camera.height_factor(),
+            TuningParam::CameraFollowLerp => camera.follow_lerp(),
+            TuningParam::CameraPitch => camera.pitch(),
+            TuningParam::CameraHeight => camera.height(),
+            TuningParam::CameraNear => camera.near(),
+            TuningParam::CameraFar => camera.far(),
+            TuningParam::CameraScale => camera.scale(),
+            TuningParam::CarAcceleration => car.acceleration(),
+            TuningParam::CarMaxSpeed => car.max_speed(),
+            TuningParam::CarTurnSpeed => car.turn_speed(),
+            TuningParam::CarDrag => car.drag(),
+        }
+    }
+
+    /// Adds `delta` to the parameter on the given cameras and cars
+    fn nudge(&self, delta: f32, cameras: &mut [Camera], cars: &mut [Car]) {
+        match self {
+            TuningParam::CameraHeightFactor => {
+                for camera in cameras.iter_mut() {
+                    camera.set_height_factor(camera.height_factor() + delta);
+                }
+            }
+            TuningParam::CameraFollowLerp => {
+                for camera in cameras.iter_mut() {
+                    camera.set_follow_lerp(camera.follow_lerp() + delta);
+                }
+            }
+            TuningParam::CameraPitch => {
+                for camera in cameras.iter_mut() {
+                    camera.adjust_pitch(delta);
+                }
+            }
+            TuningParam::CameraHeight => {
+                for camera in cameras.iter_mut() {
+                    camera.adjust_height(delta);
+                }
+            }
+            TuningParam::CameraNear => {
+                for camera in cameras.iter_mut() {
+                    camera.adjust_near(delta);
+                }
+            }
+            TuningParam::CameraFar => {
+                for camera in cameras.iter_mut() {
+                    camera.adjust_far(delta);
+                }
+            }
+            TuningParam::CameraScale => {
+                for camera in cameras.iter_mut() {
+                    camera.adjust_scale(delta);
+                }
+            }
+            TuningParam::CarAcceleration => {
+                for car in cars.iter_mut() {
+                    car.set_acceleration(car.acceleration() + delta);
+                }
+            }
+            TuningParam::CarMaxSpeed => {
+                for car in cars.iter_mut() {
+                    car.set_max_speed(car.max_speed() + delta);
+                }
+            }
+            TuningParam::CarTurnSpeed => {
+                for car in cars.iter_mut() {
+                    car.set_turn_speed(car.turn_speed() + delta);
+                }
+            }
+            TuningParam::CarDrag => {
+                for car in cars.iter_mut() {
+                    car.set_drag(car.drag() + delta);
+                }
+            }
+        }
+    }
+}
+
+/// A command understood by the debug overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugCommand {
+    /// Show or hide the overlay (F1)
+    Toggle,
+    /// Select the previous parameter (`[`)
+    PrevParam,
+    /// Select the next parameter (`]`)
+    NextParam,
+    /// Increase the selected parameter by one step (`=`)
+    Increase,
+    /// Decrease the selected parameter by one step (`-`)
+    Decrease,
+}
+
+/// Developer overlay for live-editing camera and physics constants
+///
+/// Provides:
+/// * A list of tunable parameters with their current values
+/// * Keyboard selection and nudging of the selected parameter
+/// * Writes through the public setters on `Camera` and `Car`
+pub struct DebugOverlay {
+    /// Whether the overlay is drawn and accepts commands
+    visible: bool,
+    /// Index into `TuningParam::ALL`
+    selected: usize,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugOverlay {
+    /// Creates a hidden overlay with the first parameter selected
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            selected: 0,
+        }
+    }
+
+    /// Returns whether the overlay is currently shown
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Returns the currently selected parameter
+    pub fn selected(&self) -> TuningParam {
+        TuningParam::ALL[self.selected]
+    }
+
+    /// Reads debug keys and applies the resulting commands
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Current input context
+    /// * `cameras` - Cameras affected by camera parameters
+    /// * `cars` - Cars affected by physics parameters
+    pub fn handle_input(&mut self, ctx: &Context, cameras: &mut [Camera], cars: &mut [Car]) {
+        let bindings = [
+            (KeyCode::F1, DebugCommand::Toggle),
+            (KeyCode::BracketLeft, DebugCommand::PrevParam),
+            (KeyCode::BracketRight, DebugCommand::NextParam),
+            (KeyCode::Equal, DebugCommand::Increase),
+            (KeyCode::Minus, DebugCommand::Decrease),
+        ];
+
+        for (key, command) in bindings {
+            if ctx.input.is_physical_key_pressed(key) {
+                self.apply(command, cameras, cars);
+            }
+        }
+    }
+
+    /// Applies a single command
+    ///
+    /// Commands other than `Toggle` are ignored while the overlay is hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to apply
+    /// * `cameras` - Cameras affected by camera parameters
+    /// * `cars` - Cars affected by physics parameters
+    pub fn apply(&mut self, command: DebugCommand, cameras: &mut [Camera], cars: &mut [Car]) {
+        if command != DebugCommand::Toggle && !self.visible {
+            return;
+        }
+
+        let count = TuningParam::ALL.len();
+        match command {
+            DebugCommand::Toggle => self.visible = !self.visible,
+            DebugCommand::PrevParam => self.selected = (self.selected + count - 1) % count,
+            DebugCommand::NextParam => self.selected = (self.selected + 1) % count,
+            DebugCommand::Increase | DebugCommand::Decrease => {
+                let param = self.selected();
+                let delta = if command == DebugCommand::Increase {
+                    param.step()
+                } else {
+                    -param.step()
+                };
+                param.nudge(delta, cameras, cars);
+
+                if let (Some(camera), Some(car)) = (cameras.first(), cars.first()) {
+                    log::info!("Debug: {} = {}", param.label(), param.value(camera, car));
+                }
+            }
+        }
+    }
+
+    /// Draws the parameter list in the top-left corner of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer (width * height * 4 bytes)
+    /// * `width` - Frame width in pixels
+    /// * `height` - Frame height in pixels
+    /// * `font` - Font used for the text
+    /// * `camera` - Camera the displayed camera values are read from
+    /// * `car` - Car the displayed physics values and telemetry are read from
+    pub fn render(
+        &self,
+        frame: &mut [u8],
+        width: u32,
+        height: u32,
+        font: &Font,
+        camera: &Camera,
+        car: &Car,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        for (i, param) in TuningParam::ALL.iter().enumerate() {
+            let (marker, color) = if i == self.selected {
+                (">", [255, 255, 0, 255])
+            } else {
+                (" ", [255, 255, 255, 255])
+            };
+            let line = format!(
+                "{} {}: {:.4}",
+                marker,
+                param.label(),
+                param.value(camera, car)
+            );
+            let pos = Vec2::new(8.0, 8.0 + i as f32 * (TEXT_SCALE + 4.0));
+            draw_text(frame, width, height, font, &line, pos, color);
+        }
+
+        // Read-only physics state below the tunable values
+        let telemetry = car.telemetry();
+        let lines = [
+            format!("  speed: {:.1}", telemetry.speed),
+            format!(
+                "  accel: {:.0} {:.0}",
+                telemetry.acceleration_force.x, telemetry.acceleration_force.y
+            ),
+            format!(
+                "  drag: {:.0} {:.0}",
+                telemetry.drag_force.x, telemetry.drag_force.y
+            ),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let row = TuningParam::ALL.len() + i;
+            let pos = Vec2::new(8.0, 8.0 + row as f32 * (TEXT_SCALE + 4.0));
+            draw_text(frame, width, height, font, line, pos, [160, 160, 160, 255]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_ignored_while_hidden() {
+        let mut overlay = DebugOverlay::new();
+        let mut cars = [Car::new(0.0, 0.0)];
+        overlay.apply(DebugCommand::NextParam, &mut [], &mut cars);
+        assert_eq!(overlay.selected(), TuningParam::ALL[0]);
+
+        overlay.apply(DebugCommand::Toggle, &mut [], &mut cars);
+        assert!(overlay.is_visible());
+        overlay.apply(DebugCommand::NextParam, &mut [], &mut cars);
+        assert_eq!(overlay.selected(), TuningParam::ALL[1]);
+    }
+
+    #[test]
+    fn selection_wraps_around() {
+        let mut overlay = DebugOverlay::new();
+        overlay.apply(DebugCommand::Toggle, &mut [], &mut []);
+        overlay.apply(DebugCommand::PrevParam, &mut [], &mut []);
+        assert_eq!(overlay.selected(), *TuningParam::ALL.last().unwrap());
+        overlay.apply(DebugCommand::NextParam, &mut [], &mut []);
+        assert_eq!(overlay.selected(), TuningParam::ALL[0]);
+    }
+
+    #[test]
+    fn nudges_every_camera_and_car() {
+        let mut overlay = DebugOverlay::new();
+        let mut cameras = [Camera::default(), Camera::default()];
+        let mut cars = [Car::new(0.0, 0.0), Car::new(0.0, 0.0)];
+        overlay.apply(DebugCommand::Toggle, &mut cameras, &mut cars);
+        while overlay.selected() != TuningParam::CarMaxSpeed {
+            overlay.apply(DebugCommand::NextParam, &mut cameras, &mut cars);
+        }
+
+        let before = cars[0].max_speed();
+        overlay.apply(DebugCommand::Increase, &mut cameras, &mut cars);
+        let step = TuningParam::CarMaxSpeed.step();
+        assert!(cars.iter().all(|car| car.max_speed() == before + step));
+        overlay.apply(DebugCommand::Decrease, &mut cameras, &mut cars);
+        assert!(cars.iter().all(|car| car.max_speed() == before));
+    }
+
+    #[test]
+    fn nudges_go_through_the_setters() {
+        let mut cameras = [Camera::default()];
+        let mut cars = [Car::new(0.0, 0.0)];
+        for _ in 0..1000 {
+            TuningParam::CameraHeight.nudge(-1.0, &mut cameras, &mut cars);
+            TuningParam::CarDrag.nudge(-1.0, &mut cameras, &mut cars);
+        }
+        assert!(TuningParam::CameraHeight.value(&cameras[0], &cars[0]) > 0.0);
+        assert_eq!(TuningParam::CarDrag.value(&cameras[0], &cars[0]), 0.0);
+    }
+}
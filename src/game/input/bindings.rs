@@ -0,0 +1,179 @@
+//! Keyboard layout for the player controls
+
+use pix_win_loop::KeyCode;
+
+/// A car control that can be bound to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Drive forward
+    Throttle,
+    /// Drive backward
+    Reverse,
+    /// Steer left
+    Left,
+    /// Steer right
+    Right,
+    /// Brake
+    Brake,
+    /// Handbrake, lets the car slide
+    Handbrake,
+}
+
+impl Action {
+    /// Number of actions
+    pub const COUNT: usize = 6;
+
+    /// All actions in declaration order
+    pub const ALL: [Action; Action::COUNT] = [
+        Action::Throttle,
+        Action::Reverse,
+        Action::Left,
+        Action::Right,
+        Action::Brake,
+        Action::Handbrake,
+    ];
+
+    /// Returns the position of the action in `Action::ALL`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Keys of one player, one per action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerBindings {
+    /// Keys indexed by `Action::index`
+    keys: [KeyCode; Action::COUNT],
+}
+
+impl PlayerBindings {
+    /// Creates bindings for one player
+    ///
+    /// # Arguments
+    ///
+    /// * `throttle` - Key for driving forward
+    /// * `reverse` - Key for driving backward
+    /// * `left` - Key for steering left
+    /// * `right` - Key for steering right
+    /// * `brake` - Key for braking
+    /// * `handbrake` - Key for the handbrake
+    pub fn new(
+        throttle: KeyCode,
+        reverse: KeyCode,
+        left: KeyCode,
+        right: KeyCode,
+        brake: KeyCode,
+        handbrake: KeyCode,
+    ) -> Self {
+        Self {
+            keys: [throttle, reverse, left, right, brake, handbrake],
+        }
+    }
+
+    /// Returns the key bound to an action
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys[action.index()]
+    }
+
+    /// Binds an action to a different key
+    pub fn set_key(&mut self, action: Action, key: KeyCode) {
+        self.keys[action.index()] = key;
+    }
+}
+
+/// Keyboard layout of both players
+///
+/// The default matches the original hardcoded controls:
+/// * Player 1: WASD + Space to brake, Left Shift for the handbrake
+/// * Player 2: Arrow keys + Right Shift to brake, Right Control for the handbrake
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBindings {
+    /// Bindings indexed by player
+    players: [PlayerBindings; 2],
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            players: [
+                PlayerBindings::new(
+                    KeyCode::KeyW,
+                    KeyCode::KeyS,
+                    KeyCode::KeyA,
+                    KeyCode::KeyD,
+                    KeyCode::Space,
+                    KeyCode::ShiftLeft,
+                ),
+                PlayerBindings::new(
+                    KeyCode::ArrowUp,
+                    KeyCode::ArrowDown,
+                    KeyCode::ArrowLeft,
+                    KeyCode::ArrowRight,
+                    KeyCode::ShiftRight,
+                    KeyCode::ControlRight,
+                ),
+            ],
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Creates the default keyboard layout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bindings of all players, indexed by player
+    pub fn players(&self) -> &[PlayerBindings] {
+        &self.players
+    }
+
+    /// Returns the bindings of a player, if the player exists
+    pub fn player(&self, player: usize) -> Option<&PlayerBindings> {
+        self.players.get(player)
+    }
+
+    /// Returns the key a player has bound to an action, if the player exists
+    pub fn key(&self, player: usize, action: Action) -> Option<KeyCode> {
+        self.player(player).map(|bindings| bindings.key(action))
+    }
+
+    /// Binds a player's action to a different key
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - Index of the player, unknown players are ignored
+    /// * `action` - Action to rebind
+    /// * `key` - New key for the action
+    pub fn set_key(&mut self, player: usize, action: Action, key: KeyCode) {
+        if let Some(bindings) = self.players.get_mut(player) {
+            bindings.set_key(action, key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_indices_match_all() {
+        for (i, action) in Action::ALL.iter().enumerate() {
+            assert_eq!(action.index(), i);
+        }
+    }
+
+    #[test]
+    fn keys_can_be_remapped_per_player() {
+        let mut bindings = KeyBindings::new();
+        assert_eq!(bindings.key(0, Action::Throttle), Some(KeyCode::KeyW));
+        assert_eq!(bindings.key(1, Action::Throttle), Some(KeyCode::ArrowUp));
+
+        bindings.set_key(1, Action::Throttle, KeyCode::KeyI);
+        assert_eq!(bindings.key(1, Action::Throttle), Some(KeyCode::KeyI));
+        assert_eq!(bindings.key(0, Action::Throttle), Some(KeyCode::KeyW));
+
+        bindings.set_key(2, Action::Throttle, KeyCode::KeyI);
+        assert_eq!(bindings.key(2, Action::Throttle), None);
+    }
+}
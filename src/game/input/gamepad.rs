@@ -0,0 +1,137 @@
+//! Analog gamepad state and polling
+
+/// Stick deflection below which the stick counts as centered
+const STICK_DEAD_ZONE: f32 = 0.15;
+
+/// Trigger travel below which the trigger counts as released
+const TRIGGER_DEAD_ZONE: f32 = 0.05;
+
+/// Analog controls read from one gamepad
+///
+/// Values use the same conventions as `CarInput`:
+/// * `steer`: -1.0 (full right) to 1.0 (full left)
+/// * `throttle`: 0.0 to 1.0 (right trigger)
+/// * `brake`: 0.0 to 1.0 (left trigger)
+///
+/// Values inside the dead zones are reported as exactly 0.0 so a
+/// resting gamepad never overrides the keyboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadState {
+    steer: f32,
+    throttle: f32,
+    brake: f32,
+}
+
+impl GamepadState {
+    /// Creates a gamepad state from raw axis values
+    ///
+    /// # Arguments
+    ///
+    /// * `stick_x` - Left stick X, -1.0 (left) to 1.0 (right)
+    /// * `right_trigger` - Right trigger travel, 0.0 to 1.0
+    /// * `left_trigger` - Left trigger travel, 0.0 to 1.0
+    pub fn new(stick_x: f32, right_trigger: f32, left_trigger: f32) -> Self {
+        Self {
+            steer: -Self::dead_zone(stick_x, STICK_DEAD_ZONE).clamp(-1.0, 1.0),
+            throttle: Self::dead_zone(right_trigger, TRIGGER_DEAD_ZONE).clamp(0.0, 1.0),
+            brake: Self::dead_zone(left_trigger, TRIGGER_DEAD_ZONE).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Get the steering value
+    pub fn steer(&self) -> f32 {
+        self.steer
+    }
+
+    /// Get the throttle value
+    pub fn throttle(&self) -> f32 {
+        self.throttle
+    }
+
+    /// Get the brake value
+    pub fn brake(&self) -> f32 {
+        self.brake
+    }
+
+    /// Zeroes values inside the dead zone (and NaN from broken drivers)
+    fn dead_zone(value: f32, dead_zone: f32) -> f32 {
+        if value.abs() > dead_zone {
+            value
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Connected gamepads polled through gilrs
+///
+/// The first two connected gamepads control player 1 and 2.
+#[cfg(feature = "gilrs")]
+pub struct Gamepads {
+    gilrs: gilrs::Gilrs,
+}
+
+#[cfg(feature = "gilrs")]
+impl Gamepads {
+    /// Opens the platform gamepad backend
+    ///
+    /// # Returns
+    ///
+    /// None if no backend is available on this system
+    pub fn new() -> Option<Self> {
+        match gilrs::Gilrs::new() {
+            Ok(gilrs) => Some(Self { gilrs }),
+            Err(err) => {
+                log::warn!("Gamepads unavailable: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Processes pending gamepad events and reads the current state
+    ///
+    /// # Returns
+    ///
+    /// State per player, None for players without a connected gamepad
+    pub fn poll(&mut self) -> [Option<GamepadState>; 2] {
+        use gilrs::{Axis, Button};
+
+        while self.gilrs.next_event().is_some() {}
+
+        let mut states = [None; 2];
+        let connected = self.gilrs.gamepads().filter(|(_, pad)| pad.is_connected());
+        for (state, (_, pad)) in states.iter_mut().zip(connected) {
+            let trigger = |button| pad.button_data(button).map_or(0.0, |data| data.value());
+            *state = Some(GamepadState::new(
+                pad.value(Axis::LeftStickX),
+                trigger(Button::RightTrigger2),
+                trigger(Button::LeftTrigger2),
+            ));
+        }
+
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_inputs_fall_in_the_dead_zone() {
+        let state = GamepadState::new(0.1, 0.04, 0.04);
+        assert_eq!(state, GamepadState::default());
+        assert_eq!(
+            GamepadState::new(f32::NAN, f32::NAN, 0.0),
+            GamepadState::default()
+        );
+    }
+
+    #[test]
+    fn stick_right_steers_right() {
+        let state = GamepadState::new(0.5, 2.0, 0.5);
+        assert_eq!(state.steer(), -0.5);
+        assert_eq!(state.throttle(), 1.0);
+        assert_eq!(state.brake(), 0.5);
+    }
+}
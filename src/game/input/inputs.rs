@@ -1,57 +1,224 @@
+#[cfg(feature = "gilrs")]
+use super::Gamepads;
+use super::{Action, GamepadState, KeyBindings};
 use crate::game::world::CarInput;
-use pix_win_loop::{Context, KeyCode, NamedKey};
+use pix_win_loop::Context;
+
+/// How keyboard players map to cars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputScheme {
+    /// Each car is driven by its own set of keys
+    #[default]
+    DualPlayer,
+    /// Car 1 is driven by both sets of keys, car 2 gets no input, e.g.
+    /// for a single player racing the AI
+    SinglePlayerBoth,
+}
 
 /// Input handler for two-player racing controls
 ///
 /// Manages keyboard input for dual car control:
-/// * Car 1: WASD keys + Space for brake
-/// * Car 2: Arrow keys + Shift for brake
+/// * Car 1: WASD keys + Space for brake + Left Shift for handbrake (by default)
+/// * Car 2: Arrow keys + Right Shift for brake + Right Control for handbrake (by default)
+/// * Keys are looked up in remappable `KeyBindings`
 /// * Updates per-frame input state
 /// * Converts key states to normalized controls
+/// * Optional steering auto-center that eases released steering back to 0
+/// * Analog gamepad input per player, taking precedence over keys when active
+/// * A single-player scheme merging both key sets into car 1
 pub struct Inputs {
-    // Car 1 - WASD controls
-    /// Car 1 Forward movement (W)
-    w: bool,
-    /// Car 1 Backward movement (S)
-    s: bool,
-    /// Car 1 Left turn (A)
-    a: bool,
-    /// Car 1 Right turn (D)
-    d: bool,
-    /// Car 1 Brake (Space)
-    space: bool,
-
-    // Car 2 - Arrow controls
-    /// Car 2 Forward movement (Up)
-    up: bool,
-    /// Car 2 Backward movement (Down)
-    down: bool,
-    /// Car 2 Left turn (Left)
-    left: bool,
-    /// Car 2 Right turn (Right)
-    right: bool,
-    /// Car 2 Brake (Shift)
-    shift: bool,
+    // Keyboard
+    /// Key of every action per car
+    bindings: KeyBindings,
+    /// Whether the key of each action is held, per car and `Action::index`
+    held: [[bool; Action::COUNT]; 2],
+    /// `held` as of the previous `update`, for edge detection
+    prev: [[bool; Action::COUNT]; 2],
+    /// How the key sets map to cars
+    scheme: InputScheme,
+
+    // Steering
+    /// Current steering value per car after auto-centering
+    turns: [f32; 2],
+    /// Auto-center time constant in seconds, None to stop steering instantly
+    auto_center: Option<f32>,
+
+    // Gamepads
+    /// Latest analog gamepad state per car
+    gamepads: [GamepadState; 2],
+    /// Gamepad backend, None if unavailable
+    #[cfg(feature = "gilrs")]
+    gamepad_backend: Option<Gamepads>,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Inputs {
-    /// Creates a new input handler with keys unpressed
+    /// Creates a new input handler with the default key bindings
     ///
     /// # Returns
     ///
     /// New input state with all controls inactive
     pub fn new() -> Self {
+        Self::with_bindings(KeyBindings::default())
+    }
+
+    /// Creates a new input handler with custom key bindings
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Key of every action per car
+    ///
+    /// # Returns
+    ///
+    /// New input state with all controls inactive
+    pub fn with_bindings(bindings: KeyBindings) -> Self {
         Self {
-            w: false,
-            s: false,
-            a: false,
-            d: false,
-            space: false,
-            up: false,
-            down: false,
-            left: false,
-            right: false,
-            shift: false,
+            bindings,
+            held: [[false; Action::COUNT]; 2],
+            prev: [[false; Action::COUNT]; 2],
+            scheme: InputScheme::DualPlayer,
+            turns: [0.0; 2],
+            auto_center: None,
+            gamepads: [GamepadState::default(); 2],
+            #[cfg(feature = "gilrs")]
+            gamepad_backend: Gamepads::new(),
+        }
+    }
+
+    /// Creates a new input handler with the default key bindings and a scheme
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - How the key sets map to cars
+    ///
+    /// # Returns
+    ///
+    /// New input state with all controls inactive
+    pub fn with_scheme(scheme: InputScheme) -> Self {
+        let mut inputs = Self::new();
+        inputs.set_scheme(scheme);
+        inputs
+    }
+
+    /// Returns how the key sets map to cars
+    pub fn scheme(&self) -> InputScheme {
+        self.scheme
+    }
+
+    /// Sets how the key sets map to cars, taking effect on the next `update`
+    pub fn set_scheme(&mut self, scheme: InputScheme) {
+        self.scheme = scheme;
+    }
+
+    /// Returns the key bindings
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    /// Replaces the key bindings, taking effect on the next `update`
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+    }
+
+    /// Returns whether the key of a car's action was held at the last `update`
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car, unknown indices report false
+    /// * `action` - Action to check
+    pub fn is_action_down(&self, car_index: usize, action: Action) -> bool {
+        self.held
+            .get(car_index)
+            .is_some_and(|held| held[action.index()])
+    }
+
+    /// Returns whether the key of a car's action went down at the last `update`
+    ///
+    /// Unlike [`Inputs::is_action_down`] this is only true for the one
+    /// update in which the key started being held.
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car, unknown indices report false
+    /// * `action` - Action to check
+    pub fn just_pressed(&self, car_index: usize, action: Action) -> bool {
+        self.is_action_down(car_index, action) && !self.was_action_down(car_index, action)
+    }
+
+    /// Returns whether the key of a car's action went up at the last `update`
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car, unknown indices report false
+    /// * `action` - Action to check
+    pub fn just_released(&self, car_index: usize, action: Action) -> bool {
+        !self.is_action_down(car_index, action) && self.was_action_down(car_index, action)
+    }
+
+    /// Returns whether the key of a car's action was held before the last `update`
+    fn was_action_down(&self, car_index: usize, action: Action) -> bool {
+        self.prev
+            .get(car_index)
+            .is_some_and(|prev| prev[action.index()])
+    }
+
+    /// Returns the steering auto-center time constant in seconds
+    pub fn auto_center(&self) -> Option<f32> {
+        self.auto_center
+    }
+
+    /// Sets the steering auto-center time constant
+    ///
+    /// With `Some(time_constant)`, releasing the steering keys eases the
+    /// turn value back to 0 instead of dropping it instantly. Smaller time
+    /// constants center faster. `None` disables auto-centering.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_constant` - Seconds for the turn value to decay to ~37%
+    pub fn set_auto_center(&mut self, time_constant: Option<f32>) {
+        self.auto_center = time_constant.filter(|tc| *tc > 0.0);
+    }
+
+    /// Returns the latest gamepad state of a car, if the car exists
+    pub fn gamepad(&self, car_index: usize) -> Option<GamepadState> {
+        self.gamepads.get(car_index).copied()
+    }
+
+    /// Sets the gamepad state of a car
+    ///
+    /// Called by `update` when gamepad polling is enabled, but can also be
+    /// used to feed analog input from another source.
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car, unknown indices are ignored
+    /// * `state` - Analog gamepad state
+    pub fn set_gamepad(&mut self, car_index: usize, state: GamepadState) {
+        if let Some(gamepad) = self.gamepads.get_mut(car_index) {
+            *gamepad = state;
+        }
+    }
+
+    /// Combines a keyboard value with an analog gamepad value
+    ///
+    /// The analog value wins whenever it is non-zero, so either device
+    /// works and a deflected stick is never cut off by a digital key.
+    ///
+    /// # Arguments
+    ///
+    /// * `keyboard` - Value from the digital keys
+    /// * `analog` - Value from the gamepad (0.0 when at rest)
+    pub fn mix(keyboard: f32, analog: f32) -> f32 {
+        if analog != 0.0 {
+            analog
+        } else {
+            keyboard
         }
     }
 
@@ -65,89 +232,253 @@ impl Inputs {
     ///
     /// Self reference for method chaining
     pub fn update(&mut self, ctx: &Context) -> &Self {
-        // Update WASD states
-        self.w = ctx.input.is_physical_key_down(KeyCode::KeyW);
-        self.s = ctx.input.is_physical_key_down(KeyCode::KeyS);
-        self.a = ctx.input.is_physical_key_down(KeyCode::KeyA);
-        self.d = ctx.input.is_physical_key_down(KeyCode::KeyD);
-        self.space = ctx.input.is_physical_key_down(KeyCode::Space);
-
-        // Update arrow key states
-        self.up = ctx.input.is_physical_key_down(KeyCode::ArrowUp);
-        self.down = ctx.input.is_physical_key_down(KeyCode::ArrowDown);
-        self.left = ctx.input.is_physical_key_down(KeyCode::ArrowLeft);
-        self.right = ctx.input.is_physical_key_down(KeyCode::ArrowRight);
-        self.shift = ctx.input.is_logical_key_down(NamedKey::Shift);
+        // Update key states of both cars from their bindings
+        let mut held = [[false; Action::COUNT]; 2];
+        for (held, bindings) in held.iter_mut().zip(self.bindings.players()) {
+            for action in Action::ALL {
+                held[action.index()] = ctx.input.is_physical_key_down(bindings.key(action));
+            }
+        }
+        self.set_held(held);
+
+        #[cfg(feature = "gilrs")]
+        if let Some(backend) = &mut self.gamepad_backend {
+            let polled = backend.poll();
+            for (gamepad, state) in self.gamepads.iter_mut().zip(polled) {
+                *gamepad = state.unwrap_or_default();
+            }
+        }
+
+        self.update_steering(ctx.frame_time().as_secs_f32());
 
         self
     }
 
-    /// Converts current key states to car control inputs
+    /// Stores the keys held this update, keeping the previous ones for edges
     ///
-    /// # Returns
+    /// With [`InputScheme::SinglePlayerBoth`] the second key set is merged
+    /// into the first car's.
     ///
-    /// Array of two CarInputs:
-    /// * \[0\]: Car 1 controls from WASD
-    /// * \[1\]: Car 2 controls from arrows
-    pub fn get_car_inputs(&self) -> [CarInput; 2] {
-        [self.get_car1_input(), self.get_car2_input()]
+    /// # Arguments
+    ///
+    /// * `held` - Whether each action's key is down, per key set
+    fn set_held(&mut self, mut held: [[bool; Action::COUNT]; 2]) {
+        // A single player holds an action with a key of either set
+        if self.scheme == InputScheme::SinglePlayerBoth {
+            let [first, second] = &mut held;
+            for (held, other) in first.iter_mut().zip(second.iter_mut()) {
+                *held |= std::mem::take(other);
+            }
+        }
+
+        self.prev = self.held;
+        self.held = held;
     }
 
-    /// Processes WASD controls for car 1
+    /// Advances the per-car steering values by one input frame
     ///
-    /// Creates normalized inputs (-1.0 to 1.0):
-    /// * W/S: Forward/Backward throttle
-    /// * A/D: Left/Right steering
-    /// * Space: Brake (0.0 to 1.0)
-    fn get_car1_input(&self) -> CarInput {
-        // Calculate control values
-        let throttle = if self.w {
-            1.0
-        } else if self.s {
-            -1.0
-        } else {
+    /// Held steering keys or a deflected stick set the turn value directly,
+    /// released controls either zero it or let it decay when auto-center
+    /// is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Time since the previous input frame in seconds
+    pub fn update_steering(&mut self, dt: f32) {
+        let raw_turns = [0, 1].map(|car| {
+            let keys = Self::axis(
+                self.is_action_down(car, Action::Left),
+                self.is_action_down(car, Action::Right),
+            );
+            Self::mix(keys, self.gamepads[car].steer())
+        });
+
+        for (turn, raw) in self.turns.iter_mut().zip(raw_turns) {
+            *turn = if raw != 0.0 {
+                raw
+            } else if let Some(time_constant) = self.auto_center {
+                Self::center_turn(*turn, dt, time_constant)
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Eases a turn value towards 0 with exponential decay
+    ///
+    /// # Arguments
+    ///
+    /// * `turn` - Current turn value
+    /// * `dt` - Elapsed time in seconds
+    /// * `time_constant` - Decay time constant in seconds
+    ///
+    /// # Returns
+    ///
+    /// The decayed turn value, snapped to 0 once it becomes negligible
+    pub fn center_turn(turn: f32, dt: f32, time_constant: f32) -> f32 {
+        let centered = turn * (-dt / time_constant).exp();
+        if centered.abs() < 0.01 {
             0.0
-        };
+        } else {
+            centered
+        }
+    }
 
-        let turn = if self.a {
+    /// Converts a pair of opposing keys to an axis value
+    fn axis(positive: bool, negative: bool) -> f32 {
+        if positive {
             1.0
-        } else if self.d {
+        } else if negative {
             -1.0
         } else {
             0.0
-        };
-
-        let brake = if self.space { 1.0 } else { 0.0 };
+        }
+    }
 
-        CarInput::new(throttle, turn, brake)
+    /// Converts current key states to car control inputs
+    ///
+    /// # Returns
+    ///
+    /// One CarInput per keyboard player:
+    /// * \[0\]: Car 1 controls (WASD by default, plus arrows in single player)
+    /// * \[1\]: Car 2 controls (arrows by default, neutral in single player)
+    pub fn get_car_inputs(&self) -> Vec<CarInput> {
+        (0..self.held.len())
+            .map(|car| self.get_car_input(car))
+            .collect()
     }
 
-    /// Processes arrow key controls for car 2
+    /// Processes the controls of one car
     ///
     /// Creates normalized inputs (-1.0 to 1.0):
-    /// * Up/Down: Forward/Backward throttle
-    /// * Left/Right: Left/Right steering  
-    /// * Shift: Brake (0.0 to 1.0)
-    fn get_car2_input(&self) -> CarInput {
-        // Calculate control values
-        let throttle = if self.up {
-            1.0
-        } else if self.down {
-            -1.0
-        } else {
-            0.0
-        };
+    /// * Throttle/Reverse: Forward/Backward throttle
+    /// * Left/Right: Left/Right steering (after auto-center)
+    /// * Brake: Brake (0.0 to 1.0)
+    /// * Handbrake: Handbrake (on/off)
+    ///
+    /// The car's gamepad triggers override the keys while pressed. With
+    /// [`InputScheme::SinglePlayerBoth`] car 2 always gets neutral input.
+    fn get_car_input(&self, car: usize) -> CarInput {
+        if self.scheme == InputScheme::SinglePlayerBoth && car == 1 {
+            return CarInput::default();
+        }
 
-        let turn = if self.left {
+        let throttle = Self::axis(
+            self.is_action_down(car, Action::Throttle),
+            self.is_action_down(car, Action::Reverse),
+        );
+        let throttle = Self::mix(throttle, self.gamepads[car].throttle());
+
+        let turn = self.turns[car];
+
+        let brake = if self.is_action_down(car, Action::Brake) {
             1.0
-        } else if self.right {
-            -1.0
         } else {
             0.0
         };
-
-        let brake = if self.shift { 1.0 } else { 0.0 };
+        let brake = Self::mix(brake, self.gamepads[car].brake());
 
         CarInput::new(throttle, turn, brake)
+            .with_handbrake(self.is_action_down(car, Action::Handbrake))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulates an `update` with the given actions held per car
+    fn press(inputs: &mut Inputs, held: [&[Action]; 2]) {
+        inputs.set_held(held.map(|actions| Action::ALL.map(|action| actions.contains(&action))));
+        inputs.update_steering(1.0 / 60.0);
+    }
+
+    #[test]
+    fn edges_last_one_update() {
+        let mut inputs = Inputs::new();
+        press(&mut inputs, [&[Action::Brake], &[]]);
+        assert!(inputs.just_pressed(0, Action::Brake));
+        assert!(!inputs.just_pressed(1, Action::Brake));
+
+        press(&mut inputs, [&[Action::Brake], &[]]);
+        assert!(inputs.is_action_down(0, Action::Brake));
+        assert!(!inputs.just_pressed(0, Action::Brake));
+
+        press(&mut inputs, [&[], &[]]);
+        assert!(inputs.just_released(0, Action::Brake));
+        assert!(!inputs.just_pressed(5, Action::Brake));
+    }
+
+    #[test]
+    fn keys_become_car_inputs() {
+        let mut inputs = Inputs::new();
+        press(
+            &mut inputs,
+            [
+                &[Action::Throttle, Action::Left, Action::Handbrake],
+                &[Action::Reverse, Action::Right, Action::Brake],
+            ],
+        );
+        let cars = inputs.get_car_inputs();
+        assert_eq!(cars[0], CarInput::new(1.0, 1.0, 0.0).with_handbrake(true));
+        assert_eq!(cars[1], CarInput::new(-1.0, -1.0, 1.0));
+    }
+
+    #[test]
+    fn active_gamepads_override_keys() {
+        let mut inputs = Inputs::new();
+        inputs.set_gamepad(0, GamepadState::new(1.0, 0.5, 0.0));
+        press(&mut inputs, [&[Action::Throttle, Action::Left], &[]]);
+        let car = inputs.get_car_inputs()[0];
+        assert_eq!(car.throttle(), 0.5);
+        assert_eq!(car.turn(), -1.0);
+    }
+
+    #[test]
+    fn single_player_leaves_car_two_neutral() {
+        let mut inputs = Inputs::with_scheme(InputScheme::SinglePlayerBoth);
+        press(&mut inputs, [&[Action::Throttle], &[Action::Throttle]]);
+        let cars = inputs.get_car_inputs();
+        assert_eq!(cars[0].throttle(), 1.0);
+        assert_eq!(cars[1], CarInput::default());
+    }
+
+    #[test]
+    fn auto_center_eases_steering_back() {
+        let mut inputs = Inputs::new();
+        inputs.set_auto_center(Some(0.1));
+        press(&mut inputs, [&[Action::Left], &[]]);
+        press(&mut inputs, [&[], &[]]);
+        let turn = inputs.get_car_inputs()[0].turn();
+        assert!(turn > 0.0 && turn < 1.0);
+
+        for _ in 0..120 {
+            press(&mut inputs, [&[], &[]]);
+        }
+        assert_eq!(inputs.get_car_inputs()[0].turn(), 0.0);
+
+        inputs.set_auto_center(Some(0.0));
+        assert_eq!(inputs.auto_center(), None);
+    }
+
+    #[test]
+    fn schemes_route_each_key_set() {
+        // (scheme, key set pressed, car expected to move)
+        let cases = [
+            (InputScheme::DualPlayer, 0, 0),
+            (InputScheme::DualPlayer, 1, 1),
+            (InputScheme::SinglePlayerBoth, 0, 0),
+            (InputScheme::SinglePlayerBoth, 1, 0),
+        ];
+        for (scheme, keys, car) in cases {
+            let mut inputs = Inputs::with_scheme(scheme);
+            let mut held: [&[Action]; 2] = [&[], &[]];
+            held[keys] = &[Action::Throttle];
+            press(&mut inputs, held);
+
+            let cars = inputs.get_car_inputs();
+            assert_eq!(cars[car].throttle(), 1.0, "{scheme:?} keys {keys}");
+            assert_eq!(cars[1 - car], CarInput::default(), "{scheme:?} keys {keys}");
+        }
     }
 }
@@ -1,7 +1,14 @@
 //! Input state tracking and key registration
 //!
 //! Tracks the current state of keyboard inputs for two players.
-//! Simply registers which keys are pressed or released.
+//! Simply registers which bound keys are pressed or released, optionally
+//! combined with analog gamepad input (`gilrs` feature).
 
+mod bindings;
+mod gamepad;
 mod inputs;
-pub use inputs::Inputs;
+pub use bindings::{Action, KeyBindings, PlayerBindings};
+pub use gamepad::GamepadState;
+#[cfg(feature = "gilrs")]
+pub use gamepad::Gamepads;
+pub use inputs::{InputScheme, Inputs};
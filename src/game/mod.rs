@@ -1,8 +1,20 @@
-//! TODO: Add docs
+//! Everything that makes up a race
+//!
+//! This is the only home of the game types, there are no parallel
+//! implementations elsewhere in the crate:
+//! * [`world::Car`] and [`world::World`] for the simulation
+//! * [`rendering::Renderer`] for drawing, with [`crate::assets::Texture`]
+//!   holding the pixels
+//! * [`glam::Vec2`] for all vector math
 
+pub mod ai;
 pub mod camera; // TODO: Move inside the renderer module
+#[cfg(debug_assertions)]
+pub mod debug;
 pub mod input;
 
 pub mod rendering;
+pub mod replay;
+pub mod track;
 pub mod utils;
 pub mod world;
@@ -0,0 +1,59 @@
+//! Per-pixel coloring of the ground plane
+
+use crate::assets::{SampleMode, Texture, WrapMode};
+use glam::Vec2;
+
+/// Colors the ground plane one pixel at a time
+///
+/// The renderer projects every ground pixel to world space and asks the
+/// shader for its color. Fog, time of day and the debug grid are applied
+/// on top afterwards. Closures taking `(world_x, world_y, depth)` are
+/// shaders too.
+pub trait GroundShader {
+    /// Returns the color of the ground at a world position
+    ///
+    /// # Arguments
+    ///
+    /// * `world_x` - X position in world space
+    /// * `world_y` - Y position in world space
+    /// * `depth` - Distance from the camera along the view direction
+    fn shade(&self, world_x: f32, world_y: f32, depth: f32) -> [u8; 4];
+}
+
+impl<F> GroundShader for F
+where
+    F: Fn(f32, f32, f32) -> [u8; 4],
+{
+    fn shade(&self, world_x: f32, world_y: f32, depth: f32) -> [u8; 4] {
+        self(world_x, world_y, depth)
+    }
+}
+
+/// Samples a texture stretched over the ground, the renderer's default
+///
+/// Borrowing the texture lets custom shaders reuse it, e.g. to blend two
+/// textures.
+#[derive(Debug, Clone, Copy)]
+pub struct TextureShader<'a> {
+    /// Texture covering the ground
+    pub texture: &'a Texture,
+    /// Texels per world unit on each axis
+    pub texel_scale: Vec2,
+    /// Whether the texture tiles or ends at its edges
+    pub wrap: WrapMode,
+    /// Color outside the texture when it doesn't tile
+    pub void_color: [u8; 4],
+}
+
+impl GroundShader for TextureShader<'_> {
+    /// Samples the texture bilinearly, ignoring the depth
+    fn shade(&self, world_x: f32, world_y: f32, _depth: f32) -> [u8; 4] {
+        self.texture.sample_mode(
+            world_x * self.texel_scale.x,
+            world_y * self.texel_scale.y,
+            self.void_color,
+            SampleMode::Bilinear,
+            self.wrap,
+        )
+    }
+}
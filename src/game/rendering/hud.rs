@@ -0,0 +1,68 @@
+/// Race info shown in the corner of a player's view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerHud {
+    /// Speed of the player's car in units/s
+    pub speed: f32,
+    /// Laps the player has completed
+    pub laps_completed: u32,
+    /// Laps needed to finish the race
+    pub laps: u32,
+    /// Place in the race, 1 being in the lead
+    pub position: usize,
+}
+
+impl PlayerHud {
+    /// Returns the speed line, e.g. `"SPD: 042"`
+    ///
+    /// Speeds are rounded and capped at 999 so the line never grows,
+    /// reversing shows the speed backwards.
+    pub fn speed_text(&self) -> String {
+        format!("SPD: {:03.0}", self.speed.abs().min(999.0))
+    }
+
+    /// Returns the lap line, e.g. `"LAP: 2/3"`
+    ///
+    /// Shows the lap being driven, staying on the last lap once finished.
+    pub fn lap_text(&self) -> String {
+        let lap = (self.laps_completed + 1).min(self.laps);
+        format!("LAP: {}/{}", lap, self.laps)
+    }
+
+    /// Returns the position line, e.g. `"POS: 1st"`
+    pub fn position_text(&self) -> String {
+        let suffix = match (self.position % 10, self.position % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("POS: {}{}", self.position, suffix)
+    }
+
+    /// Returns all lines top to bottom
+    pub fn lines(&self) -> [String; 3] {
+        [self.speed_text(), self.lap_text(), self.position_text()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hud(speed: f32) -> PlayerHud {
+        PlayerHud {
+            speed,
+            laps_completed: 0,
+            laps: 3,
+            position: 1,
+        }
+    }
+
+    #[test]
+    fn speed_is_zero_padded() {
+        assert_eq!(hud(42.0).speed_text(), "SPD: 042");
+        assert_eq!(hud(-42.0).speed_text(), "SPD: 042");
+        assert_eq!(hud(5000.0).speed_text(), "SPD: 999");
+    }
+}
@@ -3,8 +3,24 @@
 //! Implements an SNES Mode 7-style renderer with perspective-correct
 //! texture mapping. Handles all visual aspects.
 
+mod ground_shader;
+mod hud;
+mod render_config;
 mod renderable;
 mod renderer;
+mod screenshot;
+mod sky;
+mod split;
+mod sprite;
+mod time_of_day;
 
+pub use ground_shader::{GroundShader, TextureShader};
+pub use hud::PlayerHud;
+pub use render_config::RenderConfig;
 pub use renderable::Renderable;
 pub use renderer::Renderer;
+pub use screenshot::save_frame;
+pub use sky::SkyConfig;
+pub use split::SplitConfig;
+pub use sprite::Sprite;
+pub use time_of_day::TimeOfDay;
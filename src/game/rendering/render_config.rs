@@ -0,0 +1,35 @@
+use crate::consts::{PIXELS_HEIGHT, PIXELS_WIDTH};
+
+/// Internal resolution the game is drawn at
+///
+/// The frame buffer is scaled to the window, so a smaller resolution
+/// trades sharpness for speed without changing the window size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderConfig {
+    /// Frame buffer width in pixels
+    pub width: u32,
+    /// Frame buffer height in pixels
+    pub height: u32,
+}
+
+impl Default for RenderConfig {
+    /// Creates the compile-time default of `PIXELS_WIDTH`x`PIXELS_HEIGHT`
+    fn default() -> Self {
+        Self::new(PIXELS_WIDTH, PIXELS_HEIGHT)
+    }
+}
+
+impl RenderConfig {
+    /// Creates a config for a frame buffer size
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Frame buffer width in pixels, clamped to ≥ 1
+    /// * `height` - Frame buffer height in pixels, clamped to ≥ 1
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width: width.max(1),
+            height: height.max(1),
+        }
+    }
+}
@@ -9,13 +9,57 @@ use glam::Vec2;
 /// * Rotation angle
 /// * Base rendering size
 /// * Associated texture file
+/// * Optional width and height, draw layer, source rectangle, color
+///   tint and ground shadow
 pub trait Renderable {
     /// Get the position of the entity in world space
     fn position(&self) -> Vec2;
 
+    /// Get the position to draw the entity at between two physics steps
+    ///
+    /// `alpha` goes from 0.0 (previous step) to 1.0 (latest step).
+    /// Entities that don't move between steps keep the default.
+    fn interpolated_position(&self, _alpha: f32) -> Vec2 {
+        self.position()
+    }
+
+    /// Get the heading of the entity in radians, using the same
+    /// convention as `Camera::angle`
+    fn rotation(&self) -> f32;
+
     /// Get the base size for rendering
     fn base_size(&self) -> f32;
 
+    /// Get the width and height in world units, a `base_size` square by default
+    fn size(&self) -> Vec2 {
+        Vec2::splat(self.base_size())
+    }
+
+    /// Get the draw layer, higher layers are drawn over lower ones
+    /// whatever their distance
+    fn layer(&self) -> i32 {
+        0
+    }
+
     /// Get the texture filename for this entity
     fn texture_file(&self, world: &World) -> &str;
+
+    /// Whether a shadow is drawn on the ground below the entity
+    fn casts_shadow(&self) -> bool {
+        false
+    }
+
+    /// Get the part of the texture that is drawn as `(x, y, width, height)`
+    ///
+    /// None draws the whole texture. Animated entities return the
+    /// current frame of their sprite sheet, see
+    /// [`AnimatedTexture::frame_uv`](crate::assets::AnimatedTexture::frame_uv).
+    fn source_rect(&self, _world: &World) -> Option<(u32, u32, u32, u32)> {
+        None
+    }
+
+    /// Get the color each texel is multiplied with, white leaves the texture as is
+    fn tint(&self) -> [u8; 4] {
+        [255; 4]
+    }
 }
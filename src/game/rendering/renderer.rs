@@ -1,7 +1,26 @@
 use super::super::camera::Camera;
 use super::super::rendering::Renderable;
-use crate::assets::{AssetManager, Texture};
+use super::{GroundShader, SkyConfig, TextureShader, TimeOfDay};
+use crate::assets::{AssetManager, Texture, WrapMode};
+use crate::game::utils::{blend_pixel_at, put_pixel};
 use crate::game::world::World;
+use crate::menu::{draw_text_scaled, text_metrics, TEXT_SCALE};
+use glam::Vec2;
+use rusttype::Font;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+/// Ground color outside the track texture, the grass at its edges
+const DEFAULT_VOID_COLOR: [u8; 4] = [70, 144, 70, 255];
+
+/// Default distance between two debug grid lines in world units
+const DEFAULT_GRID_SPACING: f32 = 64.0;
+
+/// Width of a debug grid line in world units
+const GRID_LINE_WIDTH: f32 = 2.0;
+
+/// Color of the debug grid lines
+const GRID_COLOR: [u8; 4] = [255, 255, 0, 255];
 
 /// A Mode 7-style renderer for perspective-correct texture mapping
 ///
@@ -9,7 +28,8 @@ use crate::game::world::World;
 /// * Ground plane perspective transformation
 /// * Configurable camera with position, angle, and scale
 /// * Bilinear texture sampling for ground plane and sprites
-/// * Horizon rendering with solid background // TODO: Make background pretty
+/// * Pluggable ground shaders replacing the ground texture
+/// * Sky gradient above the horizon
 /// * Screen-to-world and back coordinate mapping
 /// * Texture-mapped sprite rendering with rotation
 ///
@@ -21,6 +41,30 @@ pub struct Renderer {
     viewport_width: u32,
     /// Output viewport height in pixels
     viewport_height: u32,
+    /// Ground texels per world unit on each axis
+    texel_scale: Vec2,
+    /// Colors of the sky above the horizon
+    sky: SkyConfig,
+    /// Strength of the distance fog, 0.0 disables it
+    fog_density: f32,
+    /// Whether the ground texture tiles infinitely instead of ending
+    wrap_ground: bool,
+    /// Progress between the previous and latest physics step entities are drawn at
+    blending_factor: f32,
+    /// Lighting of the ground and sky
+    time_of_day: TimeOfDay,
+    /// Ground color outside the ground texture when it doesn't tile
+    void_color: [u8; 4],
+    /// Whether world-space grid lines are drawn over the ground
+    draw_grid: bool,
+    /// Distance between two grid lines in world units
+    grid_spacing: f32,
+    /// Custom ground coloring, None samples the ground texture
+    ground_shader: Option<Box<dyn GroundShader>>,
+    /// Screen rows each projected ground row is repeated over
+    ground_lod: u32,
+    /// Texture names entities asked for that aren't loaded, warned about once
+    missing_textures: RefCell<HashSet<String>>,
 }
 
 impl Renderer {
@@ -34,112 +78,309 @@ impl Renderer {
     ///
     /// # Returns
     ///
-    /// Configured renderer for the specified dimensions
+    /// Configured renderer for the specified dimensions, mapping one
+    /// ground texel to one world unit, with the default sky
     pub fn new(viewport_width: u32, viewport_height: u32, ground_texture: Texture) -> Self {
         Self {
             ground_texture,
             viewport_width,
             viewport_height,
+            texel_scale: Vec2::ONE,
+            sky: SkyConfig::default(),
+            fog_density: 1.0,
+            wrap_ground: false,
+            blending_factor: 1.0,
+            time_of_day: TimeOfDay::default(),
+            void_color: DEFAULT_VOID_COLOR,
+            draw_grid: false,
+            grid_spacing: DEFAULT_GRID_SPACING,
+            ground_shader: None,
+            ground_lod: 1,
+            missing_textures: RefCell::new(HashSet::new()),
         }
     }
 
-    /// Maps screen coordinates to world space
+    /// Returns the colors of the sky above the horizon
+    pub fn sky(&self) -> &SkyConfig {
+        &self.sky
+    }
+
+    /// Sets the colors of the sky above the horizon
+    pub fn set_sky(&mut self, sky: SkyConfig) {
+        self.sky = sky;
+    }
+
+    /// Returns whether world-space grid lines are drawn over the ground
+    pub fn draw_grid(&self) -> bool {
+        self.draw_grid
+    }
+
+    /// Sets whether world-space grid lines are drawn over the ground
     ///
-    /// Performs perspective projection using:
-    /// * Camera height for z-depth calculation
-    /// * Pitch angle for horizon determination
-    /// * View angle for world rotation
-    /// * Scale for world space sizing
+    /// A debug aid for seeing how the ground projection maps world space.
+    pub fn set_draw_grid(&mut self, draw_grid: bool) {
+        self.draw_grid = draw_grid;
+    }
+
+    /// Returns the distance between two grid lines in world units
+    pub fn grid_spacing(&self) -> f32 {
+        self.grid_spacing
+    }
+
+    /// Sets the distance between two grid lines in world units
+    ///
+    /// Values below the line width are clamped to it, so the grid never
+    /// covers the whole ground.
+    pub fn set_grid_spacing(&mut self, grid_spacing: f32) {
+        self.grid_spacing = grid_spacing.max(GRID_LINE_WIDTH * 2.0);
+    }
+
+    /// Returns whether a world position lies on a grid line
     ///
     /// # Arguments
     ///
-    /// * `screen_x` - X position in screen space
-    /// * `screen_y` - Y position in screen space
-    /// * `camera` - View transformation parameters
+    /// * `world` - Position in world space
+    /// * `spacing` - Distance between two grid lines
+    /// * `line_width` - Width of a line, centered on multiples of `spacing`
+    pub fn is_on_grid_line(world: Vec2, spacing: f32, line_width: f32) -> bool {
+        let half_width = line_width / 2.0;
+        let near_line = |coordinate: f32| {
+            let offset = coordinate.rem_euclid(spacing);
+            offset <= half_width || spacing - offset <= half_width
+        };
+        near_line(world.x) || near_line(world.y)
+    }
+
+    /// Returns the time of day from 0.0 (noon) to 1.0 (night)
+    pub fn time_of_day(&self) -> f32 {
+        self.time_of_day.value()
+    }
+
+    /// Sets the time of day the ground and sky are tinted for
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// World space coordinates if visible, None if occluded
-    fn transform(&self, screen_x: f32, screen_y: f32, camera: &Camera) -> Option<(f32, f32)> {
-        let x = (screen_x - self.viewport_width as f32 / 2.0) / self.viewport_width as f32 * 2.0;
-        let y =
-            (screen_y - (self.viewport_height as f32 / 2.0)) / self.viewport_height as f32 * 2.0;
+    /// * `time` - 0.0 (noon, no tint) to 1.0 (night), see [`TimeOfDay`]
+    pub fn set_time_of_day(&mut self, time: f32) {
+        self.time_of_day = TimeOfDay::new(time);
+    }
 
-        let horizon = camera.pitch.tan() * 0.5;
-        if y < horizon {
-            return None;
-        }
+    /// Returns the ground color outside the ground texture
+    pub fn void_color(&self) -> [u8; 4] {
+        self.void_color
+    }
+
+    /// Sets the ground color outside the ground texture
+    ///
+    /// Only visible while the ground doesn't tile (see
+    /// [`Renderer::set_wrap_ground`]). Fogged like the ground itself.
+    pub fn set_void_color(&mut self, color: [u8; 4]) {
+        self.void_color = color;
+    }
 
-        let z = camera.height / (y - horizon + 0.00001);
-        if z <= camera.near || z >= camera.far {
-            return None;
+    /// Returns how many screen rows share one projected ground row
+    pub fn ground_lod(&self) -> u32 {
+        self.ground_lod
+    }
+
+    /// Sets how many screen rows share one projected ground row
+    ///
+    /// 1 projects every row. Higher values project every `lod`-th row
+    /// and copy it into the rows below, trading vertical detail for
+    /// speed on weak hardware. 0 is treated as 1.
+    pub fn set_ground_lod(&mut self, lod: u32) {
+        self.ground_lod = lod.max(1);
+    }
+
+    /// Returns the sky color right above the horizon
+    pub fn horizon_color(&self) -> [u8; 4] {
+        self.sky.horizon
+    }
+
+    /// Sets the sky color right above the horizon
+    ///
+    /// Distant ground fades into this color too, so the ground and sky
+    /// meet without a seam.
+    pub fn set_horizon_color(&mut self, color: [u8; 4]) {
+        self.sky.horizon = color;
+    }
+
+    /// Returns the strength of the distance fog
+    pub fn fog_density(&self) -> f32 {
+        self.fog_density
+    }
+
+    /// Sets the strength of the distance fog
+    ///
+    /// Ground fades into the sky's horizon color towards the far plane.
+    /// 1.0 fades linearly with depth, higher values fog in earlier,
+    /// lower values later and 0.0 disables fog. Negative values are
+    /// clamped to 0.0.
+    pub fn set_fog_density(&mut self, fog_density: f32) {
+        self.fog_density = fog_density.max(0.0);
+    }
+
+    /// Returns whether the ground texture tiles infinitely
+    pub fn wrap_ground(&self) -> bool {
+        self.wrap_ground
+    }
+
+    /// Sets whether the ground texture tiles infinitely
+    ///
+    /// When false (the default) everything outside the ground texture
+    /// is drawn in the void color (see [`Renderer::set_void_color`]), making the track a finite island.
+    pub fn set_wrap_ground(&mut self, wrap_ground: bool) {
+        self.wrap_ground = wrap_ground;
+    }
+
+    /// Returns where between two physics steps entities are drawn
+    pub fn blending_factor(&self) -> f32 {
+        self.blending_factor
+    }
+
+    /// Sets where between two physics steps entities are drawn
+    ///
+    /// # Arguments
+    ///
+    /// * `blending_factor` - 0.0 draws entities at their previous step,
+    ///   1.0 (the default) at their latest, clamped to that range
+    pub fn set_blending_factor(&mut self, blending_factor: f32) {
+        self.blending_factor = if blending_factor.is_nan() {
+            1.0
+        } else {
+            blending_factor.clamp(0.0, 1.0)
+        };
+    }
+
+    /// Computes how much a ground texel at depth `z` is fogged
+    ///
+    /// # Returns
+    ///
+    /// Blend factor towards the fog color, 0.0 at the near plane up
+    /// to 1.0 at the far plane
+    pub fn fog_factor(&self, z: f32, camera: &Camera) -> f32 {
+        if self.fog_density <= 0.0 || camera.far() <= camera.near() {
+            return 0.0;
         }
 
-        let world_x = x * z * camera.scale;
-        let world_z = z;
+        let t = ((z - camera.near()) / (camera.far() - camera.near())).clamp(0.0, 1.0);
+        t.powf(1.0 / self.fog_density)
+    }
 
-        let (sin_angle, cos_angle) = camera.angle.sin_cos();
-        let rotated_x = world_x * cos_angle - world_z * sin_angle;
-        let rotated_z = world_x * sin_angle + world_z * cos_angle;
+    /// Returns the number of ground texels per world unit on each axis
+    pub fn texel_scale(&self) -> Vec2 {
+        self.texel_scale
+    }
 
-        Some((rotated_x + camera.x, rotated_z + camera.y))
+    /// Sets the number of ground texels per world unit on each axis
+    ///
+    /// # Arguments
+    ///
+    /// * `texel_scale` - Texels per world unit, e.g. `(0.5, 0.5)` for a
+    ///   512² texture covering a 1024² world
+    pub fn set_texel_scale(&mut self, texel_scale: Vec2) {
+        self.texel_scale = texel_scale;
     }
 
-    /// Maps world space coordinates to screen space
+    /// Stretches the ground texture to cover a world of the given size
     ///
-    /// Performs inverse perspective projection:
-    /// 1. Untranslate from camera position
-    /// 2. Unrotate by camera angle
-    /// 3. Project to screen space using camera parameters
+    /// # Arguments
+    ///
+    /// * `world_size` - Width and height of the world in world units
+    pub fn fit_ground_to_world(&mut self, world_size: Vec2) {
+        self.texel_scale = Vec2::new(
+            self.ground_texture.width() as f32 / world_size.x,
+            self.ground_texture.height() as f32 / world_size.y,
+        );
+    }
+
+    /// Maps a world position to ground texture coordinates
     ///
     /// # Arguments
     ///
     /// * `world_x` - X position in world space
     /// * `world_y` - Y position in world space
-    /// * `camera` - View transformation parameters
     ///
     /// # Returns
     ///
-    /// Screen space coordinates if visible, None if occluded
-    fn untransform(&self, world_x: f32, world_y: f32, camera: &Camera) -> Option<(f32, f32)> {
-        let untranslated_x = world_x - camera.x;
-        let untranslated_y = world_y - camera.y;
+    /// Texel coordinates to sample the ground texture at
+    pub fn world_to_texel(&self, world_x: f32, world_y: f32) -> (f32, f32) {
+        (world_x * self.texel_scale.x, world_y * self.texel_scale.y)
+    }
 
-        let (sin_angle, cos_angle) = camera.angle.sin_cos();
-        let unrotated_x = untranslated_x * cos_angle + untranslated_y * sin_angle;
-        let unrotated_y = -untranslated_x * sin_angle + untranslated_y * cos_angle;
+    /// Replaces the texture used for the ground plane
+    ///
+    /// # Arguments
+    ///
+    /// * `ground_texture` - New ground plane texture, e.g. from `TileMap::to_texture`
+    pub fn set_ground_texture(&mut self, ground_texture: Texture) {
+        self.ground_texture = ground_texture;
+    }
 
-        let z = unrotated_y;
-        if z <= camera.near || z >= camera.far {
-            return None;
-        }
+    /// Colors the ground with a custom shader instead of the ground texture
+    ///
+    /// Fog, time of day and the debug grid still apply on top.
+    pub fn set_ground_shader(&mut self, ground_shader: Box<dyn GroundShader>) {
+        self.ground_shader = Some(ground_shader);
+    }
+
+    /// Goes back to coloring the ground with the ground texture
+    pub fn clear_ground_shader(&mut self) {
+        self.ground_shader = None;
+    }
 
-        let scaled_x = unrotated_x / (z * camera.scale);
-        let horizon = camera.pitch.tan() * 0.5;
-        let projected_y = horizon + camera.height / z;
+    /// Returns whether a custom ground shader is set
+    pub fn has_ground_shader(&self) -> bool {
+        self.ground_shader.is_some()
+    }
 
-        if projected_y < horizon {
-            return None;
-        }
+    /// Returns the viewport size in pixels
+    fn viewport(&self) -> Vec2 {
+        Vec2::new(self.viewport_width as f32, self.viewport_height as f32)
+    }
 
-        let screen_x = (scaled_x + 1.0) * self.viewport_width as f32 / 2.0;
-        let screen_y = (projected_y + 1.0) * self.viewport_height as f32 / 2.0;
+    /// Maps world space coordinates to screen space, see [`Camera::world_to_screen`]
+    ///
+    /// # Returns
+    ///
+    /// Screen space coordinates if visible, None if occluded
+    fn untransform(&self, world_x: f32, world_y: f32, camera: &Camera) -> Option<(f32, f32)> {
+        camera
+            .world_to_screen(Vec2::new(world_x, world_y), self.viewport())
+            .map(|screen| (screen.x, screen.y))
+    }
+
+    /// Quickly checks whether something at a world position can't be on screen
+    ///
+    /// Rejects positions behind the near plane, beyond the far plane or
+    /// left or right of the view, without projecting them. Positions
+    /// that pass can still be rejected by [`Renderer::untransform`],
+    /// e.g. below the bottom of the viewport.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position in world space
+    /// * `camera` - View transformation parameters
+    pub fn is_culled(&self, position: Vec2, camera: &Camera) -> bool {
+        let relative = position - camera.position();
+        let (sin_angle, cos_angle) = camera.angle().sin_cos();
 
-        if screen_x < 0.0
-            || screen_x >= self.viewport_width as f32
-            || screen_y < 0.0
-            || screen_y >= self.viewport_height as f32
-        {
-            return None;
+        // Distance along the view direction, same as `z` in `untransform`
+        let depth = -relative.x * sin_angle + relative.y * cos_angle;
+        if depth <= camera.near() || depth >= camera.far() {
+            return true;
         }
 
-        Some((screen_x, screen_y))
+        let lateral = relative.x * cos_angle + relative.y * sin_angle;
+        lateral.abs() >= depth * camera.scale()
     }
 
     /// Generic render function for any renderable entity
     ///
     /// Handles perspective projection and texture mapping for any
-    /// object implementing the Renderable trait.
+    /// object implementing the Renderable trait. The texture is stretched
+    /// over the entity's width and height and turned by its rotation
+    /// relative to the camera.
     ///
     /// # Arguments
     ///
@@ -148,7 +389,7 @@ impl Renderer {
     /// * `world` - Game world state for context
     /// * `camera` - View transformation parameters
     /// * `assets` - Asset manager for texture loading
-    fn render_entity<T: Renderable>(
+    fn render_entity<T: Renderable + ?Sized>(
         &self,
         frame: &mut [u8],
         entity: &T,
@@ -156,75 +397,362 @@ impl Renderer {
         camera: &Camera,
         assets: &AssetManager,
     ) {
-        let pos = entity.position();
-
-        // Calculate distance and scaling
-        let dx = pos.x - camera.x;
-        let dy = pos.y - camera.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        let reference_distance = 100.0;
-        let min_size = 5.0;
-
-        let scale_factor = (reference_distance / distance).min(4.0).max(0.25);
-        let entity_size = (entity.base_size() * scale_factor).max(min_size) as u32;
+        let pos = entity.interpolated_position(self.blending_factor);
+        let entity_size = Self::entity_size(entity, camera);
+        let rotation = entity.rotation() - camera.angle();
 
         if let Some((screen_x, screen_y)) = self.untransform(pos.x, pos.y, camera) {
-            let start_x = (screen_x - entity_size as f32 / 2.0).max(0.0) as u32;
-            let start_y = (screen_y - entity_size as f32 / 2.0).max(0.0) as u32;
-            let end_x = (start_x + entity_size).min(self.viewport_width);
-            let end_y = (start_y + entity_size).min(self.viewport_height);
+            let center = Vec2::new(screen_x, screen_y);
+            let extent = Self::rotated_extent(entity_size, rotation);
+            let start_x = (center.x - extent.x).max(0.0) as u32;
+            let start_y = (center.y - extent.y).max(0.0) as u32;
+            let end_x = ((center.x + extent.x).ceil().max(0.0) as u32).min(self.viewport_width);
+            let end_y = ((center.y + extent.y).ceil().max(0.0) as u32).min(self.viewport_height);
 
-            let texture = assets.get_texture(entity.texture_file(world));
+            let name = entity.texture_file(world);
+            let Some(texture) = assets.try_get_texture(name) else {
+                // Saves and track files can name any texture, skip typos
+                if self.missing_textures.borrow_mut().insert(name.to_string()) {
+                    log::warn!("Unknown texture {}, not drawing entities using it", name);
+                }
+                return;
+            };
+            let (rect_x, rect_y, rect_width, rect_height) =
+                entity
+                    .source_rect(world)
+                    .unwrap_or((0, 0, texture.width(), texture.height()));
+            let rect_origin = Vec2::new(rect_x as f32, rect_y as f32);
+            let texture_size = Vec2::new(rect_width as f32, rect_height as f32);
+            let tint = entity.tint();
 
             for y in start_y..end_y {
                 for x in start_x..end_x {
-                    let tex_x =
-                        ((x - start_x) as f32 / entity_size as f32) * texture.width() as f32;
-                    let tex_y =
-                        ((y - start_y) as f32 / entity_size as f32) * texture.height() as f32;
+                    let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - center;
+                    let texel = Self::sprite_texel(offset, rotation, entity_size, texture_size);
 
-                    let color = texture.sample_bilinear(tex_x, tex_y, [0, 0, 0, 0]);
-
-                    if color[3] > 0 {
-                        let idx = ((y * self.viewport_width + x) * 4) as usize;
-                        frame[idx..idx + 4].copy_from_slice(&color);
+                    // Stay inside the source rectangle, never blending in a neighboring frame
+                    if texel.x < 0.0
+                        || texel.y < 0.0
+                        || texel.x >= texture_size.x
+                        || texel.y >= texture_size.y
+                    {
+                        continue;
                     }
+                    let texel = rect_origin + texel.min(texture_size - Vec2::ONE);
+
+                    let color = Self::apply_tint(
+                        texture.sample_bilinear(texel.x, texel.y, [0, 0, 0, 0]),
+                        tint,
+                    );
+
+                    self.blend_at(frame, x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Linearly blends color `a` towards `b` by `t` (0.0 to 1.0)
+    fn mix_color(a: [u8; 4], b: [u8; 4], t: f32) -> [u8; 4] {
+        let mut result = [0; 4];
+        for (i, channel) in result.iter_mut().enumerate() {
+            *channel = (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8;
+        }
+        result
+    }
+
+    /// Multiplies each channel of a color with the matching tint channel
+    fn apply_tint(color: [u8; 4], tint: [u8; 4]) -> [u8; 4] {
+        let mut result = [0; 4];
+        for i in 0..4 {
+            result[i] = (color[i] as u16 * tint[i] as u16 / 255) as u8;
+        }
+        result
+    }
+
+    /// Draws a soft shadow on the ground below an entity
+    ///
+    /// The shadow is a dark, semi-transparent ellipse at the bottom of
+    /// where the sprite is drawn, fading out towards its edge. Entities
+    /// that don't cast shadows are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer for output
+    /// * `entity` - Any type implementing Renderable
+    /// * `camera` - View transformation parameters
+    fn render_shadow<T: Renderable + ?Sized>(&self, frame: &mut [u8], entity: &T, camera: &Camera) {
+        const SHADOW_ALPHA: f32 = 140.0;
+
+        if !entity.casts_shadow() {
+            return;
+        }
+
+        let pos = entity.interpolated_position(self.blending_factor);
+        let Some((screen_x, screen_y)) = self.untransform(pos.x, pos.y, camera) else {
+            return;
+        };
+
+        let size = Self::entity_size(entity, camera);
+        let radius = Vec2::new(size.x * 0.45, size.y * 0.15);
+        let center = Vec2::new(screen_x, screen_y + size.y * 0.4);
+
+        let start_x = (center.x - radius.x).max(0.0) as u32;
+        let start_y = (center.y - radius.y).max(0.0) as u32;
+        let end_x = ((center.x + radius.x).ceil().max(0.0) as u32).min(self.viewport_width);
+        let end_y = ((center.y + radius.y).ceil().max(0.0) as u32).min(self.viewport_height);
+
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                let offset = (Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - center) / radius;
+                let falloff = 1.0 - offset.length_squared();
+                if falloff <= 0.0 {
+                    continue;
+                }
+
+                let alpha = (SHADOW_ALPHA * falloff) as u8;
+                self.blend_at(frame, x, y, [0, 0, 0, alpha]);
+            }
+        }
+    }
+
+    /// Draws every car's skid marks as dark dots on the ground
+    ///
+    /// Dots shrink with distance like entities and fade with their
+    /// trail point's alpha.
+    fn render_trails(&self, frame: &mut [u8], world: &World, camera: &Camera) {
+        const SKID_ALPHA: f32 = 110.0;
+        const SKID_SIZE: f32 = 4.0;
+
+        for point in world.trails().iter().flat_map(|trail| trail.points()) {
+            let pos = point.position;
+            let Some((screen_x, screen_y)) = self.untransform(pos.x, pos.y, camera) else {
+                continue;
+            };
+
+            let radius = (SKID_SIZE * Self::distance_scale(pos, camera) / 2.0).max(0.5);
+            let start_x = (screen_x - radius).max(0.0) as u32;
+            let start_y = (screen_y - radius).max(0.0) as u32;
+            let end_x = ((screen_x + radius).ceil() as u32).min(self.viewport_width);
+            let end_y = ((screen_y + radius).ceil() as u32).min(self.viewport_height);
+
+            let color = [20, 20, 20, (SKID_ALPHA * point.alpha.clamp(0.0, 1.0)) as u8];
+            for y in start_y..end_y {
+                for x in start_x..end_x {
+                    self.blend_at(frame, x, y, color);
                 }
             }
         }
     }
 
+    /// Composites a color over a viewport pixel, ignoring out-of-range pixels
+    fn blend_at(&self, frame: &mut [u8], x: u32, y: u32, color: [u8; 4]) {
+        blend_pixel_at(
+            frame,
+            self.viewport_width,
+            self.viewport_height,
+            x,
+            y,
+            color,
+        );
+    }
+
+    /// Computes the on-screen width and height of an entity in pixels
+    ///
+    /// Both sides are at least 5 pixels, so far away entities stay visible.
+    fn entity_size<T: Renderable + ?Sized>(entity: &T, camera: &Camera) -> Vec2 {
+        let min_size = 5.0;
+        let scale_factor = Self::distance_scale(entity.position(), camera);
+        (entity.size() * scale_factor)
+            .max(Vec2::splat(min_size))
+            .floor()
+    }
+
+    /// Computes half the size of the screen box a rotated sprite covers
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - On-screen sprite width and height in pixels
+    /// * `rotation` - Sprite heading relative to the camera in radians
+    fn rotated_extent(size: Vec2, rotation: f32) -> Vec2 {
+        let (sin, cos) = rotation.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        Vec2::new(size.x * cos + size.y * sin, size.x * sin + size.y * cos) / 2.0
+    }
+
+    /// Maps a pixel of a rotated on-screen sprite to its texture coordinate
+    ///
+    /// The lookup is rotated around the sprite center, samples outside
+    /// the sprite's rectangle land outside the texture (and come back
+    /// transparent).
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Pixel position relative to the sprite center
+    /// * `rotation` - Sprite heading relative to the camera in radians
+    /// * `size` - On-screen sprite width and height in pixels
+    /// * `texture_size` - Texture dimensions in texels
+    fn sprite_texel(offset: Vec2, rotation: f32, size: Vec2, texture_size: Vec2) -> Vec2 {
+        let rotated = Vec2::from_angle(rotation).rotate(offset);
+        texture_size / 2.0 + rotated / size * texture_size
+    }
+
+    /// Computes the perspective size factor for something at a world position
+    ///
+    /// Objects at 100 world units from the camera are drawn at their base
+    /// size, closer objects grow and farther objects shrink (clamped to
+    /// 0.25x..4x).
+    fn distance_scale(pos: Vec2, camera: &Camera) -> f32 {
+        let reference_distance = 100.0;
+        let distance = pos.distance(camera.position());
+        (reference_distance / distance).clamp(0.25, 4.0)
+    }
+
+    /// Computes where world-anchored text is drawn on screen
+    ///
+    /// The text is centered horizontally on the projected anchor with
+    /// its baseline on the anchor's screen row.
+    ///
+    /// # Arguments
+    ///
+    /// * `world_pos` - Anchor position in world space
+    /// * `text` - Text to draw
+    /// * `camera` - View transformation parameters
+    /// * `font` - Font used to measure the text
+    ///
+    /// # Returns
+    ///
+    /// `(top_left, size)` of the text, None if the anchor is off-screen
+    pub fn world_text_placement(
+        &self,
+        world_pos: Vec2,
+        text: &str,
+        camera: &Camera,
+        font: &Font,
+    ) -> Option<(Vec2, f32)> {
+        let (screen_x, screen_y) = self.untransform(world_pos.x, world_pos.y, camera)?;
+
+        let size = TEXT_SCALE * Self::distance_scale(world_pos, camera);
+        let (width, ascent) = text_metrics(font, text, size);
+
+        Some((Vec2::new(screen_x - width / 2.0, screen_y - ascent), size))
+    }
+
+    /// Draws text anchored to a position in the world
+    ///
+    /// The anchor is projected like any entity and the text is scaled
+    /// by the same distance factor, so labels shrink into the distance.
+    /// Nothing is drawn when the anchor is off-screen, and glyphs are
+    /// clipped to the viewport.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - RGBA pixel buffer for output
+    /// * `world_pos` - Anchor position in world space (baseline center)
+    /// * `text` - Text to draw
+    /// * `camera` - View transformation parameters
+    /// * `font` - Font used for the glyphs
+    /// * `color` - RGBA text color
+    pub fn render_world_text(
+        &self,
+        frame: &mut [u8],
+        world_pos: Vec2,
+        text: &str,
+        camera: &Camera,
+        font: &Font,
+        color: [u8; 4],
+    ) {
+        if let Some((pos, size)) = self.world_text_placement(world_pos, text, camera, font) {
+            draw_text_scaled(
+                frame,
+                self.viewport_width,
+                self.viewport_height,
+                font,
+                text,
+                pos,
+                size,
+                color,
+            );
+        }
+    }
+
     /// Renders the perspective-mapped ground plane
     ///
     /// Implements Mode 7-style rendering:
-    /// * Maps screen pixels to texture coordinates
+    /// * Maps each row to the ground once and steps across it linearly
     /// * Uses bilinear filtering for texture sampling
-    /// * Renders horizon in solid color
+    /// * Fades distant ground into the horizon color
+    /// * Renders the sky gradient where no ground is visible
+    /// * Projects only every `ground_lod`-th row, repeating it below
     ///
     /// # Arguments
     ///
     /// * `frame` - RGBA pixel buffer for output
     /// * `camera` - View transformation parameters
     fn render_ground(&self, frame: &mut [u8], camera: &Camera) {
-        for y in 0..self.viewport_height {
+        // Same horizon as `Camera::ground_scanline`, converted to a screen row
+        let horizon = camera.horizon();
+        let horizon_row = (horizon + 1.0) * self.viewport_height as f32 / 2.0;
+        let texture_shader = TextureShader {
+            texture: &self.ground_texture,
+            texel_scale: self.texel_scale,
+            wrap: if self.wrap_ground {
+                WrapMode::Repeat
+            } else {
+                WrapMode::Clamp
+            },
+            void_color: self.void_color,
+        };
+        let shader = self
+            .ground_shader
+            .as_deref()
+            .unwrap_or(&texture_shader as &dyn GroundShader);
+
+        let tint = self.time_of_day.tint();
+
+        let row_bytes = self.viewport_width as usize * 4;
+        let viewport = self.viewport();
+        for y in (0..self.viewport_height).step_by(self.ground_lod as usize) {
+            let sky_color = Self::apply_tint(self.sky.color_at_row(y as f32, horizon_row), tint);
+
+            // The whole row lies at one depth, so step linearly along it
+            let scanline = camera.ground_scanline(y as f32, viewport);
+            let fog = scanline.map_or(0.0, |(_, _, z)| self.fog_factor(z, camera));
+
             for x in 0..self.viewport_width {
-                let screen_x = x as f32;
-                let screen_y = y as f32;
-
-                let color =
-                    if let Some((world_x, world_y)) = self.transform(screen_x, screen_y, camera) {
-                        self.ground_texture.sample_bilinear(
-                            world_x,
-                            world_y,
-                            [255, 105, 180, 255], // Hotpink for out-of-bounds
-                        )
+                let color = if let Some((start, step, z)) = scanline {
+                    let world = start + step * x as f32;
+                    let texel = shader.shade(world.x, world.y, z);
+                    let color =
+                        Self::apply_tint(Self::mix_color(texel, self.sky.horizon, fog), tint);
+
+                    let on_grid = self.draw_grid
+                        && Self::is_on_grid_line(world, self.grid_spacing, GRID_LINE_WIDTH);
+                    if on_grid {
+                        GRID_COLOR
                     } else {
-                        [255, 0, 255, 255] // Magenta for horizon
-                    };
+                        color
+                    }
+                } else {
+                    sky_color
+                };
+
+                put_pixel(
+                    frame,
+                    self.viewport_width,
+                    self.viewport_height,
+                    x,
+                    y,
+                    color,
+                );
+            }
 
-                let idx = ((y * self.viewport_width + x) * 4) as usize;
-                frame[idx..idx + 4].copy_from_slice(&color);
+            // Fill the skipped rows, clipped to the viewport and the frame
+            let row_start = y as usize * row_bytes;
+            let rows_end = (y + self.ground_lod).min(self.viewport_height) as usize * row_bytes;
+            let mut copy_start = row_start + row_bytes;
+            while copy_start + row_bytes <= rows_end.min(frame.len()) {
+                frame.copy_within(row_start..row_start + row_bytes, copy_start);
+                copy_start += row_bytes;
             }
         }
     }
@@ -240,18 +768,429 @@ impl Renderer {
     ///
     /// # Panics
     ///
-    /// If frame buffer size doesn't match viewport dimensions
+    /// In debug builds, if frame buffer size doesn't match viewport
+    /// dimensions. Release builds skip pixels outside the frame instead.
     pub fn render(&self, frame: &mut [u8], world: &World, camera: &Camera, assets: &AssetManager) {
-        assert_eq!(
+        debug_assert_eq!(
             frame.len(),
             (self.viewport_width * self.viewport_height * 4) as usize
         );
 
         self.render_ground(frame, camera);
+        self.render_trails(frame, world, camera);
+
+        // Skip everything that can't be on screen before sorting and projecting
+        let entities: Vec<_> = Self::renderables(world)
+            .into_iter()
+            .filter(|entity| {
+                !self.is_culled(entity.interpolated_position(self.blending_factor), camera)
+            })
+            .collect();
 
-        // Render all cars using the generic render_entity function
-        for car in &world.cars {
-            self.render_entity(frame, car, world, camera, assets);
+        // Shadows go on the ground first so no sprite is ever darkened
+        for entity in &entities {
+            self.render_shadow(frame, *entity, camera);
         }
+
+        // Render all entities layer by layer, far to near within a layer
+        // so nearer ones occlude farther ones
+        let positions: Vec<_> = entities
+            .iter()
+            .map(|entity| entity.interpolated_position(self.blending_factor))
+            .collect();
+        let mut order = Self::depth_order(&positions, camera);
+        order.sort_by_key(|&index| entities[index].layer());
+        for index in order {
+            self.render_entity(frame, entities[index], world, camera, assets);
+        }
+    }
+
+    /// Renders a complete frame into a newly allocated buffer
+    ///
+    /// Useful for screenshots and tests where no window is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - Game world containing entities to render
+    /// * `camera` - Current camera parameters
+    /// * `assets` - Asset manager for texture loading
+    ///
+    /// # Returns
+    ///
+    /// RGBA pixel buffer of `viewport_width * viewport_height * 4` bytes
+    pub fn render_to_vec(&self, world: &World, camera: &Camera, assets: &AssetManager) -> Vec<u8> {
+        let mut frame = vec![0; (self.viewport_width * self.viewport_height * 4) as usize];
+        self.render(&mut frame, world, camera, assets);
+        frame
+    }
+
+    /// Collects everything in the world that gets drawn as a sprite
+    ///
+    /// # Returns
+    ///
+    /// All cars, followed by the visible world objects (active and with
+    /// a sprite)
+    pub fn renderables(world: &World) -> Vec<&dyn Renderable> {
+        let cars = world.cars.iter().map(|car| car as &dyn Renderable);
+        let objects = world
+            .objects()
+            .iter()
+            .filter(|object| object.is_visible())
+            .map(|object| object as &dyn Renderable);
+        cars.chain(objects).collect()
+    }
+
+    /// Orders positions for painter's-algorithm drawing
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - World positions of the things to draw
+    /// * `camera` - Camera the distances are measured from
+    ///
+    /// # Returns
+    ///
+    /// Indices into `positions`, farthest from the camera first
+    pub fn depth_order(positions: &[Vec2], camera: &Camera) -> Vec<usize> {
+        let eye = camera.position();
+        let mut by_distance: Vec<(f32, usize)> = positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| (position.distance_squared(eye), index))
+            .collect();
+
+        by_distance.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        by_distance.into_iter().map(|(_, index)| index).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::TRACK_FILE;
+    use crate::game::rendering::Sprite;
+    use crate::game::world::WorldObject;
+    use std::f32::consts::PI;
+    use std::rc::Rc;
+
+    /// A plain square entity for drawing tests
+    struct Marker {
+        position: Vec2,
+        rotation: f32,
+        shadow: bool,
+    }
+
+    impl Renderable for Marker {
+        fn position(&self) -> Vec2 {
+            self.position
+        }
+
+        fn rotation(&self) -> f32 {
+            self.rotation
+        }
+
+        fn base_size(&self) -> f32 {
+            20.0
+        }
+
+        fn texture_file(&self, _world: &World) -> &str {
+            crate::consts::CAR_FILE
+        }
+
+        fn casts_shadow(&self) -> bool {
+            self.shadow
+        }
+    }
+
+    /// Renders only the ground into a fresh frame
+    fn ground_frame(renderer: &Renderer, camera: &Camera) -> Vec<u8> {
+        let mut frame = vec![0; (renderer.viewport_width * renderer.viewport_height * 4) as usize];
+        renderer.render_ground(&mut frame, camera);
+        frame
+    }
+
+    #[test]
+    fn unknown_entity_texture_is_skipped() {
+        let assets = AssetManager::new();
+        let renderer = Renderer::new(64, 48, assets.get_texture(TRACK_FILE).clone());
+        let mut world = World::new(1);
+        let mut camera = Camera::default();
+        camera.follow_car(&world.cars[0], 1.0 / 60.0);
+
+        let ahead = world.cars[0].position() + world.cars[0].forward() * 40.0;
+        world.spawn_object(WorldObject::new(
+            ahead,
+            Some(Sprite::new("missing.png", 16.0, 16.0)),
+        ));
+
+        // Rendering twice only warns once and never panics
+        let frame = renderer.render_to_vec(&world, &camera, &assets);
+        assert_eq!(frame.len(), 64 * 48 * 4);
+        renderer.render_to_vec(&world, &camera, &assets);
+        assert_eq!(renderer.missing_textures.borrow().len(), 1);
+    }
+
+    #[test]
+    fn scanlines_match_the_per_pixel_projection() {
+        // Records every ground position the renderer shades, in drawing order
+        let shaded = Rc::new(RefCell::new(Vec::new()));
+        let recorder = Rc::clone(&shaded);
+        let mut renderer = Renderer::new(64, 48, Texture::checkerboard(4, 4, 1));
+        renderer.set_ground_shader(Box::new(move |x: f32, y: f32, z: f32| {
+            recorder.borrow_mut().push((Vec2::new(x, y), z));
+            [0, 0, 0, 255]
+        }));
+
+        let camera = Camera::new(100.0, 50.0, 20.0, 0.7);
+        ground_frame(&renderer, &camera);
+
+        let viewport = renderer.viewport();
+        let shaded = shaded.borrow().clone();
+        assert!(shaded.len() >= 64 * 10);
+        let mut shaded = shaded.into_iter();
+        for y in 0..48 {
+            for x in 0..64 {
+                let screen = Vec2::new(x as f32, y as f32);
+                let Some((world, z)) = camera.screen_to_ground(screen, viewport) else {
+                    continue;
+                };
+                let (fast, fast_z) = shaded.next().unwrap();
+                assert!(fast.distance(world) < 1e-3 * (1.0 + z), "{screen}");
+                assert!((fast_z - z).abs() < 1e-3);
+            }
+        }
+        assert_eq!(shaded.next(), None);
+    }
+
+    #[test]
+    fn ground_lod_repeats_rows_and_fills_the_frame() {
+        let mut renderer = Renderer::new(32, 25, Texture::checkerboard(64, 64, 4));
+        renderer.set_ground_lod(2);
+        let camera = Camera::new(30.0, 10.0, 20.0, 0.3);
+
+        // An odd height leaves a last row without a partner
+        let sentinel = [1, 2, 3, 4];
+        let mut frame = sentinel.repeat(32 * 25);
+        renderer.render_ground(&mut frame, &camera);
+
+        assert!(frame.chunks_exact(4).all(|pixel| pixel != sentinel));
+        let rows: Vec<_> = frame.chunks_exact(32 * 4).collect();
+        for pair in rows.chunks_exact(2) {
+            assert_eq!(pair[0], pair[1]);
+        }
+        assert_ne!(rows[0], rows[24]);
+    }
+
+    #[test]
+    fn depth_order_draws_far_to_near() {
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        let near = Vec2::new(0.0, 50.0);
+        let far = Vec2::new(10.0, 300.0);
+        assert_eq!(Renderer::depth_order(&[near, far], &camera), [1, 0]);
+        assert_eq!(Renderer::depth_order(&[far, near], &camera), [0, 1]);
+        assert!(Renderer::depth_order(&[], &camera).is_empty());
+    }
+
+    #[test]
+    fn sky_fades_from_top_to_horizon() {
+        let sky = SkyConfig {
+            top: [0, 0, 100, 255],
+            horizon: [200, 100, 200, 255],
+        };
+        assert_eq!(sky.color_at_row(0.0, 50.0), sky.top);
+        assert_eq!(sky.color_at_row(25.0, 50.0), [100, 50, 150, 255]);
+        assert_eq!(sky.color_at_row(50.0, 50.0), sky.horizon);
+
+        // A level camera puts the horizon at the center, the gradient fills the top half
+        let mut renderer = Renderer::new(8, 100, Texture::checkerboard(4, 4, 1));
+        renderer.set_sky(sky);
+        let mut camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        camera.set_pitch(0.0);
+        let frame = ground_frame(&renderer, &camera);
+        let pixel = |row: usize| &frame[row * 8 * 4..row * 8 * 4 + 4];
+        assert_eq!(pixel(0), sky.top);
+        let above_horizon = pixel(49);
+        assert!(above_horizon[0] > 190 && above_horizon[2] > 190);
+    }
+
+    #[test]
+    fn fog_thickens_towards_the_far_plane() {
+        let mut renderer = Renderer::new(8, 8, Texture::checkerboard(4, 4, 1));
+        let camera = Camera::default();
+        assert_eq!(renderer.fog_factor(camera.near(), &camera), 0.0);
+        assert!(renderer.fog_factor(camera.far() - 1e-3, &camera) > 0.999);
+        assert_eq!(renderer.fog_factor(camera.far() * 2.0, &camera), 1.0);
+
+        // Denser fog sets in earlier
+        let middle = (camera.near() + camera.far()) / 2.0;
+        let linear = renderer.fog_factor(middle, &camera);
+        renderer.set_fog_density(2.0);
+        assert!(renderer.fog_factor(middle, &camera) > linear);
+        renderer.set_fog_density(-1.0);
+        assert_eq!(renderer.fog_factor(camera.far(), &camera), 0.0);
+    }
+
+    #[test]
+    fn only_shadow_casters_darken_the_ground() {
+        let renderer = Renderer::new(64, 48, Texture::checkerboard(4, 4, 1));
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        let shadow_pixels = |shadow: bool| {
+            let mut frame = vec![255; 64 * 48 * 4];
+            let marker = Marker {
+                position: Vec2::new(0.0, 100.0),
+                rotation: 0.0,
+                shadow,
+            };
+            renderer.render_shadow(&mut frame, &marker, &camera);
+            frame.iter().filter(|&&channel| channel != 255).count()
+        };
+
+        assert_eq!(shadow_pixels(false), 0);
+        assert!(shadow_pixels(true) > 0);
+    }
+
+    #[test]
+    fn evening_tints_the_ground_warm_and_dark() {
+        let white = Texture::from_rgba(4, 4, vec![255; 4 * 4 * 4]).unwrap();
+        let mut renderer = Renderer::new(16, 16, white);
+        renderer.set_wrap_ground(true);
+        renderer.set_fog_density(0.0);
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        let ground_pixel = |renderer: &Renderer| {
+            let frame = ground_frame(renderer, &camera);
+            <[u8; 4]>::try_from(&frame[frame.len() - 4..]).unwrap()
+        };
+
+        assert_eq!(ground_pixel(&renderer), [255; 4]);
+
+        renderer.set_time_of_day(0.4);
+        let [r, g, b, a] = ground_pixel(&renderer);
+        assert_eq!(a, 255);
+        assert!(r > g && g > b);
+        assert!((r as u32 + g as u32 + b as u32) < 3 * 255);
+    }
+
+    #[test]
+    fn ground_outside_the_texture_uses_the_void_color() {
+        let mut renderer = Renderer::new(16, 16, Texture::checkerboard(4, 4, 1));
+        renderer.set_fog_density(0.0);
+        renderer.set_void_color([10, 20, 30, 255]);
+
+        // Looking away from the 4x4 texture at the origin
+        let camera = Camera::new(-500.0, -500.0, 20.0, PI);
+        let frame = ground_frame(&renderer, &camera);
+        assert_eq!(frame[frame.len() - 4..], [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn rotated_sprites_sample_turned_texels() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let texture = Texture::from_rgba(2, 1, [red, blue].concat()).unwrap();
+        let texture_size = Vec2::new(2.0, 1.0);
+        let sample = |rotation: f32| {
+            let size = Vec2::splat(10.0);
+            let texel = Renderer::sprite_texel(Vec2::new(-3.0, 0.0), rotation, size, texture_size);
+            texture.sample(texel.x, texel.y, [0; 4])
+        };
+
+        assert_eq!(sample(0.0), red);
+        assert_eq!(sample(PI), blue);
+    }
+
+    #[test]
+    fn red_tint_zeroes_green_and_blue() {
+        let white = [255; 4];
+        assert_eq!(
+            Renderer::apply_tint(white, [255, 0, 0, 255]),
+            [255, 0, 0, 255]
+        );
+        assert_eq!(
+            Renderer::apply_tint([10, 20, 30, 40], white),
+            [10, 20, 30, 40]
+        );
+
+        let mut car = crate::game::world::Car::new(0.0, 0.0);
+        assert_eq!(car.tint()[0], 255);
+        car.set_player_index(1);
+        assert_eq!(car.tint()[2], 255);
+        car.set_player_index(5);
+        assert_eq!(car.tint(), white);
+    }
+
+    #[test]
+    fn entities_behind_the_camera_are_culled() {
+        let assets = AssetManager::new();
+        let renderer = Renderer::new(64, 48, assets.get_texture(TRACK_FILE).clone());
+        let mut world = World::new(1);
+        let car = &world.cars[0];
+
+        // Looking the same way as the car from just in front of it
+        let ahead = car.position() + car.forward() * 50.0;
+        let camera = Camera::new(ahead.x, ahead.y, 20.0, car.angle());
+        assert!(renderer.is_culled(car.position(), &camera));
+        assert!(!renderer.is_culled(ahead + car.forward() * 100.0, &camera));
+
+        let with_car = renderer.render_to_vec(&world, &camera, &assets);
+        world.cars.clear();
+        assert_eq!(renderer.render_to_vec(&world, &camera, &assets), with_car);
+    }
+
+    #[test]
+    fn grid_lines_are_centered_on_multiples_of_the_spacing() {
+        let on_line = |x: f32, y: f32| Renderer::is_on_grid_line(Vec2::new(x, y), 64.0, 2.0);
+        assert!(on_line(64.5, 10.0));
+        assert!(on_line(10.0, 127.2));
+        assert!(on_line(-0.5, 10.0));
+        assert!(!on_line(32.0, 32.0));
+        assert!(!on_line(65.5, 10.0));
+    }
+
+    #[test]
+    fn custom_ground_shaders_see_the_depth() {
+        let mut renderer = Renderer::new(16, 64, Texture::checkerboard(4, 4, 1));
+        renderer.set_fog_density(0.0);
+        renderer.set_ground_shader(Box::new(|_: f32, _: f32, depth: f32| {
+            let gray = (255.0 - depth).clamp(0.0, 255.0) as u8;
+            [gray, gray, gray, 255]
+        }));
+        assert!(renderer.has_ground_shader());
+
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        let frame = ground_frame(&renderer, &camera);
+        let gray = |row: usize| frame[row * 16 * 4];
+        let near = gray(63);
+        let far = gray(45);
+        assert!(far < near, "{far} < {near}");
+
+        renderer.clear_ground_shader();
+        assert!(!renderer.has_ground_shader());
+    }
+
+    #[test]
+    fn twice_as_wide_sprites_cover_twice_the_pixels() {
+        let assets = AssetManager::new();
+        let renderer = Renderer::new(160, 120, Texture::checkerboard(4, 4, 1));
+        let world = World::new(1);
+        let camera = Camera::new(0.0, 0.0, 20.0, 0.0);
+        let footprint = |width: f32| {
+            let sprite = Sprite::new(crate::consts::CAR_FILE, width, 20.0);
+            let object = WorldObject::new(Vec2::new(0.0, 100.0), Some(sprite));
+            let mut frame = vec![0; 160 * 120 * 4];
+            renderer.render_entity(&mut frame, &object, &world, &camera, &assets);
+            frame.chunks_exact(4).filter(|pixel| pixel[3] > 0).count() as f32
+        };
+
+        let ratio = footprint(40.0) / footprint(20.0);
+        assert!((ratio - 2.0).abs() < 0.2, "{ratio}");
+    }
+
+    #[test]
+    fn small_ground_textures_stretch_over_the_world() {
+        let mut renderer = Renderer::new(16, 16, Texture::checkerboard(512, 512, 8));
+        assert_eq!(renderer.world_to_texel(100.0, 200.0), (100.0, 200.0));
+
+        renderer.fit_ground_to_world(Vec2::splat(1024.0));
+        assert_eq!(renderer.texel_scale(), Vec2::splat(0.5));
+        assert_eq!(renderer.world_to_texel(1024.0, 512.0), (512.0, 256.0));
     }
 }
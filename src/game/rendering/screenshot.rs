@@ -0,0 +1,34 @@
+//! Saving rendered frames to disk
+
+use anyhow::{ensure, Result};
+use std::path::Path;
+
+/// Saves an RGBA frame buffer as an image file
+///
+/// The format is picked from the file extension, e.g. `.png`.
+///
+/// # Arguments
+///
+/// * `path` - Destination file
+/// * `width` - Frame width in pixels
+/// * `height` - Frame height in pixels
+/// * `frame` - RGBA pixel buffer (width * height * 4 bytes)
+///
+/// # Errors
+///
+/// If the buffer size doesn't match the dimensions or the image can't
+/// be encoded or written
+pub fn save_frame(path: impl AsRef<Path>, width: u32, height: u32, frame: &[u8]) -> Result<()> {
+    let expected = width as usize * height as usize * 4;
+    ensure!(
+        frame.len() == expected,
+        "Frame for a {}x{} image must be {} bytes, got {}",
+        width,
+        height,
+        expected,
+        frame.len()
+    );
+
+    image::save_buffer(path, frame, width, height, image::ExtendedColorType::Rgba8)?;
+    Ok(())
+}
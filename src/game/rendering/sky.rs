@@ -0,0 +1,50 @@
+/// Colors of the sky drawn above the horizon
+///
+/// The sky is a vertical gradient from `top` at the top of the view
+/// to `horizon` at the horizon line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyConfig {
+    /// Color of the top row of the view
+    pub top: [u8; 4],
+    /// Color right above the horizon
+    pub horizon: [u8; 4],
+}
+
+impl Default for SkyConfig {
+    /// Creates a clear day sky, deep blue fading to a pale horizon
+    fn default() -> Self {
+        Self {
+            top: [40, 80, 170, 255],
+            horizon: [170, 205, 240, 255],
+        }
+    }
+}
+
+impl SkyConfig {
+    /// Returns the sky color of a screen row
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Screen row, 0 being the top of the view
+    /// * `horizon_row` - Screen row of the horizon
+    ///
+    /// # Returns
+    ///
+    /// `top` at row 0, `horizon` at and below `horizon_row`, linearly
+    /// interpolated in between
+    pub fn color_at_row(&self, row: f32, horizon_row: f32) -> [u8; 4] {
+        let t = if horizon_row > 0.0 {
+            (row / horizon_row).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let mut color = [0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let top = self.top[i] as f32;
+            let horizon = self.horizon[i] as f32;
+            *channel = (top + (horizon - top) * t).round() as u8;
+        }
+        color
+    }
+}
@@ -0,0 +1,140 @@
+use std::ops::Range;
+
+/// Layout of the split-screen views and the separators between them
+///
+/// Every view gets the same height. Separators sit in the rows between
+/// two views; rows left over by the division widen the separators by
+/// one row each, top to bottom, so every screen row belongs to exactly
+/// one view or separator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitConfig {
+    /// Whether separators are drawn, disabled separators take no rows
+    pub enabled: bool,
+    /// Color of the separators
+    pub color: [u8; 4],
+    /// Rows per separator, not counting leftover rows
+    pub thickness: u32,
+}
+
+impl Default for SplitConfig {
+    /// Creates the original split: a 1 px red line
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            color: [255, 0, 0, 255],
+            thickness: 1,
+        }
+    }
+}
+
+impl SplitConfig {
+    /// Computes the screen rows of every view
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - Screen height in rows
+    /// * `views` - Number of views stacked top to bottom
+    ///
+    /// # Returns
+    ///
+    /// Row range of each view, top to bottom, all of the same length.
+    /// The rows between two consecutive ranges are a separator. Empty
+    /// if `views` is 0.
+    pub fn view_rows(&self, height: u32, views: usize) -> Vec<Range<u32>> {
+        if views == 0 {
+            return Vec::new();
+        }
+
+        let thickness = if self.enabled { self.thickness } else { 0 };
+        let views = views as u32;
+        let separators = views - 1;
+        let available = height.saturating_sub(thickness * separators);
+        let view_height = available / views;
+        let leftover = available % views;
+
+        let mut rows = Vec::with_capacity(views as usize);
+        let mut top = 0;
+        for view in 0..views {
+            rows.push(top..top + view_height);
+            top += view_height + thickness + (view < leftover) as u32;
+        }
+        rows
+    }
+
+    /// Returns the height of every view, see [`SplitConfig::view_rows`]
+    pub fn view_height(&self, height: u32, views: usize) -> u32 {
+        self.view_rows(height, views)
+            .first()
+            .map_or(0, |rows| rows.len() as u32)
+    }
+
+    /// Returns the color separator rows are filled with
+    ///
+    /// Black when disabled, for the leftover rows that remain.
+    pub fn separator_color(&self) -> [u8; 4] {
+        if self.enabled {
+            self.color
+        } else {
+            [0, 0, 0, 255]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that views and the separators between them cover every row once
+    fn assert_covers(split: &SplitConfig, height: u32, views: usize) {
+        let rows = split.view_rows(height, views);
+        assert_eq!(rows.len(), views);
+        assert_eq!(rows[0].start, 0);
+        assert_eq!(rows[views - 1].end, height);
+        for pair in rows.windows(2) {
+            assert_eq!(pair[0].len(), pair[1].len());
+            assert!(pair[0].end <= pair[1].start);
+        }
+
+        let mut owners = vec![0; height as usize];
+        let separators = rows.windows(2).map(|pair| pair[0].end..pair[1].start);
+        for range in rows.iter().cloned().chain(separators) {
+            for row in range {
+                owners[row as usize] += 1;
+            }
+        }
+        assert!(owners.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn boundary_rows_at_even_and_odd_heights() {
+        let split = SplitConfig::default();
+        assert_eq!(split.view_rows(1000, 2), [0..499, 501..1000]);
+        assert_eq!(split.view_rows(1001, 2), [0..500, 501..1001]);
+
+        let disabled = SplitConfig {
+            enabled: false,
+            ..split
+        };
+        assert_eq!(disabled.view_rows(1000, 2), [0..500, 500..1000]);
+        assert_eq!(disabled.view_rows(1001, 2), [0..500, 501..1001]);
+    }
+
+    #[test]
+    fn views_and_separators_cover_every_row() {
+        for thickness in [0, 1, 3] {
+            for enabled in [true, false] {
+                let split = SplitConfig {
+                    enabled,
+                    thickness,
+                    ..SplitConfig::default()
+                };
+                for height in [999, 1000, 1001, 17] {
+                    for views in 1..=4 {
+                        assert_covers(&split, height, views);
+                    }
+                }
+            }
+        }
+        assert!(SplitConfig::default().view_rows(1000, 0).is_empty());
+    }
+}
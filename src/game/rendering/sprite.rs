@@ -0,0 +1,105 @@
+use glam::Vec2;
+
+/// Texture and world-space size of a drawable object
+///
+/// The texture is looked up in the `AssetManager` by file name when the
+/// object is drawn.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sprite {
+    /// File name of the texture in the asset manager
+    texture_file: String,
+    /// Width in world units
+    width: f32,
+    /// Height in world units
+    height: f32,
+    /// Turn in radians on top of the object's heading
+    #[cfg_attr(feature = "serde", serde(default))]
+    rotation: f32,
+    /// Factor on the width and height
+    #[cfg_attr(feature = "serde", serde(default = "unit_scale"))]
+    scale: f32,
+    /// Draw order, higher layers are drawn over lower ones
+    #[cfg_attr(feature = "serde", serde(default))]
+    layer: i32,
+}
+
+/// Scale of sprites saved before they had one
+#[cfg(feature = "serde")]
+fn unit_scale() -> f32 {
+    1.0
+}
+
+impl Sprite {
+    /// Creates an unrotated, unscaled sprite on layer 0
+    ///
+    /// # Arguments
+    ///
+    /// * `texture_file` - File name of the texture in the asset manager
+    /// * `width` - Width in world units, clamped to ≥ 0
+    /// * `height` - Height in world units, clamped to ≥ 0
+    pub fn new(texture_file: impl Into<String>, width: f32, height: f32) -> Self {
+        Self {
+            texture_file: texture_file.into(),
+            width: width.max(0.0),
+            height: height.max(0.0),
+            rotation: 0.0,
+            scale: 1.0,
+            layer: 0,
+        }
+    }
+
+    /// Returns the file name of the texture
+    pub fn texture_file(&self) -> &str {
+        &self.texture_file
+    }
+
+    /// Returns the width in world units
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Returns the height in world units
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Returns the turn in radians on top of the object's heading
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Sets the turn in radians on top of the object's heading, NaN is ignored
+    pub fn set_rotation(&mut self, rotation: f32) {
+        if !rotation.is_nan() {
+            self.rotation = rotation;
+        }
+    }
+
+    /// Returns the factor on the width and height
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets the factor on the width and height, clamped to ≥ 0, NaN is ignored
+    pub fn set_scale(&mut self, scale: f32) {
+        if !scale.is_nan() {
+            self.scale = scale.max(0.0);
+        }
+    }
+
+    /// Returns the draw order, higher layers are drawn over lower ones
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    /// Sets the draw order, higher layers are drawn over lower ones
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    /// Returns the scaled width and height in world units
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width, self.height) * self.scale
+    }
+}
@@ -0,0 +1,53 @@
+/// Tint at noon, leaving colors unchanged
+const NOON: [f32; 3] = [255.0, 255.0, 255.0];
+
+/// Warm orange tint at dusk
+const DUSK: [f32; 3] = [255.0, 170.0, 120.0];
+
+/// Dark blue tint at night
+const NIGHT: [f32; 3] = [70.0, 80.0, 140.0];
+
+/// Time of day the track is lit for, from 0.0 (noon) to 1.0 (night)
+///
+/// The ground and sky are multiplied by a tint that goes from white at
+/// noon over warm orange at dusk (0.5) to dark blue at night.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TimeOfDay(f32);
+
+impl TimeOfDay {
+    /// Creates a time of day
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - 0.0 (noon) to 1.0 (night), clamped to that range
+    ///   with NaN treated as noon
+    pub fn new(time: f32) -> Self {
+        if time.is_nan() {
+            Self(0.0)
+        } else {
+            Self(time.clamp(0.0, 1.0))
+        }
+    }
+
+    /// Returns the time from 0.0 (noon) to 1.0 (night)
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns the color everything is multiplied with at this time
+    ///
+    /// Exactly white at noon, so the tint is a no-op there.
+    pub fn tint(&self) -> [u8; 4] {
+        let (from, to, t) = if self.0 < 0.5 {
+            (NOON, DUSK, self.0 * 2.0)
+        } else {
+            (DUSK, NIGHT, self.0 * 2.0 - 1.0)
+        };
+
+        let mut tint = [255; 4];
+        for (i, channel) in tint.iter_mut().take(3).enumerate() {
+            *channel = (from[i] + (to[i] - from[i]) * t).round() as u8;
+        }
+        tint
+    }
+}
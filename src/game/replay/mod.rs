@@ -0,0 +1,17 @@
+//! Race recording and playback
+//!
+//! World updates are deterministic on a fixed timestep, so a race can
+//! be stored as nothing more than the car inputs of every fixed step
+//! and replayed onto a freshly reset world.
+
+mod recorder;
+mod replay;
+
+pub use recorder::Recorder;
+pub use replay::Replay;
+
+/// Magic bytes at the start of every replay file
+const MAGIC: &[u8; 4] = b"M7RP";
+
+/// Version of the replay file layout
+const VERSION: u8 = 3;
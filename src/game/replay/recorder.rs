@@ -0,0 +1,45 @@
+use super::Replay;
+use crate::game::world::CarInput;
+
+/// Collects the car inputs of every fixed world step
+///
+/// Recorder provides:
+/// * Appending the inputs of all cars once per step
+/// * Turning the recording into a `Replay` for playback or saving
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorder {
+    /// Recorded inputs, one entry per fixed step
+    steps: Vec<Vec<CarInput>>,
+}
+
+impl Recorder {
+    /// Creates an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the inputs used for one fixed world step
+    pub fn record(&mut self, inputs: &[CarInput]) {
+        self.steps.push(inputs.to_vec());
+    }
+
+    /// Returns the number of recorded steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns whether nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Drops everything recorded so far
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    /// Returns a replay of everything recorded so far
+    pub fn to_replay(&self) -> Replay {
+        Replay::new(self.steps.clone())
+    }
+}
@@ -0,0 +1,268 @@
+use super::{MAGIC, VERSION};
+use crate::game::world::CarInput;
+use anyhow::{bail, ensure, Context as _, Result};
+use std::path::Path;
+
+/// Bytes per recorded car input: 3 analog inputs * 4 bytes + 1 handbrake byte
+const INPUT_SIZE: usize = 3 * 4 + 1;
+
+/// Size of the file header: magic, version, car count and step count
+const HEADER_SIZE: usize = 4 + 1 + 1 + 8;
+
+/// A recorded race that can be played back step by step
+///
+/// The file format is little-endian:
+/// * 4 bytes magic `M7RP`
+/// * 1 byte version
+/// * 1 byte car count
+/// * 8 bytes step count
+/// * Per step and car: throttle, turn and brake as `f32`, then the
+///   handbrake as a 0/1 byte
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Replay {
+    /// Recorded inputs, one entry per fixed step
+    steps: Vec<Vec<CarInput>>,
+}
+
+impl Replay {
+    /// Creates a replay from recorded inputs
+    ///
+    /// The car count is taken from the first step, later steps are
+    /// expected to hold the same number of inputs.
+    pub fn new(steps: Vec<Vec<CarInput>>) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the recorded inputs, one entry per fixed step
+    pub fn steps(&self) -> &[Vec<CarInput>] {
+        &self.steps
+    }
+
+    /// Returns the recorded inputs in playback order
+    pub fn iter(&self) -> impl Iterator<Item = &[CarInput]> + '_ {
+        self.steps.iter().map(Vec::as_slice)
+    }
+
+    /// Returns the number of cars each step holds inputs for
+    pub fn car_count(&self) -> usize {
+        self.steps.first().map_or(0, Vec::len)
+    }
+
+    /// Encodes the replay into the binary file format
+    ///
+    /// Steps with fewer inputs than the first step are padded with
+    /// released controls, extra inputs are dropped. A replay without cars
+    /// is encoded but rejected by [`Replay::from_bytes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the replay holds more than 255 cars
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let cars = self.car_count();
+        let car_byte = u8::try_from(cars).expect("Replays hold at most 255 cars");
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.steps.len() * cars * INPUT_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(car_byte);
+        bytes.extend_from_slice(&(self.steps.len() as u64).to_le_bytes());
+
+        for step in &self.steps {
+            for car in 0..cars {
+                let input = step.get(car).copied().unwrap_or_default();
+                for value in [input.throttle(), input.turn(), input.brake()] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                bytes.push(input.handbrake() as u8);
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes a replay from the binary file format
+    ///
+    /// # Errors
+    ///
+    /// If the header is wrong or holds no cars, the data is truncated or
+    /// an input is outside its valid range
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() >= HEADER_SIZE,
+            "Replay is too short for a header"
+        );
+        ensure!(&bytes[..4] == MAGIC, "Not a replay file");
+        ensure!(
+            bytes[4] == VERSION,
+            "Unsupported replay version {}, expected {}",
+            bytes[4],
+            VERSION
+        );
+
+        let cars = bytes[5] as usize;
+        ensure!(cars > 0, "Replay holds no cars");
+        let step_size = cars * INPUT_SIZE;
+        let count = u64::from_le_bytes(bytes[6..HEADER_SIZE].try_into()?);
+        let data = &bytes[HEADER_SIZE..];
+        ensure!(
+            count.checked_mul(step_size as u64) == Some(data.len() as u64),
+            "Replay claims {} steps but holds {} bytes of inputs",
+            count,
+            data.len()
+        );
+
+        let read = |input: &[u8], offset: usize| {
+            f32::from_le_bytes(input[offset..offset + 4].try_into().unwrap())
+        };
+        let mut steps = Vec::with_capacity(data.len() / step_size);
+        for (step, step_data) in data.chunks_exact(step_size).enumerate() {
+            let mut inputs = Vec::with_capacity(cars);
+            for (car, input) in step_data.chunks_exact(INPUT_SIZE).enumerate() {
+                let (throttle, turn, brake) = (read(input, 0), read(input, 4), read(input, 8));
+                let handbrake = match input[12] {
+                    0 => false,
+                    1 => true,
+                    _ => bail!("Invalid handbrake for car {} in step {}", car + 1, step),
+                };
+                let Some(valid) = CarInput::try_new(throttle, turn, brake) else {
+                    bail!("Invalid input for car {} in step {}", car + 1, step);
+                };
+                inputs.push(valid.with_handbrake(handbrake));
+            }
+            steps.push(inputs);
+        }
+
+        Ok(Self { steps })
+    }
+
+    /// Writes the replay to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes())
+            .with_context(|| format!("Failed to write replay to {}", path.display()))
+    }
+
+    /// Reads a replay from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read replay from {}", path.display()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        Replay::new(vec![
+            vec![CarInput::new(1.0, 0.5, 0.0), CarInput::default()],
+            vec![
+                CarInput::new(-0.25, -1.0, 1.0).with_handbrake(true),
+                CarInput::new(0.75, 0.0, 0.5),
+            ],
+        ])
+    }
+
+    /// Builds a header for `cars` cars and `count` steps
+    fn header(cars: u8, count: u64) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.push(cars);
+        bytes.extend_from_slice(&count.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let replay = sample();
+        let bytes = replay.to_bytes();
+        assert_eq!(bytes.len(), HEADER_SIZE + 2 * 2 * INPUT_SIZE);
+        assert_eq!(Replay::from_bytes(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn short_steps_are_padded() {
+        let replay = Replay::new(vec![
+            vec![CarInput::default(); 2],
+            vec![CarInput::default()],
+        ]);
+        let decoded = Replay::from_bytes(&replay.to_bytes()).unwrap();
+        assert_eq!(decoded.steps()[1], vec![CarInput::default(); 2]);
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        let mut bytes = sample().to_bytes();
+        assert!(Replay::from_bytes(&bytes[..HEADER_SIZE - 1]).is_err());
+
+        bytes[0] = b'X';
+        assert!(Replay::from_bytes(&bytes).is_err());
+
+        let mut bytes = sample().to_bytes();
+        bytes[4] = VERSION + 1;
+        assert!(Replay::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_replays_without_cars() {
+        assert!(Replay::from_bytes(&header(0, 0)).is_err());
+        assert!(Replay::from_bytes(&header(0, u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_step_count() {
+        let bytes = sample().to_bytes();
+        assert!(Replay::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut huge = header(2, u64::MAX);
+        huge.extend_from_slice(&bytes[HEADER_SIZE..]);
+        assert!(Replay::from_bytes(&huge).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let mut bytes = sample().to_bytes();
+        bytes[HEADER_SIZE + 12] = 2;
+        assert!(Replay::from_bytes(&bytes).is_err());
+
+        let mut bytes = sample().to_bytes();
+        bytes[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&2.0f32.to_le_bytes());
+        assert!(Replay::from_bytes(&bytes).is_err());
+
+        let mut bytes = sample().to_bytes();
+        bytes[HEADER_SIZE + 8..HEADER_SIZE + 12].copy_from_slice(&f32::NAN.to_le_bytes());
+        assert!(Replay::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn replay_reproduces_the_recorded_race() {
+        use super::super::Recorder;
+        use crate::game::world::World;
+
+        let mut world = World::new_two_player();
+        let mut recorder = Recorder::new();
+        for step in 0..600 {
+            let turn = ((step / 60) as f32 * 0.7).sin();
+            let inputs = [
+                CarInput::new(1.0, turn, 0.0),
+                CarInput::new(0.8, -turn, 0.0).with_handbrake(step % 90 < 10),
+            ];
+            recorder.record(&inputs);
+            world.step_fixed(&inputs);
+        }
+
+        let replay = Replay::from_bytes(&recorder.to_replay().to_bytes()).unwrap();
+        let mut replayed = World::new_two_player();
+        for inputs in replay.iter() {
+            replayed.step_fixed(inputs);
+        }
+
+        assert_eq!(replayed.cars.len(), world.cars.len());
+        for (original, copy) in world.cars.iter().zip(&replayed.cars) {
+            assert_eq!(copy.position(), original.position());
+            assert_eq!(copy.velocity(), original.velocity());
+        }
+    }
+}
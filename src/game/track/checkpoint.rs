@@ -0,0 +1,236 @@
+//! Checkpoints and lap progress tracking
+
+use super::RacingLine;
+use glam::Vec2;
+
+/// A circular trigger area cars have to drive through
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    /// Center of the trigger area
+    position: Vec2,
+    /// Radius of the trigger area
+    radius: f32,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Center of the trigger area
+    /// * `radius` - Radius of the trigger area, clamped to ≥ 0
+    pub fn new(position: Vec2, radius: f32) -> Self {
+        Self {
+            position,
+            radius: radius.max(0.0),
+        }
+    }
+
+    /// Places a checkpoint on every `every`-th point of a racing line
+    ///
+    /// # Arguments
+    ///
+    /// * `racing_line` - Racing line to take the positions from
+    /// * `every` - Spacing in racing line points (0 is treated as 1)
+    /// * `radius` - Radius of each trigger area
+    ///
+    /// # Returns
+    ///
+    /// Checkpoints in driving order, starting at the first racing line point
+    pub fn along(racing_line: &RacingLine, every: usize, radius: f32) -> Vec<Self> {
+        racing_line
+            .points()
+            .iter()
+            .step_by(every.max(1))
+            .map(|&point| Self::new(point, radius))
+            .collect()
+    }
+
+    /// Returns the center of the trigger area
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Returns the radius of the trigger area
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns whether a car at `position` triggers this checkpoint
+    pub fn check_trigger(&self, position: Vec2) -> bool {
+        position.distance_squared(self.position) <= self.radius * self.radius
+    }
+
+    /// Returns how far along a movement the trigger area was entered
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Position at the start of the movement
+    /// * `to` - Position at the end of the movement
+    ///
+    /// # Returns
+    ///
+    /// Fraction of the way from `from` to `to` at which the trigger area
+    /// is first touched: 0.0 if `from` is already inside, 1.0 if the
+    /// movement never touches it
+    pub fn entry_fraction(&self, from: Vec2, to: Vec2) -> f32 {
+        if self.check_trigger(from) {
+            return 0.0;
+        }
+
+        // Solve |from + delta * t - position| = radius for the first t
+        let offset = from - self.position;
+        let delta = to - from;
+        let a = delta.length_squared();
+        let b = 2.0 * offset.dot(delta);
+        let c = offset.length_squared() - self.radius * self.radius;
+        let discriminant = b * b - 4.0 * a * c;
+        if a == 0.0 || discriminant < 0.0 {
+            return 1.0;
+        }
+
+        ((-b - discriminant.sqrt()) / (2.0 * a)).clamp(0.0, 1.0)
+    }
+}
+
+/// Lap progress of a single car
+///
+/// Checkpoints only count when driven in order. The first pass through
+/// checkpoint 0 starts the race, every later pass through checkpoint 0
+/// after all other checkpoints completes a lap.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LapTracker {
+    /// Index of the checkpoint that has to be triggered next
+    next: usize,
+    /// Completed laps
+    laps: u32,
+    /// Whether checkpoint 0 has been passed at least once
+    started: bool,
+}
+
+impl LapTracker {
+    /// Creates a tracker waiting for checkpoint 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of completed laps
+    pub fn laps(&self) -> u32 {
+        self.laps
+    }
+
+    /// Returns the index of the checkpoint that has to be triggered next
+    pub fn next_checkpoint(&self) -> usize {
+        self.next
+    }
+
+    /// Returns how many checkpoints have been triggered in order so far
+    ///
+    /// Counts across laps, so a car further ahead in the race always has
+    /// a higher value.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Total number of checkpoints on the track
+    pub fn checkpoints_passed(&self, count: usize) -> usize {
+        if !self.started {
+            return 0;
+        }
+
+        let this_lap = if self.next == 0 { count } else { self.next };
+        self.laps as usize * count + this_lap
+    }
+
+    /// Registers a triggered checkpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the triggered checkpoint
+    /// * `count` - Total number of checkpoints on the track
+    ///
+    /// # Returns
+    ///
+    /// true if this completed a lap, always false with fewer than 2
+    /// checkpoints since a car sitting in the only one would complete a
+    /// lap on every call
+    pub fn trigger(&mut self, index: usize, count: usize) -> bool {
+        if count < 2 || index != self.next {
+            return false;
+        }
+
+        let completed = index == 0 && self.started;
+        if completed {
+            self.laps += 1;
+        }
+
+        self.started = true;
+        self.next = (self.next + 1) % count;
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_trigger_includes_edge() {
+        let checkpoint = Checkpoint::new(Vec2::ZERO, 10.0);
+        assert!(checkpoint.check_trigger(Vec2::new(10.0, 0.0)));
+        assert!(!checkpoint.check_trigger(Vec2::new(10.1, 0.0)));
+    }
+
+    #[test]
+    fn entry_fraction_finds_first_touch() {
+        let checkpoint = Checkpoint::new(Vec2::ZERO, 10.0);
+        let fraction = checkpoint.entry_fraction(Vec2::new(-30.0, 0.0), Vec2::new(10.0, 0.0));
+        assert!((fraction - 0.5).abs() < 1e-5);
+        assert_eq!(checkpoint.entry_fraction(Vec2::ZERO, Vec2::X), 0.0);
+        assert_eq!(
+            checkpoint.entry_fraction(Vec2::new(-30.0, 20.0), Vec2::new(30.0, 20.0)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn lap_tracker_ignores_out_of_order_checkpoints() {
+        let mut tracker = LapTracker::new();
+        assert!(!tracker.trigger(1, 3));
+        assert_eq!(tracker.next_checkpoint(), 0);
+        assert_eq!(tracker.checkpoints_passed(3), 0);
+
+        assert!(!tracker.trigger(0, 3));
+        assert!(!tracker.trigger(2, 3));
+        assert_eq!(tracker.next_checkpoint(), 1);
+        assert_eq!(tracker.checkpoints_passed(3), 1);
+    }
+
+    #[test]
+    fn lap_tracker_counts_laps_after_start() {
+        let mut tracker = LapTracker::new();
+        // The first pass through checkpoint 0 only starts the race
+        assert!(!tracker.trigger(0, 3));
+        assert!(!tracker.trigger(1, 3));
+        assert!(!tracker.trigger(2, 3));
+        assert_eq!(tracker.checkpoints_passed(3), 3);
+        assert!(tracker.trigger(0, 3));
+        assert_eq!(tracker.laps(), 1);
+        assert_eq!(tracker.checkpoints_passed(3), 4);
+
+        // Sitting in checkpoint 0 doesn't count again
+        assert!(!tracker.trigger(0, 3));
+        assert_eq!(tracker.laps(), 1);
+    }
+
+    #[test]
+    fn lap_tracker_needs_two_checkpoints() {
+        let mut tracker = LapTracker::new();
+        for _ in 0..10 {
+            assert!(!tracker.trigger(0, 1));
+            assert!(!tracker.trigger(0, 0));
+        }
+        assert_eq!(tracker.laps(), 0);
+    }
+}
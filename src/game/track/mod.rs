@@ -0,0 +1,22 @@
+//! Tile-based track representation
+//!
+//! Describes a track as a grid of tiles where each tile has
+//! surface properties (friction, collision, color) that the
+//! renderer and physics can query, plus the racing line that
+//! defines the driving direction and the checkpoints that count laps.
+//! Off-track ground can also be detected from the ground texture colors,
+//! and whole tracks can be described in `.track` files.
+
+mod checkpoint;
+mod racing_line;
+mod surface;
+mod tile;
+mod tilemap;
+mod track_def;
+
+pub use checkpoint::{Checkpoint, LapTracker};
+pub use racing_line::RacingLine;
+pub use surface::OffTrackSurface;
+pub use tile::TileType;
+pub use tilemap::TileMap;
+pub use track_def::TrackDef;
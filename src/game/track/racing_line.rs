@@ -0,0 +1,133 @@
+//! Ordered checkpoints describing the intended driving direction
+
+use glam::Vec2;
+
+/// A closed loop of checkpoints in driving order
+///
+/// RacingLine provides:
+/// * The checkpoint positions of a track in the order they are driven
+/// * The expected driving direction at any world position
+///
+/// The last checkpoint connects back to the first one.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RacingLine {
+    /// Checkpoint positions in driving order
+    points: Vec<Vec2>,
+}
+
+impl RacingLine {
+    /// Creates a racing line from checkpoints in driving order
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two checkpoints are given
+    pub fn new(points: Vec<Vec2>) -> Self {
+        assert!(
+            points.len() >= 2,
+            "A racing line needs at least 2 checkpoints, got {}",
+            points.len()
+        );
+
+        Self { points }
+    }
+
+    /// Racing line of the default track, driven counterclockwise
+    pub fn default_track() -> Self {
+        Self::new(vec![
+            Vec2::new(850.0, 600.0),
+            Vec2::new(850.0, 300.0),
+            Vec2::new(800.0, 190.0),
+            Vec2::new(680.0, 185.0),
+            Vec2::new(560.0, 250.0),
+            Vec2::new(455.0, 320.0),
+            Vec2::new(350.0, 250.0),
+            Vec2::new(250.0, 180.0),
+            Vec2::new(180.0, 260.0),
+            Vec2::new(176.0, 500.0),
+            Vec2::new(180.0, 720.0),
+            Vec2::new(260.0, 820.0),
+            Vec2::new(380.0, 850.0),
+            Vec2::new(480.0, 790.0),
+            Vec2::new(590.0, 745.0),
+            Vec2::new(700.0, 800.0),
+            Vec2::new(790.0, 850.0),
+            Vec2::new(850.0, 780.0),
+        ])
+    }
+
+    /// Returns the checkpoints in driving order
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Returns the segment from checkpoint `index` to the next one
+    pub fn segment(&self, index: usize) -> (Vec2, Vec2) {
+        let start = self.points[index % self.points.len()];
+        let end = self.points[(index + 1) % self.points.len()];
+        (start, end)
+    }
+
+    /// Finds the segment closest to a world position
+    ///
+    /// # Returns
+    ///
+    /// Index of the checkpoint the closest segment starts at
+    pub fn nearest_segment(&self, pos: Vec2) -> usize {
+        (0..self.points.len())
+            .map(|i| {
+                let (start, end) = self.segment(i);
+                let along = end - start;
+                let t = ((pos - start).dot(along) / along.length_squared()).clamp(0.0, 1.0);
+                (i, pos.distance_squared(start + along * t))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Returns the intended driving direction at a world position
+    ///
+    /// # Returns
+    ///
+    /// Normalized direction of the closest segment
+    pub fn direction_at(&self, pos: Vec2) -> Vec2 {
+        let (start, end) = self.segment(self.nearest_segment(pos));
+        (end - start).normalize_or_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> RacingLine {
+        RacingLine::new(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(100.0, 0.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 100.0),
+        ])
+    }
+
+    #[test]
+    fn last_segment_closes_the_loop() {
+        let line = square();
+        assert_eq!(line.segment(3), (Vec2::new(0.0, 100.0), Vec2::ZERO));
+        assert_eq!(line.segment(4), line.segment(0));
+    }
+
+    #[test]
+    fn direction_follows_the_nearest_segment() {
+        let line = square();
+        assert_eq!(line.nearest_segment(Vec2::new(50.0, -10.0)), 0);
+        assert_eq!(line.direction_at(Vec2::new(50.0, -10.0)), Vec2::X);
+        assert_eq!(line.direction_at(Vec2::new(110.0, 50.0)), Vec2::Y);
+        assert_eq!(line.direction_at(Vec2::new(-5.0, 50.0)), Vec2::NEG_Y);
+    }
+
+    #[test]
+    #[should_panic]
+    fn needs_two_points() {
+        RacingLine::new(vec![Vec2::ZERO]);
+    }
+}
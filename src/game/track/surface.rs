@@ -0,0 +1,116 @@
+//! Off-track detection from the colors of the ground texture
+
+use crate::assets::Texture;
+use glam::Vec2;
+
+/// Grass color of the default track texture
+const GRASS_COLOR: [u8; 3] = [70, 144, 70];
+
+/// Largest per-channel difference that still counts as grass
+const GRASS_TOLERANCE: u8 = 24;
+
+/// Extra friction on grass, the same as a grass tile
+const GRASS_FRICTION: f32 = 2.0;
+
+/// Slows cars down on ground texture pixels of an off-track color
+///
+/// OffTrackSurface provides:
+/// * Lookups of the ground texture under a world position
+/// * Color matching with a per-channel tolerance
+/// * The extra friction of off-track ground
+///
+/// The texture maps one texel to one world unit, like the renderer's
+/// default. Positions outside the texture count as off-track.
+#[derive(Debug, Clone)]
+pub struct OffTrackSurface {
+    /// Ground texture the track is drawn on
+    ground: Texture,
+    /// RGB color of off-track ground
+    color: [u8; 3],
+    /// Largest per-channel difference that still matches `color`
+    tolerance: u8,
+    /// Linear friction coefficient applied off-track
+    friction: f32,
+}
+
+impl OffTrackSurface {
+    /// Creates an off-track surface
+    ///
+    /// # Arguments
+    ///
+    /// * `ground` - Ground texture the track is drawn on
+    /// * `color` - RGB color of off-track ground
+    /// * `tolerance` - Largest per-channel difference that still matches
+    /// * `friction` - Linear friction coefficient applied off-track
+    pub fn new(ground: Texture, color: [u8; 3], tolerance: u8, friction: f32) -> Self {
+        Self {
+            ground,
+            color,
+            tolerance,
+            friction,
+        }
+    }
+
+    /// Creates an off-track surface matching the grass of the default track
+    pub fn grass(ground: Texture) -> Self {
+        Self::new(ground, GRASS_COLOR, GRASS_TOLERANCE, GRASS_FRICTION)
+    }
+
+    /// Returns the friction coefficient applied off-track
+    pub fn friction(&self) -> f32 {
+        self.friction
+    }
+
+    /// Returns whether a world position is off the track
+    pub fn is_off_track(&self, position: Vec2) -> bool {
+        let (width, height) = (self.ground.width() as f32, self.ground.height() as f32);
+        if !(0.0..width).contains(&position.x) || !(0.0..height).contains(&position.y) {
+            return true;
+        }
+
+        let texel = self.ground.sample(position.x, position.y, [0; 4]);
+        texel
+            .iter()
+            .zip(self.color)
+            .all(|(&channel, expected)| channel.abs_diff(expected) <= self.tolerance)
+    }
+
+    /// Returns the extra friction at a world position, 0.0 on the track
+    pub fn friction_at(&self, position: Vec2) -> f32 {
+        if self.is_off_track(position) {
+            self.friction
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2x1 ground with grass on the left and road on the right
+    fn surface() -> OffTrackSurface {
+        let mut pixels = vec![72, 140, 75, 255];
+        pixels.extend_from_slice(&[78, 76, 85, 255]);
+        OffTrackSurface::grass(Texture::from_rgba(2, 1, pixels).unwrap())
+    }
+
+    #[test]
+    fn grass_colors_are_off_track() {
+        let surface = surface();
+        assert!(surface.is_off_track(Vec2::new(0.5, 0.5)));
+        assert!(!surface.is_off_track(Vec2::new(1.5, 0.5)));
+        assert_eq!(surface.friction_at(Vec2::new(0.5, 0.5)), surface.friction());
+        assert_eq!(surface.friction_at(Vec2::new(1.5, 0.5)), 0.0);
+    }
+
+    #[test]
+    fn outside_the_texture_is_off_track() {
+        let surface = surface();
+        assert!(surface.is_off_track(Vec2::new(-0.1, 0.5)));
+        assert!(surface.is_off_track(Vec2::new(2.0, 0.5)));
+        assert!(surface.is_off_track(Vec2::new(1.5, 1.0)));
+        assert!(surface.is_off_track(Vec2::NAN));
+    }
+}
@@ -0,0 +1,50 @@
+//! Tile types and their surface properties
+
+/// The kind of surface a track tile is made of
+///
+/// Each tile type carries fixed properties:
+/// * Surface friction applied to cars driving over it
+/// * Whether it blocks cars (walls)
+/// * The color used when drawing it into a ground texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileType {
+    /// Asphalt, no extra friction
+    Road,
+    /// Off-track surface that slows cars down
+    Grass,
+    /// Solid barrier
+    Wall,
+    /// Speed pad that pushes cars along their velocity
+    Boost,
+}
+
+impl TileType {
+    /// Linear surface friction coefficient
+    ///
+    /// Applied against the car's velocity every update. Negative
+    /// values push the car along its current velocity instead.
+    pub fn friction(&self) -> f32 {
+        match self {
+            TileType::Road => 0.0,
+            TileType::Grass => 2.0,
+            TileType::Wall => 0.0,
+            TileType::Boost => -0.5,
+        }
+    }
+
+    /// Whether cars collide with this tile
+    pub fn is_solid(&self) -> bool {
+        matches!(self, TileType::Wall)
+    }
+
+    /// RGBA color used when rendering the tile
+    pub fn color(&self) -> [u8; 4] {
+        match self {
+            TileType::Road => [80, 78, 88, 255],
+            TileType::Grass => [72, 145, 70, 255],
+            TileType::Wall => [230, 60, 60, 255],
+            TileType::Boost => [250, 210, 60, 255],
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! A 2D grid of track tiles
+
+use super::TileType;
+use crate::assets::Texture;
+use anyhow::{ensure, Context as _, Result};
+use glam::Vec2;
+
+/// A uniform grid of tiles covering the world
+///
+/// TileMap provides:
+/// * Tile storage in row-major order
+/// * World position to tile lookups
+/// * Conversion into a ground texture for rendering
+///
+/// Tile (0, 0) covers world coordinates `0..tile_size` on both axes.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileMap {
+    /// Number of tiles horizontally
+    width: u32,
+    /// Number of tiles vertically
+    height: u32,
+    /// Size of a single tile in world units
+    tile_size: f32,
+    /// Tiles in row-major order
+    tiles: Vec<TileType>,
+}
+
+impl TileMap {
+    /// Creates a tile map filled with a single tile type
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Number of tiles horizontally
+    /// * `height` - Number of tiles vertically
+    /// * `tile_size` - Size of a single tile in world units
+    /// * `fill` - Tile type every cell starts as
+    ///
+    /// # Errors
+    ///
+    /// If the tile size isn't positive or the map holds more than
+    /// `u32::MAX` tiles
+    pub fn new(width: u32, height: u32, tile_size: f32, fill: TileType) -> Result<Self> {
+        ensure!(
+            tile_size > 0.0,
+            "Tile size must be positive, got {}",
+            tile_size
+        );
+        let count = width
+            .checked_mul(height)
+            .with_context(|| format!("A {}x{} tile map is too large", width, height))?;
+
+        Ok(Self {
+            width,
+            height,
+            tile_size,
+            tiles: vec![fill; count as usize],
+        })
+    }
+
+    /// Returns whether the tile storage matches the grid size
+    ///
+    /// Always true for maps built through `new`, only deserialized maps
+    /// can be inconsistent, e.g. with a tile size that isn't positive.
+    pub fn is_consistent(&self) -> bool {
+        self.tile_size > 0.0
+            && self
+                .width
+                .checked_mul(self.height)
+                .is_some_and(|count| count as usize == self.tiles.len())
+    }
+
+    /// Returns the tile at grid coordinates, None if outside the grid
+    pub fn get(&self, x: u32, y: u32) -> Option<TileType> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.tiles[(y * self.width + x) as usize])
+    }
+
+    /// Sets the tile at grid coordinates, ignored if outside the grid
+    pub fn set(&mut self, x: u32, y: u32, tile: TileType) {
+        if x < self.width && y < self.height {
+            self.tiles[(y * self.width + x) as usize] = tile;
+        }
+    }
+
+    /// Returns the tile under a world position
+    ///
+    /// # Arguments
+    ///
+    /// * `world_pos` - Position in world space
+    ///
+    /// # Returns
+    ///
+    /// The tile covering the position, None if outside the map
+    pub fn tile_at(&self, world_pos: Vec2) -> Option<TileType> {
+        if world_pos.x < 0.0 || world_pos.y < 0.0 {
+            return None;
+        }
+
+        let x = (world_pos.x / self.tile_size) as u32;
+        let y = (world_pos.y / self.tile_size) as u32;
+        self.get(x, y)
+    }
+
+    /// Renders the tiles into a ground texture
+    ///
+    /// The texture uses one texel per world unit so it lines up with
+    /// the world when used as the renderer's ground texture.
+    pub fn to_texture(&self) -> Texture {
+        let tex_width = (self.width as f32 * self.tile_size) as u32;
+        let tex_height = (self.height as f32 * self.tile_size) as u32;
+        let mut pixels = Vec::with_capacity(tex_width as usize * tex_height as usize * 4);
+
+        for y in 0..tex_height {
+            for x in 0..tex_width {
+                let tile = self
+                    .tile_at(Vec2::new(x as f32, y as f32))
+                    .unwrap_or(TileType::Grass);
+                pixels.extend_from_slice(&tile.color());
+            }
+        }
+
+        Texture {
+            width: tex_width,
+            height: tex_height,
+            pixels,
+        }
+    }
+
+    /// Get the number of tiles horizontally
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the number of tiles vertically
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the size of a single tile in world units
+    pub fn tile_size(&self) -> f32 {
+        self.tile_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_validates_the_size() {
+        assert!(TileMap::new(4, 3, 16.0, TileType::Road).is_ok());
+        assert!(TileMap::new(4, 3, 0.0, TileType::Road).is_err());
+        assert!(TileMap::new(4, 3, -1.0, TileType::Road).is_err());
+        assert!(TileMap::new(4, 3, f32::NAN, TileType::Road).is_err());
+        assert!(TileMap::new(u32::MAX, 2, 16.0, TileType::Road).is_err());
+    }
+
+    #[test]
+    fn tile_at_maps_world_positions() {
+        let mut map = TileMap::new(4, 3, 16.0, TileType::Road).unwrap();
+        map.set(1, 2, TileType::Grass);
+        map.set(9, 9, TileType::Grass);
+        assert!(map.is_consistent());
+
+        assert_eq!(map.tile_at(Vec2::new(20.0, 40.0)), Some(TileType::Grass));
+        assert_eq!(map.tile_at(Vec2::new(15.9, 40.0)), Some(TileType::Road));
+        assert_eq!(map.tile_at(Vec2::new(-0.1, 0.0)), None);
+        assert_eq!(map.tile_at(Vec2::new(64.0, 0.0)), None);
+        assert_eq!(map.get(4, 0), None);
+    }
+
+    #[test]
+    fn texture_has_one_texel_per_world_unit() {
+        let mut map = TileMap::new(2, 1, 4.0, TileType::Road).unwrap();
+        map.set(1, 0, TileType::Grass);
+        let texture = map.to_texture();
+
+        assert_eq!((texture.width(), texture.height()), (8, 4));
+        assert_eq!(&texture.pixels[..4], &TileType::Road.color());
+        assert_eq!(&texture.pixels[4 * 4..4 * 5], &TileType::Grass.color());
+    }
+}
@@ -0,0 +1,212 @@
+//! Data-driven track descriptions
+
+use super::{Checkpoint, RacingLine};
+use crate::consts::TRACK_FILE;
+use crate::game::world::StartGrid;
+use anyhow::{ensure, Result};
+use glam::Vec2;
+
+/// Everything needed to race on a track, loadable from a `.track` file
+///
+/// A `.track` file is TOML, e.g.
+///
+/// ```toml
+/// texture = "track.png"
+/// size = [1024.0, 1024.0]
+/// laps = 3
+///
+/// [grid]
+/// origin = [845.0, 720.0]
+/// angle = 3.1416
+/// lane_spacing = 50.0
+/// row_spacing = 70.0
+/// lanes = 2
+///
+/// [[checkpoints]]
+/// position = [850.0, 600.0]
+/// radius = 100.0
+///
+/// [[checkpoints]]
+/// position = [180.0, 260.0]
+/// radius = 100.0
+/// ```
+///
+/// At least 2 checkpoints are required, a lap is only counted after
+/// driving through all of them in order and back to the first one. The
+/// racing line may be left out, the checkpoints are driven in order
+/// then.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackDef {
+    /// Asset name of the ground texture
+    pub texture: String,
+    /// Width and height of the playfield in world units
+    pub size: Vec2,
+    /// Laps needed to finish a race
+    pub laps: u32,
+    /// Starting positions of the cars
+    pub grid: StartGrid,
+    /// Checkpoints that have to be driven through in order
+    pub checkpoints: Vec<Checkpoint>,
+    /// Points in driving order, empty to follow the checkpoints
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub racing_line: Vec<Vec2>,
+}
+
+impl Default for TrackDef {
+    /// Describes the built-in track
+    ///
+    /// * `TRACK_FILE` stretched over a 1024x1024 playfield
+    /// * 3 laps
+    /// * The default starting grid and racing line
+    /// * A checkpoint with a radius of 100 on every third racing line point
+    fn default() -> Self {
+        let racing_line = RacingLine::default_track();
+        Self {
+            texture: TRACK_FILE.to_string(),
+            size: Vec2::splat(1024.0),
+            laps: 3,
+            grid: StartGrid::default(),
+            checkpoints: Checkpoint::along(&racing_line, 3, 100.0),
+            racing_line: racing_line.points().to_vec(),
+        }
+    }
+}
+
+impl TrackDef {
+    /// Reads a track from a `.track` file
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be read, isn't valid TOML or fails
+    /// [`TrackDef::validate`]
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        use anyhow::Context as _;
+
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read track from {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("Invalid track file {}", path.display()))
+    }
+
+    /// Parses a track from the contents of a `.track` file
+    ///
+    /// # Errors
+    ///
+    /// If the text isn't valid TOML or fails [`TrackDef::validate`]
+    #[cfg(feature = "serde")]
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let track: Self = toml::from_str(text)?;
+        track.validate()?;
+        Ok(track)
+    }
+
+    /// Writes the track in the `.track` file format
+    #[cfg(feature = "serde")]
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Checks that a race can be driven on the track
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer than 2 checkpoints or racing line points, no
+    /// laps, a checkpoint with a negative radius or an empty playfield
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.checkpoints.len() >= 2,
+            "Track needs at least 2 checkpoints"
+        );
+        ensure!(
+            self.checkpoints
+                .iter()
+                .all(|checkpoint| checkpoint.radius() >= 0.0),
+            "Checkpoint radius can't be negative"
+        );
+        ensure!(
+            self.racing_line_points().len() >= 2,
+            "Racing line needs at least 2 points"
+        );
+        ensure!(self.laps > 0, "Track needs at least 1 lap");
+        ensure!(
+            self.size.x > 0.0 && self.size.y > 0.0,
+            "Track size must be positive"
+        );
+        Ok(())
+    }
+
+    /// Returns the racing line, following the checkpoints if none is given
+    pub fn racing_line_points(&self) -> Vec<Vec2> {
+        if self.racing_line.is_empty() {
+            self.checkpoints.iter().map(Checkpoint::position).collect()
+        } else {
+            self.racing_line.clone()
+        }
+    }
+
+    /// Returns the racing line, see [`TrackDef::racing_line_points`]
+    ///
+    /// # Errors
+    ///
+    /// If the track fails [`TrackDef::validate`]
+    pub fn racing_line(&self) -> Result<RacingLine> {
+        self.validate()?;
+        Ok(RacingLine::new(self.racing_line_points()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_track_is_valid() {
+        assert!(TrackDef::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_needs_two_checkpoints() {
+        let mut track = TrackDef::default();
+        track.checkpoints.truncate(1);
+        assert!(track.validate().is_err());
+        track.checkpoints.clear();
+        assert!(track.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unraceable_tracks() {
+        let mut track = TrackDef {
+            laps: 0,
+            ..TrackDef::default()
+        };
+        assert!(track.validate().is_err());
+
+        track.laps = 1;
+        track.size = Vec2::new(1024.0, 0.0);
+        assert!(track.validate().is_err());
+
+        track.size = Vec2::splat(1024.0);
+        track.racing_line = vec![Vec2::ZERO];
+        assert!(track.validate().is_err());
+    }
+
+    #[test]
+    fn racing_line_falls_back_to_checkpoints() {
+        let track = TrackDef {
+            racing_line: Vec::new(),
+            ..TrackDef::default()
+        };
+        let points: Vec<Vec2> = track.checkpoints.iter().map(Checkpoint::position).collect();
+        assert_eq!(track.racing_line_points(), points);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn toml_round_trip() {
+        let track = TrackDef::default();
+        let text = track.to_toml().unwrap();
+        assert_eq!(TrackDef::from_toml(&text).unwrap(), track);
+    }
+}
@@ -0,0 +1,94 @@
+//! Axis-aligned bounding boxes
+
+use glam::Vec2;
+
+/// An axis-aligned rectangle in world space
+///
+/// Edges are inclusive: a point on the edge is contained and boxes that
+/// only touch count as intersecting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb {
+    /// Corner with the smallest coordinates
+    pub min: Vec2,
+    /// Corner with the largest coordinates
+    pub max: Vec2,
+}
+
+impl Aabb {
+    /// Creates a box from two opposite corners in any order
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Creates a box around a center point
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - Center of the box
+    /// * `half_size` - Distance from the center to the edges, negative is treated as 0
+    pub fn from_center(center: Vec2, half_size: Vec2) -> Self {
+        let half_size = half_size.max(Vec2::ZERO);
+        Self {
+            min: center - half_size,
+            max: center + half_size,
+        }
+    }
+
+    /// Returns the center of the box
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns the width and height of the box
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Returns whether a point lies inside the box or on its edge
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Returns whether two boxes overlap or touch
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners_in_any_order() {
+        let aabb = Aabb::new(Vec2::new(4.0, -1.0), Vec2::new(-2.0, 3.0));
+        assert_eq!(aabb.min, Vec2::new(-2.0, -1.0));
+        assert_eq!(aabb.max, Vec2::new(4.0, 3.0));
+        assert_eq!(aabb.center(), Vec2::new(1.0, 1.0));
+        assert_eq!(aabb.size(), Vec2::new(6.0, 4.0));
+    }
+
+    #[test]
+    fn edges_are_inclusive() {
+        let aabb = Aabb::from_center(Vec2::ZERO, Vec2::ONE);
+        assert!(aabb.contains(Vec2::new(1.0, -1.0)));
+        assert!(!aabb.contains(Vec2::new(1.01, 0.0)));
+
+        let touching = Aabb::new(Vec2::new(1.0, 0.0), Vec2::new(2.0, 1.0));
+        let apart = Aabb::new(Vec2::new(1.1, 0.0), Vec2::new(2.0, 1.0));
+        assert!(aabb.intersects(&touching));
+        assert!(touching.intersects(&aabb));
+        assert!(!aabb.intersects(&apart));
+    }
+
+    #[test]
+    fn negative_half_size_is_a_point() {
+        let aabb = Aabb::from_center(Vec2::ONE, Vec2::splat(-3.0));
+        assert_eq!(aabb.size(), Vec2::ZERO);
+        assert!(aabb.contains(Vec2::ONE));
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers for angles in radians
+
+use std::f32::consts::{PI, TAU};
+
+/// Returns the shortest signed turn from one angle to another
+///
+/// # Returns
+///
+/// The difference wrapped into -π..π, positive turning counterclockwise
+pub fn angle_difference(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Interpolates between two angles along the shortest way around the circle
+///
+/// The result is not wrapped, so an angle that is smoothed every frame
+/// never jumps by a full turn.
+///
+/// # Arguments
+///
+/// * `from` - Angle at `t = 0.0`
+/// * `to` - Angle at `t = 1.0`, or any angle a whole number of turns away
+/// * `t` - Interpolation factor, usually 0.0..=1.0
+pub fn lerp_angle(from: f32, to: f32, t: f32) -> f32 {
+    from + angle_difference(from, to) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difference_takes_the_short_way() {
+        assert!((angle_difference(0.1, TAU - 0.1) + 0.2).abs() < 1e-5);
+        assert!((angle_difference(TAU - 0.1, 0.1) - 0.2).abs() < 1e-5);
+        assert!((angle_difference(0.0, 3.0 * TAU + 1.0) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn lerp_doesnt_wrap() {
+        let halfway = lerp_angle(TAU - 0.1, 0.1, 0.5);
+        assert!((halfway - TAU).abs() < 1e-5);
+        assert_eq!(lerp_angle(1.0, 2.0, 0.0), 1.0);
+        assert!((lerp_angle(1.0, 2.0, 1.0) - 2.0).abs() < 1e-6);
+    }
+}
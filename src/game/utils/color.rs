@@ -0,0 +1,112 @@
+//! Pixel color helpers
+
+/// Composites an RGBA color over a frame pixel (source-over)
+///
+/// The result is `src.rgb * a + dst.rgb * (1 - a)` with `a` being the
+/// source alpha; the destination alpha accumulates the same way.
+///
+/// # Arguments
+///
+/// * `dst` - Destination pixel, at least 4 bytes of RGBA
+/// * `src` - RGBA color to draw on top
+pub fn blend_pixel(dst: &mut [u8], src: [u8; 4]) {
+    let alpha = src[3] as u32;
+    match alpha {
+        0 => {}
+        255 => dst[..4].copy_from_slice(&src),
+        _ => {
+            for i in 0..3 {
+                dst[i] =
+                    ((src[i] as u32 * alpha + dst[i] as u32 * (255 - alpha) + 127) / 255) as u8;
+            }
+            dst[3] = (alpha + (dst[3] as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Returns the byte range of a pixel in an RGBA frame
+///
+/// # Returns
+///
+/// None if the pixel is outside the frame dimensions or the frame is
+/// too small to hold it
+fn pixel_range(
+    frame_len: usize,
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+) -> Option<std::ops::Range<usize>> {
+    if x >= width || y >= height {
+        return None;
+    }
+
+    let idx = (y as usize * width as usize + x as usize) * 4;
+    (idx + 4 <= frame_len).then_some(idx..idx + 4)
+}
+
+/// Writes a pixel into an RGBA frame, ignoring out-of-range coordinates
+///
+/// # Arguments
+///
+/// * `frame` - RGBA pixel buffer of `width * height * 4` bytes
+/// * `width` - Frame width in pixels
+/// * `height` - Frame height in pixels
+/// * `x` - Column of the pixel
+/// * `y` - Row of the pixel
+/// * `color` - RGBA color to write
+pub fn put_pixel(frame: &mut [u8], width: u32, height: u32, x: u32, y: u32, color: [u8; 4]) {
+    if let Some(range) = pixel_range(frame.len(), width, height, x, y) {
+        frame[range].copy_from_slice(&color);
+    }
+}
+
+/// Composites a color over a pixel of an RGBA frame, ignoring
+/// out-of-range coordinates
+///
+/// Same as [`put_pixel`] but blends like [`blend_pixel`].
+pub fn blend_pixel_at(frame: &mut [u8], width: u32, height: u32, x: u32, y: u32, color: [u8; 4]) {
+    if let Some(range) = pixel_range(frame.len(), width, height, x, y) {
+        blend_pixel(&mut frame[range], color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blend_respects_alpha() {
+        let mut pixel = [0, 0, 0, 255];
+        blend_pixel(&mut pixel, [255, 255, 255, 0]);
+        assert_eq!(pixel, [0, 0, 0, 255]);
+
+        blend_pixel(&mut pixel, [255, 0, 100, 128]);
+        assert_eq!(pixel, [128, 0, 50, 255]);
+
+        blend_pixel(&mut pixel, [1, 2, 3, 255]);
+        assert_eq!(pixel, [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn alpha_accumulates() {
+        let mut pixel = [0, 0, 0, 0];
+        blend_pixel(&mut pixel, [255, 255, 255, 128]);
+        assert_eq!(pixel[3], 128);
+        blend_pixel(&mut pixel, [255, 255, 255, 128]);
+        assert_eq!(pixel[3], 192);
+    }
+
+    #[test]
+    fn out_of_range_pixels_are_ignored() {
+        let mut frame = vec![0; 2 * 2 * 4];
+        put_pixel(&mut frame, 2, 2, 1, 1, [9; 4]);
+        assert_eq!(&frame[12..], &[9; 4]);
+
+        put_pixel(&mut frame, 2, 2, 2, 0, [7; 4]);
+        blend_pixel_at(&mut frame, 2, 2, 0, 2, [7; 4]);
+        // A frame smaller than its dimensions claim
+        put_pixel(&mut frame, 4, 4, 3, 3, [7; 4]);
+        assert!(!frame.contains(&7));
+    }
+}
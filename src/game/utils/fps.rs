@@ -56,12 +56,23 @@ impl FpsCounter {
     /// * `Some(fps)` - New FPS calculation if interval completed
     /// * `None` - Still counting frames in current interval
     pub fn update(&mut self) -> Option<f32> {
-        self.frame_count += 1;
-
         let current_time = Instant::now();
         let dt = current_time.duration_since(self.last_time).as_secs_f32();
         self.last_time = current_time;
 
+        self.update_with(dt)
+    }
+
+    /// Counts a frame that took `dt` seconds
+    ///
+    /// Same as [`FpsCounter::update`] with the frame time measured by
+    /// the caller instead of the clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Duration of the frame in seconds
+    pub fn update_with(&mut self, dt: f32) -> Option<f32> {
+        self.frame_count += 1;
         self.elapsed += dt;
 
         if self.elapsed >= self.update_interval {
@@ -73,4 +84,33 @@ impl FpsCounter {
             None
         }
     }
+
+    /// Returns the FPS of the last completed interval, 0.0 before the first
+    pub fn fps(&self) -> f32 {
+        self.current_fps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_frames_over_the_interval() {
+        let mut counter = FpsCounter::new(1.0);
+        assert_eq!(counter.fps(), 0.0);
+
+        // Uneven frames still average to 4 frames per second
+        for dt in [0.1, 0.4, 0.2] {
+            assert_eq!(counter.update_with(dt), None);
+        }
+        let fps = counter.update_with(0.3).unwrap();
+        assert!((fps - 4.0).abs() < 1e-4, "{fps}");
+        assert_eq!(counter.fps(), fps);
+
+        // The next interval starts from scratch
+        assert_eq!(counter.update_with(0.5), None);
+        let fps = counter.update_with(0.5).unwrap();
+        assert!((fps - 2.0).abs() < 1e-4, "{fps}");
+    }
 }
@@ -0,0 +1,63 @@
+//! Per-phase frame timing for stutter diagnostics
+
+use std::fmt;
+use std::time::Duration;
+
+/// A phase of a frame that is timed separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePhase {
+    /// World simulation (`World::update`)
+    Update,
+    /// Drawing the frame
+    Render,
+}
+
+impl fmt::Display for FramePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FramePhase::Update => write!(f, "update"),
+            FramePhase::Render => write!(f, "render"),
+        }
+    }
+}
+
+/// Durations of the phases of the most recent frame
+///
+/// Recording is just storing two `Duration`s, so it can stay enabled
+/// in release builds without measurable overhead.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimings {
+    /// Time spent updating the world
+    pub update: Duration,
+    /// Time spent rendering
+    pub render: Duration,
+}
+
+impl FrameTimings {
+    /// Total time spent in all phases
+    pub fn total(&self) -> Duration {
+        self.update + self.render
+    }
+
+    /// Finds the phase to blame for a long frame
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Frame time above which the frame counts as long
+    ///
+    /// # Returns
+    ///
+    /// * `Some((phase, duration))` - The slowest phase if the frame exceeded the threshold
+    /// * `None` - The frame was within the threshold
+    pub fn slow_phase(&self, threshold: Duration) -> Option<(FramePhase, Duration)> {
+        if self.total() <= threshold {
+            return None;
+        }
+
+        if self.update >= self.render {
+            Some((FramePhase::Update, self.update))
+        } else {
+            Some((FramePhase::Render, self.render))
+        }
+    }
+}
@@ -1,9 +1,22 @@
 //! Utility functions and helper types
 //!
-//! Collection of general-purpose utilities including vector math,
-//! FPS counting, and other helper functions used throughout the
-//! game. Provides common functionality shared across modules.
+//! Collection of general-purpose utilities including vector math, angle interpolation, bounding boxes,
+//! FPS counting, seedable randomness, pixel blending and other helper functions used
+//! throughout the game. Provides common functionality shared across
+//! modules.
 
+mod aabb;
+mod angle;
+mod color;
 mod fps;
+mod frame_timing;
+mod physics_clock;
+mod rng;
 
+pub use aabb::Aabb;
+pub use angle::{angle_difference, lerp_angle};
+pub use color::{blend_pixel, blend_pixel_at, put_pixel};
 pub use fps::FpsCounter;
+pub use frame_timing::{FramePhase, FrameTimings};
+pub use physics_clock::PhysicsClock;
+pub use rng::Rng;
@@ -0,0 +1,131 @@
+//! Fixed-timestep accumulator for deterministic physics
+
+use crate::consts::{FIXED_DT, MAX_LAG_TIME};
+
+/// Turns variable frame times into a whole number of fixed physics steps
+///
+/// PhysicsClock provides:
+/// * Accumulation of real elapsed time
+/// * Fixed-size steps with the remainder carried to the next frame
+/// * A lag cap so a long stall can't trigger an ever-growing catch-up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsClock {
+    /// Length of a single physics step in seconds
+    step: f32,
+    /// Maximum time that can be owed to the simulation in seconds
+    max_lag: f32,
+    /// Real time not yet simulated in seconds
+    accumulator: f32,
+}
+
+impl Default for PhysicsClock {
+    /// Creates a clock stepping `FIXED_DT`, capped at `MAX_LAG_TIME`
+    fn default() -> Self {
+        Self::new(FIXED_DT, MAX_LAG_TIME)
+    }
+}
+
+impl PhysicsClock {
+    /// Creates a clock with a custom step and lag cap
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Length of a single physics step in seconds
+    /// * `max_lag` - Maximum time that can be owed to the simulation
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive
+    pub fn new(step: f32, max_lag: f32) -> Self {
+        assert!(step > 0.0, "Physics step must be positive, got {}", step);
+
+        Self {
+            step,
+            max_lag: max_lag.max(step),
+            accumulator: 0.0,
+        }
+    }
+
+    /// Returns the length of a single physics step in seconds
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Returns the real time not yet simulated in seconds
+    pub fn leftover(&self) -> f32 {
+        self.accumulator
+    }
+
+    /// Drops any time not yet simulated, e.g. after unpausing
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+
+    /// Adds real elapsed time and returns the fixed steps to simulate
+    ///
+    /// # Arguments
+    ///
+    /// * `real_dt` - Real time since the previous tick in seconds
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding the step length once per physics step
+    pub fn tick(&mut self, real_dt: f32) -> impl Iterator<Item = f32> {
+        self.accumulator = (self.accumulator + real_dt.max(0.0)).min(self.max_lag);
+
+        // Tolerate float error so e.g. 0.05s at 1/120 gives 6 steps, not 5
+        let steps = ((self.accumulator + self.step * 1e-4) / self.step).floor();
+        self.accumulator = (self.accumulator - steps * self.step).max(0.0);
+
+        std::iter::repeat_n(self.step, steps as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carries_the_remainder_over() {
+        let mut clock = PhysicsClock::new(0.01, 1.0);
+        assert_eq!(clock.tick(0.025).count(), 2);
+        assert!((clock.leftover() - 0.005).abs() < 1e-6);
+        assert_eq!(clock.tick(0.005).count(), 1);
+        assert!(clock.leftover() < 1e-6);
+    }
+
+    #[test]
+    fn tolerates_float_error() {
+        let mut clock = PhysicsClock::new(1.0 / 120.0, 1.0);
+        assert_eq!(clock.tick(0.05).count(), 6);
+    }
+
+    #[test]
+    fn yields_the_step_length() {
+        let mut clock = PhysicsClock::default();
+        assert!(clock.tick(FIXED_DT * 3.0).all(|dt| dt == FIXED_DT));
+    }
+
+    #[test]
+    fn caps_the_lag() {
+        let mut clock = PhysicsClock::new(0.01, 0.1);
+        assert_eq!(clock.tick(10.0).count(), 10);
+        assert!(clock.leftover() < 1e-6);
+    }
+
+    #[test]
+    fn ignores_negative_time_and_resets() {
+        let mut clock = PhysicsClock::new(0.01, 1.0);
+        assert_eq!(clock.tick(-1.0).count(), 0);
+        clock.tick(0.005).count();
+        clock.reset();
+        assert_eq!(clock.leftover(), 0.0);
+        assert_eq!(clock.tick(0.005).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_non_positive_steps() {
+        PhysicsClock::new(0.0, 1.0);
+    }
+}
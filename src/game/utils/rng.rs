@@ -0,0 +1,110 @@
+//! Small seedable random number generator
+
+/// Deterministic xorshift64* pseudo random number generator
+///
+/// The same seed always yields the same sequence on every platform and
+/// crate version, which replays and headless simulations rely on. Not
+/// suitable for anything security related.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rng {
+    /// Current generator state, never 0
+    state: u64,
+}
+
+impl Default for Rng {
+    /// Creates a generator seeded with 0
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Rng {
+    /// Creates a generator
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Any value, nearby seeds still give unrelated sequences
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed with splitmix64 so small seeds don't start
+        // with a run of tiny numbers, xorshift also gets stuck on 0
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self {
+            state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z },
+        }
+    }
+
+    /// Returns the next 32 random bits
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 32) as u32
+    }
+
+    /// Returns a random value in 0.0..1.0
+    pub fn next_f32(&mut self) -> f32 {
+        // 24 bits fill the mantissa exactly, so 1.0 is never reached
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns a random value in `min..max`
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Smallest value that can be returned
+    /// * `max` - Upper bound, `min` is returned if it is not above `min`
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        if max <= min {
+            return min;
+        }
+        let value = min + (max - min) * self.next_f32();
+        // Rounding can land exactly on `max` for very close bounds
+        if value < max {
+            value
+        } else {
+            min
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+        assert_ne!(Rng::new(1).next_u32(), Rng::new(2).next_u32());
+    }
+
+    #[test]
+    fn zero_seed_doesnt_get_stuck() {
+        let mut rng = Rng::new(0);
+        let first = rng.next_u32();
+        assert!((0..10).any(|_| rng.next_u32() != first));
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..10_000 {
+            let value = rng.next_f32();
+            assert!((0.0..1.0).contains(&value));
+            let value = rng.range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&value));
+        }
+        assert_eq!(rng.range(5.0, 5.0), 5.0);
+        assert_eq!(rng.range(5.0, 1.0), 5.0);
+    }
+}
@@ -2,7 +2,8 @@
 
 use super::super::rendering::Renderable;
 use super::World;
-use crate::consts::CAR_FILE;
+use crate::consts::{CAR_FILE, MAX_LAG_TIME};
+use crate::game::utils::Aabb;
 use glam::Vec2;
 use std::cmp::PartialEq;
 
@@ -13,33 +14,110 @@ use std::cmp::PartialEq;
 ///
 /// * Position and velocity tracking
 /// * Forward/reverse/breaking acceleration with quadratic air resistance
-/// * Speed-dependent turning radius
+/// * Speed-dependent turning, no turning in place
+/// * Lateral tire grip, loosened by the handbrake to drift
+/// * Drift scoring while sliding at speed
 /// * Viscous friction at low speeds
 /// * Maximum speed limiting
+/// * External forces with a per-step acceleration clamp
 ///
 /// All physics calculations are frame-rate independent through delta time scaling.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Car {
     /// Current position in world space (read-only)
     position: Vec2,
+    /// Position before the last physics update, for interpolated drawing
+    previous_position: Vec2,
     /// Normalized vector pointing in car's forward direction
     forward: Vec2,
     /// Current velocity vector in units per second
     velocity: Vec2,
     /// Rate of acceleration in units/s²
     acceleration: f32,
+    /// Rate of deceleration while braking in units/s²
+    brake_strength: f32,
     /// Maximum turning rate in radians/s
     turn_speed: f32,
+    /// Forward speed in units/s below which steering does nothing
+    min_speed_to_turn: f32,
     /// Maximum speed in units/s
     max_speed: f32,
     /// Quadratic drag coefficient
     drag: f32,
     /// Linear friction coefficient for low speeds
     friction: f32,
+    /// Linear friction coefficient of the surface under the car
+    surface_friction: f32,
+    /// Decay rate of sideways velocity in 1/s
+    lateral_grip: f32,
+    /// Decay rate of sideways velocity while the handbrake is held in 1/s
+    handbrake_grip: f32,
+    /// External forces accumulated since the last update
+    external_force: Vec2,
+    /// Maximum magnitude of the net acceleration in units/s²
+    max_accel: f32,
+    /// Radius of the car's collision circle
+    collision_radius: f32,
     /// Current rotation in radians (counterclockwise from vertical)
     angle: f32,
+    /// Index of the player controlling this car
+    player_index: usize,
+    /// Factor on max speed and acceleration while boosted
+    boost_multiplier: f32,
+    /// Seconds of boost left
+    boost_time: f32,
+    /// Distance slid in the current drift in world units
+    drift_score: f32,
+    /// Whether the car slid at speed in the last update
+    drifting: bool,
+    /// Throttle or brake force applied in the last update in units/s²
+    last_acceleration_force: Vec2,
+    /// Drag and friction force applied in the last update in units/s²
+    last_drag_force: Vec2,
 }
 
+/// Snapshot of a car's physics state for debugging and tuning
+///
+/// Forces are the ones applied in the most recent [`Car::update`], in
+/// units/s² like the rest of the physics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CarTelemetry {
+    /// Position in world space
+    pub position: Vec2,
+    /// Velocity in units/s
+    pub velocity: Vec2,
+    /// Length of the velocity in units/s
+    pub speed: f32,
+    /// Rotation in radians (counterclockwise from vertical)
+    pub angle: f32,
+    /// Normalized heading
+    pub forward: Vec2,
+    /// Throttle or brake force, zero while coasting
+    pub acceleration_force: Vec2,
+    /// Air drag plus low-speed and surface friction
+    pub drag_force: Vec2,
+}
+
+/// Sprite tint per player index, players without an entry are drawn untinted
+const PLAYER_TINTS: [[u8; 4]; 2] = [[255, 90, 90, 255], [90, 90, 255, 255]];
+
+/// Forward speed in units/s at which the car turns at its full turn speed
+const FULL_TURN_SPEED: f32 = 60.0;
+
+/// Side length of the square car sprite in world units
+const CAR_SIZE: f32 = 60.0;
+
+/// Smallest max speed, acceleration and turn speed the setters allow
+const MIN_TUNING: f32 = 0.01;
+
+/// Slip angle in radians above which the car counts as drifting (~15°)
+const DRIFT_MIN_SLIP_ANGLE: f32 = 0.26;
+
+/// Speed in units/s below which sliding doesn't count as drifting
+const DRIFT_MIN_SPEED: f32 = 60.0;
+
 impl Car {
     /// Creates a new car at the specified position with default physics parameters
     ///
@@ -52,25 +130,108 @@ impl Car {
     ///
     /// A new Car instance with:
     /// * Acceleration: 400.0 units/s²
+    /// * Brake strength: 400.0 units/s²
     /// * Turn speed: 2.0 rad/s (8.0 now but needs to be tuned)
     /// * Max speed: 200.0 units/s
     /// * Drag coefficient: 0.001
     /// * Friction: 0.8
+    /// * Lateral grip: 8.0 /s, 1.0 /s with the handbrake held
+    /// * Max acceleration: 5000.0 units/s²
+    /// * Collision radius: 20.0 units
     /// * Initial angle: 0.0 rad (vertical)
+    /// * Player index: 0
     pub fn new(x: f32, y: f32) -> Self {
+        Self::new_with_angle(x, y, 0.0)
+    }
+
+    /// Creates a new car at the specified position facing the given angle
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Initial x-coordinate
+    /// * `y` - Initial y-coordinate
+    /// * `angle` - Initial rotation in radians (counterclockwise from vertical)
+    ///
+    /// # Returns
+    ///
+    /// A new Car instance with the same physics parameters as [`Car::new`]
+    /// and its forward vector pointing along `angle`.
+    pub fn new_with_angle(x: f32, y: f32, angle: f32) -> Self {
         Self {
             position: Vec2::new(x, y),
-            forward: Vec2::new(0.0, 1.0),
+            previous_position: Vec2::new(x, y),
+            forward: Self::forward_from_angle(angle),
             velocity: Vec2::ZERO,
             acceleration: 400.0,
-            turn_speed: 8.0, // when we stand still we rotate extremely fast bug == feature???
+            brake_strength: 400.0,
+            turn_speed: 8.0,
+            min_speed_to_turn: 2.0,
             max_speed: 200.0,
             drag: 0.005,
             friction: 0.95,
-            angle: 0.0,
+            surface_friction: 0.0,
+            lateral_grip: 8.0,
+            handbrake_grip: 1.0,
+            external_force: Vec2::ZERO,
+            max_accel: 5000.0,
+            collision_radius: 20.0,
+            angle,
+            player_index: 0,
+            boost_multiplier: 1.0,
+            boost_time: 0.0,
+            drift_score: 0.0,
+            drifting: false,
+            last_acceleration_force: Vec2::ZERO,
+            last_drag_force: Vec2::ZERO,
         }
     }
 
+    /// Puts the car back at a position facing vertically, at rest
+    ///
+    /// Physics parameters (acceleration, drag, ...) are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - New x-coordinate
+    /// * `y` - New y-coordinate
+    pub fn reset(&mut self, x: f32, y: f32) {
+        self.reset_with_angle(x, y, 0.0);
+    }
+
+    /// Puts the car back at a position facing the given angle, at rest
+    ///
+    /// Physics parameters (acceleration, drag, ...) are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - New x-coordinate
+    /// * `y` - New y-coordinate
+    /// * `angle` - New rotation in radians (counterclockwise from vertical)
+    pub fn reset_with_angle(&mut self, x: f32, y: f32, angle: f32) {
+        self.position = Vec2::new(x, y);
+        self.previous_position = self.position;
+        self.forward = Self::forward_from_angle(angle);
+        self.angle = angle;
+        self.velocity = Vec2::ZERO;
+        self.external_force = Vec2::ZERO;
+        self.surface_friction = 0.0;
+        self.boost_multiplier = 1.0;
+        self.boost_time = 0.0;
+        self.drift_score = 0.0;
+        self.drifting = false;
+        self.last_acceleration_force = Vec2::ZERO;
+        self.last_drag_force = Vec2::ZERO;
+    }
+
+    /// Computes the normalized forward vector for a rotation angle
+    ///
+    /// Falls back to facing vertically if the angle is not finite.
+    fn forward_from_angle(angle: f32) -> Vec2 {
+        Vec2::new(-angle.sin(), angle.cos())
+            .try_normalize()
+            .unwrap_or(Vec2::Y)
+    }
+
     /// Updates the car's physics state based on input controls
     ///
     /// # Arguments
@@ -79,48 +240,165 @@ impl Car {
     /// * `throttle` - Forward/reverse control (-1.0 to 1.0)
     /// * `brake` - Braking force (0.0 to 1.0)
     /// * `steering` - Left/right control (-1.0 to 1.0)
-    pub fn update(&mut self, dt: f32, throttle: f32, brake: f32, steering: f32) {
-        // Update rotation with speed-dependent turning
-        if steering != 0.0 {
-            let speed_factor = 1.0 - (self.speed() / self.max_speed).min(0.8);
-            self.angle += steering * self.turn_speed * speed_factor * dt;
+    /// * `handbrake` - Whether the handbrake is held
+    ///
+    /// While a boost is active max speed and acceleration are scaled by
+    /// its multiplier, and the boost timer counts down by `dt`.
+    ///
+    /// Sideways velocity decays by the lateral grip every update. The
+    /// handbrake swaps in the much weaker handbrake grip, so the car
+    /// keeps its sideways momentum and slides.
+    ///
+    /// While the slip angle and speed are both high the distance driven
+    /// is added to the drift score, which resets once the car grips again.
+    ///
+    /// `dt` is clamped to `MAX_LAG_TIME` so a long stall can't teleport
+    /// the car. If the state still ends up NaN or infinite (e.g. from a
+    /// NaN input) the car stops where it was before the update, or at
+    /// the origin if that was broken too.
+    pub fn update(&mut self, dt: f32, throttle: f32, brake: f32, steering: f32, handbrake: bool) {
+        let dt = if dt.is_finite() {
+            dt.clamp(0.0, MAX_LAG_TIME)
+        } else {
+            0.0
+        };
+        let (previous_position, previous_angle) = (self.position, self.angle);
+        self.previous_position = previous_position;
+
+        let max_speed = self.max_speed * self.boost_multiplier;
+        let acceleration = self.acceleration * self.boost_multiplier;
+
+        // Turn like a car on wheels: not at all while standing, faster as the
+        // car picks up speed, tighter again near top speed, and mirrored in reverse
+        let forward_speed = self.velocity.dot(self.forward);
+        if steering != 0.0 && forward_speed.abs() >= self.min_speed_to_turn {
+            let roll_factor = (forward_speed / FULL_TURN_SPEED).clamp(-1.0, 1.0);
+            let speed_factor = 1.0 - (self.speed() / max_speed).min(0.8);
+            self.angle += steering * self.turn_speed * roll_factor * speed_factor * dt;
 
             // Recalculate and normalize forward vector
-            self.forward = Vec2::new(-self.angle.sin(), self.angle.cos());
-            self.forward = self.forward.normalize();
+            self.forward = Self::forward_from_angle(self.angle);
         }
 
         // Apply acceleration force
-        let mut accel_force = if throttle != 0.0 {
-            self.forward * (self.acceleration * throttle)
+        let engine_force = if throttle != 0.0 {
+            self.forward * (acceleration * throttle)
         } else if brake > 0.0 && self.velocity.length() > 0.1 {
-            // Apply brake force against current velocity direction
-            -self.velocity.normalize() * (self.acceleration * brake)
+            // Apply brake force against current velocity direction, at most
+            // enough to stop the car instead of pushing it backwards
+            let stopping = self.velocity.length() / dt;
+            -self.velocity.normalize() * (self.brake_strength * brake).min(stopping)
         } else {
             Vec2::ZERO
         };
 
         // Apply quadratic drag at higher speeds
         let speed = self.velocity.length();
-        if speed > 1.0 {
-            let drag_force = -self.velocity.normalize() * (self.drag * speed * speed);
-            accel_force = accel_force + drag_force;
+        let mut drag_force = if speed > 1.0 {
+            -self.velocity.normalize() * (self.drag * speed * speed)
         } else {
             // Apply linear friction at low speeds
-            accel_force = accel_force - self.velocity * self.friction;
-        }
+            -self.velocity * self.friction
+        };
+
+        // Apply surface friction from the ground under the car
+        drag_force -= self.velocity * self.surface_friction;
+
+        self.last_acceleration_force = engine_force;
+        self.last_drag_force = drag_force;
+        let mut accel_force = engine_force + drag_force;
+
+        // Apply external forces and keep the net acceleration stable
+        accel_force += self.external_force;
+        self.external_force = Vec2::ZERO;
+        accel_force = accel_force.clamp_length_max(self.max_accel);
 
         // Update velocity with forces
-        self.velocity = self.velocity + accel_force * dt;
+        self.velocity += accel_force * dt;
+
+        // Bleed off sideways velocity, slowly while the handbrake is held
+        let grip = if handbrake {
+            self.handbrake_grip
+        } else {
+            self.lateral_grip
+        };
+        let forward_speed = self.velocity.dot(self.forward);
+        let lateral = self.velocity - self.forward * forward_speed;
+        self.velocity = self.forward * forward_speed + lateral * (-grip * dt).exp();
 
         // Apply speed limit
         let speed = self.velocity.length();
-        if speed > self.max_speed {
-            self.velocity = self.velocity.normalize() * self.max_speed;
+        if speed > max_speed {
+            self.velocity = self.velocity.normalize() * max_speed;
         }
 
         // Update position
-        self.position = self.position + self.velocity * dt;
+        self.position += self.velocity * dt;
+
+        // Recover from broken state instead of staying NaN forever
+        if !self.velocity.is_finite() || !self.position.is_finite() {
+            self.velocity = Vec2::ZERO;
+            self.position = if previous_position.is_finite() {
+                previous_position
+            } else {
+                Vec2::ZERO
+            };
+        }
+        if !self.angle.is_finite() {
+            self.angle = if previous_angle.is_finite() {
+                previous_angle
+            } else {
+                0.0
+            };
+            self.forward = Self::forward_from_angle(self.angle);
+        }
+
+        // Score the slide, reversing doesn't count as a drift
+        let slip = self.slip_angle().abs();
+        self.drifting = slip > DRIFT_MIN_SLIP_ANGLE
+            && slip < std::f32::consts::FRAC_PI_2
+            && self.speed() >= DRIFT_MIN_SPEED;
+        if self.drifting {
+            self.drift_score += self.speed() * dt;
+        } else {
+            self.drift_score = 0.0;
+        }
+
+        // Count the boost down and drop it once it runs out
+        if self.boost_time > 0.0 {
+            self.boost_time = (self.boost_time - dt).max(0.0);
+            if self.boost_time == 0.0 {
+                self.boost_multiplier = 1.0;
+            }
+        }
+    }
+
+    /// Temporarily scales the car's max speed and acceleration
+    ///
+    /// Replaces any boost that is still active.
+    ///
+    /// # Arguments
+    ///
+    /// * `multiplier` - Factor on max speed and acceleration, clamped to ≥ 0
+    /// * `seconds` - How long the boost lasts, ≤ 0 cancels any boost
+    pub fn apply_boost(&mut self, multiplier: f32, seconds: f32) {
+        if seconds > 0.0 {
+            self.boost_multiplier = multiplier.max(0.0);
+            self.boost_time = seconds;
+        } else {
+            self.boost_multiplier = 1.0;
+            self.boost_time = 0.0;
+        }
+    }
+
+    /// Returns the seconds of boost left, 0.0 when not boosted
+    pub fn boost_time(&self) -> f32 {
+        self.boost_time
+    }
+
+    /// Returns whether a boost is active
+    pub fn is_boosted(&self) -> bool {
+        self.boost_time > 0.0
     }
 
     /// Returns the current position
@@ -133,15 +411,278 @@ impl Car {
         self.forward
     }
 
+    /// Returns the current velocity in units per second
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// Moves the car, used by collision resolution
+    pub(crate) fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+
+    /// Returns the position before the last physics update
+    pub fn previous_position(&self) -> Vec2 {
+        self.previous_position
+    }
+
+    /// Blends between the previous and current position
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - 0.0 for the previous position up to 1.0 for the
+    ///   current one, clamped to that range
+    pub fn interpolated_position(&self, alpha: f32) -> Vec2 {
+        let alpha = if alpha.is_nan() {
+            1.0
+        } else {
+            alpha.clamp(0.0, 1.0)
+        };
+        self.previous_position.lerp(self.position, alpha)
+    }
+
+    /// Overrides the velocity, used by collision resolution
+    pub(crate) fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity = velocity;
+    }
+
+    /// Returns the forward speed in units/s below which steering does nothing
+    pub fn min_speed_to_turn(&self) -> f32 {
+        self.min_speed_to_turn
+    }
+
+    /// Sets the forward speed in units/s below which steering does nothing
+    ///
+    /// Negative values are clamped to 0.0.
+    pub fn set_min_speed_to_turn(&mut self, min_speed_to_turn: f32) {
+        self.min_speed_to_turn = min_speed_to_turn.max(0.0);
+    }
+
+    /// Returns the top speed without boosts in units/s
+    pub fn max_speed(&self) -> f32 {
+        self.max_speed
+    }
+
+    /// Sets the top speed without boosts in units/s
+    ///
+    /// Clamped to a small positive value so speed ratios stay finite.
+    pub fn set_max_speed(&mut self, max_speed: f32) {
+        self.max_speed = max_speed.max(MIN_TUNING);
+    }
+
+    /// Returns the maximum turning rate in radians/s
+    pub fn turn_speed(&self) -> f32 {
+        self.turn_speed
+    }
+
+    /// Sets the maximum turning rate in radians/s
+    ///
+    /// Clamped to a small positive value, the car always steers a little.
+    pub fn set_turn_speed(&mut self, turn_speed: f32) {
+        self.turn_speed = turn_speed.max(MIN_TUNING);
+    }
+
+    /// Returns the radius of the car's collision circle
+    pub fn collision_radius(&self) -> f32 {
+        self.collision_radius
+    }
+
+    /// Sets the radius of the car's collision circle
+    ///
+    /// Negative values are clamped to 0.0 (no collisions).
+    pub fn set_collision_radius(&mut self, collision_radius: f32) {
+        self.collision_radius = collision_radius.max(0.0);
+    }
+
+    /// Returns the axis-aligned box around the car's rotated sprite
+    ///
+    /// The sprite is a `CAR_SIZE` square turned by the car's angle, so
+    /// the box grows up to √2 times wider when the car drives diagonally.
+    pub fn bounding_box(&self) -> Aabb {
+        let (sin, cos) = self.angle().sin_cos();
+        let half_size = CAR_SIZE / 2.0 * (sin.abs() + cos.abs());
+        Aabb::from_center(self.position, Vec2::splat(half_size))
+    }
+
+    /// Returns the index of the player controlling this car
+    pub fn player_index(&self) -> usize {
+        self.player_index
+    }
+
+    /// Sets the index of the player controlling this car
+    pub fn set_player_index(&mut self, player_index: usize) {
+        self.player_index = player_index;
+    }
+
     /// Returns the current speed in units per second
     pub fn speed(&self) -> f32 {
         self.velocity.length()
     }
 
+    /// Returns how well the velocity lines up with the heading
+    ///
+    /// # Returns
+    ///
+    /// * `1.0` - Driving straight ahead (or standing still)
+    /// * `0.0` - Sliding fully sideways
+    /// * `-1.0` - Reversing
+    pub fn forward_alignment(&self) -> f32 {
+        match self.velocity.try_normalize() {
+            Some(direction) => self.forward.dot(direction),
+            None => 1.0,
+        }
+    }
+
+    /// Returns the signed angle from the heading to the velocity in radians
+    ///
+    /// Useful for drift detection, 0.0 while standing still.
+    pub fn slip_angle(&self) -> f32 {
+        if self.velocity == Vec2::ZERO {
+            return 0.0;
+        }
+
+        self.forward.angle_to(self.velocity)
+    }
+
+    /// Returns a snapshot of the car's physics state, see [`CarTelemetry`]
+    pub fn telemetry(&self) -> CarTelemetry {
+        CarTelemetry {
+            position: self.position,
+            velocity: self.velocity,
+            speed: self.speed(),
+            angle: self.angle,
+            forward: self.forward,
+            acceleration_force: self.last_acceleration_force,
+            drag_force: self.last_drag_force,
+        }
+    }
+
+    /// Returns whether the car slid sideways at speed in the last update
+    pub fn is_drifting(&self) -> bool {
+        self.drifting
+    }
+
+    /// Returns the distance slid in the current drift in world units
+    ///
+    /// Grows while [`Car::is_drifting`] and drops back to 0.0 as soon as
+    /// the tires grip again.
+    pub fn drift_score(&self) -> f32 {
+        self.drift_score
+    }
+
     /// Returns the current rotation angle in radians
     pub fn angle(&self) -> f32 {
         self.angle
     }
+
+    /// Returns the rate of acceleration in units/s²
+    pub fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    /// Sets the rate of acceleration in units/s²
+    ///
+    /// Clamped to a small positive value so the car can always drive off.
+    pub fn set_acceleration(&mut self, acceleration: f32) {
+        self.acceleration = acceleration.max(MIN_TUNING);
+    }
+
+    /// Returns the rate of deceleration while braking in units/s²
+    pub fn brake_strength(&self) -> f32 {
+        self.brake_strength
+    }
+
+    /// Sets the rate of deceleration while braking in units/s²
+    ///
+    /// Negative values are clamped to 0.0.
+    pub fn set_brake_strength(&mut self, brake_strength: f32) {
+        self.brake_strength = brake_strength.max(0.0);
+    }
+
+    /// Adds an external force for the next update
+    ///
+    /// Forces accumulate until the next `update`, where they are added to
+    /// the car's own acceleration and then cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `force` - Acceleration in units/s² (e.g. from a boost pad or force zone)
+    pub fn apply_force(&mut self, force: Vec2) {
+        self.external_force += force;
+    }
+
+    /// Instantly changes the velocity, e.g. for knockback or explosions
+    ///
+    /// Unlike [`Car::apply_force`] the change isn't scaled by time or
+    /// limited by `max_accel`. Only the max-speed clamp of the next
+    /// `update` applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `impulse` - Velocity change in units/s
+    pub fn apply_impulse(&mut self, impulse: Vec2) {
+        self.velocity += impulse;
+    }
+
+    /// Returns the maximum magnitude of the net acceleration in units/s²
+    pub fn max_accel(&self) -> f32 {
+        self.max_accel
+    }
+
+    /// Sets the maximum magnitude of the net acceleration in units/s²
+    ///
+    /// Negative values are clamped to 0.0.
+    pub fn set_max_accel(&mut self, max_accel: f32) {
+        self.max_accel = max_accel.max(0.0);
+    }
+
+    /// Returns the linear friction coefficient of the surface under the car
+    pub fn surface_friction(&self) -> f32 {
+        self.surface_friction
+    }
+
+    /// Sets the linear friction coefficient of the surface under the car
+    ///
+    /// Usually set by the world every update from the tile the car is on.
+    pub fn set_surface_friction(&mut self, surface_friction: f32) {
+        self.surface_friction = surface_friction;
+    }
+
+    /// Returns the decay rate of sideways velocity in 1/s
+    pub fn lateral_grip(&self) -> f32 {
+        self.lateral_grip
+    }
+
+    /// Sets the decay rate of sideways velocity in 1/s
+    ///
+    /// Negative values are clamped to 0.0 (no grip).
+    pub fn set_lateral_grip(&mut self, lateral_grip: f32) {
+        self.lateral_grip = lateral_grip.max(0.0);
+    }
+
+    /// Returns the decay rate of sideways velocity while the handbrake is held in 1/s
+    pub fn handbrake_grip(&self) -> f32 {
+        self.handbrake_grip
+    }
+
+    /// Sets the decay rate of sideways velocity while the handbrake is held in 1/s
+    ///
+    /// Negative values are clamped to 0.0 (no grip).
+    pub fn set_handbrake_grip(&mut self, handbrake_grip: f32) {
+        self.handbrake_grip = handbrake_grip.max(0.0);
+    }
+
+    /// Returns the quadratic drag coefficient
+    pub fn drag(&self) -> f32 {
+        self.drag
+    }
+
+    /// Sets the quadratic drag coefficient
+    ///
+    /// Negative values are clamped to 0.0.
+    pub fn set_drag(&mut self, drag: f32) {
+        self.drag = drag.max(0.0);
+    }
 }
 
 /// Input controls for car movement, with value range validation
@@ -150,11 +691,17 @@ impl Car {
 /// * `throttle`: -1.0 (full reverse) to 1.0 (full forward)
 /// * `turn`: -1.0 (full right) to 1.0 (full left)
 /// * `brake`: 0.0 to 1.0 (full brake)
-#[derive(Debug, Clone, Copy)]
+///
+/// The handbrake is a separate on/off control, released unless set with
+/// `with_handbrake`.
+///
+/// The default input releases all controls.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct CarInput {
     throttle: f32,
     turn: f32,
     brake: f32,
+    handbrake: bool,
 }
 
 impl CarInput {
@@ -180,9 +727,32 @@ impl CarInput {
             throttle,
             turn,
             brake,
+            handbrake: false,
         }
     }
 
+    /// Creates car control inputs, returning None if any is out of range
+    ///
+    /// Useful for inputs from untrusted sources like replay files.
+    pub fn try_new(throttle: f32, turn: f32, brake: f32) -> Option<Self> {
+        let valid = (-1.0..=1.0).contains(&throttle)
+            && (-1.0..=1.0).contains(&turn)
+            && (0.0..=1.0).contains(&brake);
+
+        valid.then_some(Self {
+            throttle,
+            turn,
+            brake,
+            handbrake: false,
+        })
+    }
+
+    /// Returns the input with the handbrake held or released
+    pub fn with_handbrake(mut self, handbrake: bool) -> Self {
+        self.handbrake = handbrake;
+        self
+    }
+
     /// Get the throttle input value
     pub fn throttle(&self) -> f32 {
         self.throttle
@@ -197,6 +767,11 @@ impl CarInput {
     pub fn brake(&self) -> f32 {
         self.brake
     }
+
+    /// Get whether the handbrake is held
+    pub fn handbrake(&self) -> bool {
+        self.handbrake
+    }
 }
 
 impl Renderable for Car {
@@ -204,11 +779,309 @@ impl Renderable for Car {
         self.position()
     }
 
+    fn interpolated_position(&self, alpha: f32) -> Vec2 {
+        self.interpolated_position(alpha)
+    }
+
+    fn rotation(&self) -> f32 {
+        self.angle()
+    }
+
     fn base_size(&self) -> f32 {
-        60.0 // Base car size
+        CAR_SIZE
     }
 
-    fn texture_file(&self, world: &World) -> &str {
+    fn texture_file(&self, _world: &World) -> &str {
         CAR_FILE
     }
+
+    fn casts_shadow(&self) -> bool {
+        true
+    }
+
+    fn tint(&self) -> [u8; 4] {
+        PLAYER_TINTS
+            .get(self.player_index)
+            .copied()
+            .unwrap_or([255; 4])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_with_angle_sets_forward() {
+        for angle in [0.0, 0.5, std::f32::consts::PI, -2.0] {
+            let car = Car::new_with_angle(0.0, 0.0, angle);
+            let expected = Vec2::new(-angle.sin(), angle.cos());
+            assert!(car.forward().distance(expected) < 1e-6);
+            assert!((car.forward().length() - 1.0).abs() < 1e-6);
+            assert_eq!(car.angle(), angle);
+        }
+        assert_eq!(Car::new(0.0, 0.0).forward(), Vec2::Y);
+    }
+
+    #[test]
+    fn reset_with_angle_restores_heading() {
+        let mut car = Car::new(0.0, 0.0);
+        car.update(0.1, 1.0, 0.0, 1.0, false);
+        car.reset_with_angle(5.0, 6.0, 1.0);
+        assert_eq!(car.position(), Vec2::new(5.0, 6.0));
+        assert_eq!(car.velocity(), Vec2::ZERO);
+        assert!(
+            car.forward()
+                .distance(Vec2::new(-1.0f32.sin(), 1.0f32.cos()))
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn nan_input_stops_the_car_in_place() {
+        let mut car = Car::new(10.0, 20.0);
+        car.update(0.1, 1.0, 0.0, 0.0, false);
+        let position = car.position();
+
+        car.update(0.1, f32::NAN, 0.0, 0.0, false);
+        assert_eq!(car.position(), position);
+        assert_eq!(car.velocity(), Vec2::ZERO);
+
+        car.update(0.1, 1.0, 0.0, f32::NAN, false);
+        assert!(car.angle().is_finite());
+        assert!(car.position().is_finite());
+    }
+
+    #[test]
+    fn broken_state_recovers_at_the_origin() {
+        let mut car = Car::new_with_angle(f32::NAN, 0.0, f32::INFINITY);
+        assert!(car.forward().is_finite());
+        car.update(0.1, 0.0, 0.0, 0.0, false);
+        assert_eq!(car.position(), Vec2::ZERO);
+        assert_eq!(car.angle(), 0.0);
+    }
+
+    #[test]
+    fn dt_is_clamped() {
+        let mut stalled = Car::new(0.0, 0.0);
+        stalled.apply_impulse(Vec2::new(0.0, 100.0));
+        stalled.update(60.0, 0.0, 0.0, 0.0, false);
+        assert!(stalled.position().y <= 100.0 * MAX_LAG_TIME + 1e-3);
+
+        let mut car = Car::new(0.0, 0.0);
+        car.apply_impulse(Vec2::new(0.0, 100.0));
+        for dt in [-1.0, f32::NAN, f32::INFINITY] {
+            car.update(dt, 1.0, 0.0, 0.0, false);
+        }
+        assert_eq!(car.position(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn speed_is_limited() {
+        let mut car = Car::new(0.0, 0.0);
+        car.apply_impulse(Vec2::new(0.0, 10_000.0));
+        car.update(0.01, 1.0, 0.0, 0.0, false);
+        assert!(car.speed() <= car.max_speed() + 1e-3);
+
+        car.apply_boost(1.5, 10.0);
+        for _ in 0..200 {
+            car.update(0.01, 1.0, 0.0, 0.0, false);
+        }
+        assert!(car.speed() > car.max_speed());
+        assert!(car.speed() <= car.max_speed() * 1.5 + 1e-3);
+    }
+
+    #[test]
+    fn setters_clamp_tuning() {
+        let mut car = Car::new(0.0, 0.0);
+        car.set_max_speed(-5.0);
+        car.set_turn_speed(0.0);
+        car.set_acceleration(-1.0);
+        assert_eq!(car.max_speed(), MIN_TUNING);
+        assert_eq!(car.turn_speed(), MIN_TUNING);
+        assert_eq!(car.acceleration(), MIN_TUNING);
+
+        car.set_brake_strength(-1.0);
+        car.set_max_accel(-1.0);
+        car.set_lateral_grip(-1.0);
+        car.set_drag(-1.0);
+        car.set_collision_radius(-1.0);
+        car.set_min_speed_to_turn(-1.0);
+        assert_eq!(car.brake_strength(), 0.0);
+        assert_eq!(car.max_accel(), 0.0);
+        assert_eq!(car.lateral_grip(), 0.0);
+        assert_eq!(car.drag(), 0.0);
+        assert_eq!(car.collision_radius(), 0.0);
+        assert_eq!(car.min_speed_to_turn(), 0.0);
+    }
+
+    #[test]
+    fn standing_car_doesnt_turn() {
+        let mut car = Car::new(0.0, 0.0);
+        car.update(0.1, 0.0, 0.0, 1.0, false);
+        assert_eq!(car.angle(), 0.0);
+
+        for _ in 0..30 {
+            car.update(0.01, 1.0, 0.0, 1.0, false);
+        }
+        assert!(car.angle() > 0.0);
+    }
+
+    #[test]
+    fn car_input_validates_ranges() {
+        assert!(CarInput::try_new(1.0, -1.0, 0.0).is_some());
+        assert!(CarInput::try_new(1.1, 0.0, 0.0).is_none());
+        assert!(CarInput::try_new(0.0, 0.0, -0.1).is_none());
+        assert!(CarInput::try_new(f32::NAN, 0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn brakes_decelerate_and_stop_the_car() {
+        // Distance rolled while braking from 100 units/s
+        let stopping_distance = |brake_strength: f32| {
+            let mut car = Car::new(0.0, 0.0);
+            car.set_brake_strength(brake_strength);
+            car.apply_impulse(Vec2::new(0.0, 100.0));
+
+            car.update(0.01, 0.0, 1.0, 0.0, false);
+            assert!(car.speed() <= 100.0 - brake_strength * 0.01);
+            for _ in 0..100 {
+                car.update(0.01, 0.0, 1.0, 0.0, false);
+            }
+            assert!(car.speed() < 0.1);
+            car.position().y
+        };
+
+        // Never farther than without drag: v² / 2a
+        let soft = stopping_distance(400.0);
+        assert!(soft > 0.0 && soft <= 100.0 * 100.0 / (2.0 * 400.0));
+        assert!(stopping_distance(800.0) < soft);
+    }
+
+    #[test]
+    fn acceleration_per_step_is_capped() {
+        let mut car = Car::new(0.0, 0.0);
+        car.set_max_accel(100.0);
+        car.apply_force(Vec2::new(0.0, 10_000.0));
+        car.update(0.01, 1.0, 0.0, 0.0, false);
+        assert!((car.speed() - 1.0).abs() < 1e-4);
+
+        // The force was used up by the update
+        car.update(0.01, 0.0, 0.0, 0.0, false);
+        assert!(car.speed() < 1.0);
+    }
+
+    #[test]
+    fn handbrake_lowers_lateral_grip() {
+        // Sideways velocity left after sliding for a tenth of a second
+        let lateral_after = |handbrake: bool| {
+            let mut car = Car::new(0.0, 0.0);
+            car.apply_impulse(Vec2::new(100.0, 0.0));
+            car.update(0.1, 0.0, 0.0, 0.0, handbrake);
+            car.velocity().x
+        };
+
+        let gripping = lateral_after(false);
+        let sliding = lateral_after(true);
+        assert!(gripping > 0.0);
+        assert!(sliding > gripping * 1.5);
+    }
+
+    #[test]
+    fn impulses_change_velocity_instantly() {
+        let mut car = Car::new(0.0, 0.0);
+        car.set_max_accel(0.0);
+        car.apply_impulse(Vec2::new(100.0, 0.0));
+        assert_eq!(car.velocity(), Vec2::new(100.0, 0.0));
+        assert_eq!(car.speed(), 100.0);
+
+        // Only the max speed of the next update limits them
+        car.apply_impulse(Vec2::new(1000.0, 0.0));
+        car.update(0.0, 0.0, 0.0, 0.0, false);
+        assert!((car.speed() - car.max_speed()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn only_sliding_scores_drift_points() {
+        let mut gripping = Car::new(0.0, 0.0);
+        gripping.apply_impulse(Vec2::new(0.0, 150.0));
+        gripping.update(0.01, 1.0, 0.0, 0.0, false);
+        assert!(!gripping.is_drifting());
+        assert_eq!(gripping.drift_score(), 0.0);
+
+        let mut drifting = Car::new(0.0, 0.0);
+        drifting.apply_impulse(Vec2::new(100.0, 150.0));
+        for _ in 0..10 {
+            drifting.update(0.01, 1.0, 0.0, 0.0, true);
+        }
+        assert!(drifting.is_drifting());
+        assert!(drifting.drift_score() > 0.0);
+
+        // Gripping again ends the drift
+        for _ in 0..100 {
+            drifting.update(0.01, 1.0, 0.0, 0.0, false);
+        }
+        assert!(!drifting.is_drifting());
+        assert_eq!(drifting.drift_score(), 0.0);
+    }
+
+    #[test]
+    fn telemetry_reports_the_last_update() {
+        let mut car = Car::new(0.0, 0.0);
+        car.update(0.01, 1.0, 0.0, 0.0, false);
+        let telemetry = car.telemetry();
+        assert_eq!(telemetry.position, car.position());
+        assert_eq!(telemetry.velocity, car.velocity());
+        assert_eq!(telemetry.speed, car.speed());
+        assert_eq!(telemetry.angle, car.angle());
+        assert_eq!(telemetry.forward, car.forward());
+        assert_eq!(
+            telemetry.acceleration_force,
+            car.forward() * car.acceleration()
+        );
+        assert_eq!(telemetry.drag_force, Vec2::ZERO);
+
+        // Coasting only slows the car down
+        car.update(0.01, 0.0, 0.0, 0.0, false);
+        let telemetry = car.telemetry();
+        assert_eq!(telemetry.acceleration_force, Vec2::ZERO);
+        assert!(telemetry.drag_force.dot(car.velocity()) < 0.0);
+    }
+
+    #[test]
+    fn interpolated_position_blends_the_last_update() {
+        let mut car = Car::new(0.0, 0.0);
+        car.apply_impulse(Vec2::new(0.0, 100.0));
+        car.update(0.1, 0.0, 0.0, 0.0, false);
+        let (previous, current) = (car.previous_position(), car.position());
+        assert_eq!(previous, Vec2::ZERO);
+        assert!(current.y > 0.0);
+
+        assert_eq!(car.interpolated_position(0.0), previous);
+        assert_eq!(car.interpolated_position(0.5), (previous + current) / 2.0);
+        assert_eq!(car.interpolated_position(1.0), current);
+        assert_eq!(car.interpolated_position(2.0), current);
+        assert_eq!(car.interpolated_position(f32::NAN), current);
+    }
+
+    #[test]
+    fn alignment_and_slip_follow_the_velocity() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let mut car = Car::new(0.0, 0.0);
+        assert_eq!(car.forward_alignment(), 1.0);
+        assert_eq!(car.slip_angle(), 0.0);
+
+        car.apply_impulse(Vec2::new(0.0, 100.0));
+        assert!((car.forward_alignment() - 1.0).abs() < 1e-6);
+        assert!(car.slip_angle().abs() < 1e-6);
+
+        car.set_velocity(Vec2::new(100.0, 0.0));
+        assert!(car.forward_alignment().abs() < 1e-6);
+        assert!((car.slip_angle().abs() - FRAC_PI_2).abs() < 1e-5);
+
+        car.set_velocity(Vec2::new(0.0, -100.0));
+        assert!((car.forward_alignment() + 1.0).abs() < 1e-6);
+        assert!((car.slip_angle().abs() - PI).abs() < 1e-5);
+    }
 }
@@ -0,0 +1,193 @@
+//! Collision resolution between world entities
+
+use super::Car;
+use glam::Vec2;
+
+/// Fraction of the approach speed kept after two cars bounce
+pub const CAR_RESTITUTION: f32 = 0.8;
+
+/// Separates two overlapping cars and bounces them off each other
+///
+/// Treats both cars as circles of equal mass. Overlapping cars are
+/// pushed apart along the collision normal, and if they are moving
+/// towards each other an elastic impulse exchanges part of their
+/// velocity along that normal.
+///
+/// # Arguments
+///
+/// * `a` - First car
+/// * `b` - Second car
+/// * `restitution` - Bounciness, 0.0 (no bounce) to 1.0 (perfectly elastic)
+///
+/// # Returns
+///
+/// Whether the cars were overlapping
+pub fn resolve_car_collision(a: &mut Car, b: &mut Car, restitution: f32) -> bool {
+    let min_distance = a.collision_radius() + b.collision_radius();
+    let delta = b.position() - a.position();
+    let distance = delta.length();
+
+    if distance >= min_distance {
+        return false;
+    }
+
+    // Cars exactly on top of each other get pushed apart sideways
+    let normal = if distance > f32::EPSILON {
+        delta / distance
+    } else {
+        Vec2::X
+    };
+
+    // Push both cars out of each other by half the overlap
+    let correction = normal * ((min_distance - distance) / 2.0);
+    a.set_position(a.position() - correction);
+    b.set_position(b.position() + correction);
+
+    // Only bounce when the cars are approaching each other
+    let approach_speed = (b.velocity() - a.velocity()).dot(normal);
+    if approach_speed < 0.0 {
+        let impulse = normal * (-(1.0 + restitution) * approach_speed / 2.0);
+        a.apply_impulse(-impulse);
+        b.apply_impulse(impulse);
+    }
+
+    true
+}
+
+/// Pushes a car out of a solid circular obstacle
+///
+/// A car overlapping the obstacle is moved back to its edge and the
+/// velocity component pointing into the obstacle is zeroed, so the car
+/// stops at the obstacle but can still slide around it.
+///
+/// # Arguments
+///
+/// * `car` - Car to push out
+/// * `center` - Center of the obstacle
+/// * `radius` - Radius of the obstacle
+///
+/// # Returns
+///
+/// Whether the car hit the obstacle
+pub fn resolve_obstacle_collision(car: &mut Car, center: Vec2, radius: f32) -> bool {
+    let min_distance = car.collision_radius() + radius;
+    let delta = car.position() - center;
+    let distance = delta.length();
+
+    if distance >= min_distance {
+        return false;
+    }
+
+    // A car exactly on the center gets pushed out sideways
+    let normal = if distance > f32::EPSILON {
+        delta / distance
+    } else {
+        Vec2::X
+    };
+
+    car.set_position(center + normal * min_distance);
+
+    // Only stop the part of the velocity heading into the obstacle
+    let inbound = car.velocity().dot(normal);
+    if inbound < 0.0 {
+        car.set_velocity(car.velocity() - normal * inbound);
+    }
+
+    true
+}
+
+/// Keeps a car inside a rectangular playfield
+///
+/// A car outside the bounds is moved back onto the boundary and the
+/// velocity component pointing into the wall is zeroed, so the car
+/// stops at the wall but can still slide along it.
+///
+/// # Arguments
+///
+/// * `car` - Car to confine
+/// * `min` - Minimum corner of the playfield
+/// * `max` - Maximum corner of the playfield
+///
+/// # Returns
+///
+/// Whether the car hit a wall
+pub fn confine_car(car: &mut Car, min: Vec2, max: Vec2) -> bool {
+    let position = car.position();
+    let clamped = position.clamp(min, max);
+    if clamped == position {
+        return false;
+    }
+
+    let mut velocity = car.velocity();
+    if (position.x < min.x && velocity.x < 0.0) || (position.x > max.x && velocity.x > 0.0) {
+        velocity.x = 0.0;
+    }
+    if (position.y < min.y && velocity.y < 0.0) || (position.y > max.y && velocity.y > 0.0) {
+        velocity.y = 0.0;
+    }
+
+    car.set_position(clamped);
+    car.set_velocity(velocity);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_cars_are_pushed_apart_and_bounce() {
+        let mut a = Car::new(0.0, 0.0);
+        let mut b = Car::new(30.0, 0.0);
+        a.apply_impulse(Vec2::new(100.0, 0.0));
+
+        assert!(resolve_car_collision(&mut a, &mut b, 1.0));
+        let min_distance = a.collision_radius() + b.collision_radius();
+        assert!((a.position().distance(b.position()) - min_distance).abs() < 1e-3);
+        // A fully elastic hit swaps the velocities
+        assert!(a.velocity().length() < 1e-3);
+        assert!((b.velocity().x - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn separating_cars_dont_bounce() {
+        let mut a = Car::new(0.0, 0.0);
+        let mut b = Car::new(30.0, 0.0);
+        b.apply_impulse(Vec2::new(50.0, 0.0));
+        assert!(resolve_car_collision(&mut a, &mut b, 1.0));
+        assert_eq!(a.velocity(), Vec2::ZERO);
+        assert_eq!(b.velocity(), Vec2::new(50.0, 0.0));
+
+        let mut far = Car::new(100.0, 0.0);
+        assert!(!resolve_car_collision(&mut a, &mut far, 1.0));
+    }
+
+    #[test]
+    fn stacked_cars_are_pushed_sideways() {
+        let mut a = Car::new(5.0, 5.0);
+        let mut b = Car::new(5.0, 5.0);
+        assert!(resolve_car_collision(&mut a, &mut b, CAR_RESTITUTION));
+        assert!(a.position().x < b.position().x);
+        assert_eq!(a.position().y, b.position().y);
+    }
+
+    #[test]
+    fn obstacles_stop_inbound_velocity_only() {
+        let mut car = Car::new(-25.0, 0.0);
+        car.apply_impulse(Vec2::new(100.0, 50.0));
+        assert!(resolve_obstacle_collision(&mut car, Vec2::ZERO, 10.0));
+        assert!((car.position().x + car.collision_radius() + 10.0).abs() < 1e-3);
+        assert_eq!(car.velocity(), Vec2::new(0.0, 50.0));
+        assert!(!resolve_obstacle_collision(&mut car, Vec2::ZERO, 10.0));
+    }
+
+    #[test]
+    fn confine_keeps_cars_inside_and_stops_outward_motion() {
+        let mut car = Car::new(-10.0, 50.0);
+        car.apply_impulse(Vec2::new(-20.0, 30.0));
+        assert!(confine_car(&mut car, Vec2::ZERO, Vec2::splat(100.0)));
+        assert_eq!(car.position(), Vec2::new(0.0, 50.0));
+        assert_eq!(car.velocity(), Vec2::new(0.0, 30.0));
+        assert!(!confine_car(&mut car, Vec2::ZERO, Vec2::splat(100.0)));
+    }
+}
@@ -4,8 +4,22 @@
 //! object positioning, and state updates. Handles all dynamic
 //! object interactions and maintains the game's physical state.
 
-pub use car::{Car, CarInput};
+pub use car::{Car, CarInput, CarTelemetry};
+pub use collision::{
+    confine_car, resolve_car_collision, resolve_obstacle_collision, CAR_RESTITUTION,
+};
+pub use object::{ObjectType, WorldObject};
+pub use race::Race;
+pub use spatial_grid::SpatialGrid;
+pub use start_grid::StartGrid;
+pub use trail::{Trail, TrailPoint};
 pub use world::World;
 
 mod car;
+mod collision;
+mod object;
+mod race;
+mod spatial_grid;
+mod start_grid;
+mod trail;
 mod world;
@@ -0,0 +1,204 @@
+//! Static objects placed on the track, e.g. decorations and obstacles
+
+use super::World;
+use crate::game::rendering::{Renderable, Sprite};
+use glam::Vec2;
+
+/// How cars interact with a world object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObjectType {
+    /// Purely visual, cars drive through it
+    #[default]
+    Decoration,
+    /// Solid, cars bump into it within its collision radius
+    Obstacle,
+}
+
+/// A static object placed in the world, e.g. a decoration
+///
+/// WorldObject provides:
+/// * Position and rotation in world space
+/// * An optional sprite, objects without one are never drawn
+/// * An object type, obstacles physically block cars
+/// * An active flag, inactive objects are ignored entirely
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldObject {
+    /// Position in world space
+    position: Vec2,
+    /// Heading in radians, same convention as `Car::angle`
+    rotation: f32,
+    /// Texture and size, None for invisible objects
+    sprite: Option<Sprite>,
+    /// How cars interact with the object
+    object_type: ObjectType,
+    /// Radius of the solid circle around `position` for obstacles
+    collision_radius: f32,
+    /// Whether the object currently takes part in the world
+    active: bool,
+}
+
+impl WorldObject {
+    /// Creates an active, unrotated object
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position in world space
+    /// * `sprite` - Texture and size, None for an invisible object
+    pub fn new(position: Vec2, sprite: Option<Sprite>) -> Self {
+        Self {
+            position,
+            rotation: 0.0,
+            sprite,
+            object_type: ObjectType::Decoration,
+            collision_radius: 0.0,
+            active: true,
+        }
+    }
+
+    /// Creates an active, unrotated obstacle that blocks cars
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Center of the obstacle in world space
+    /// * `collision_radius` - Radius of the solid circle, negative is treated as 0
+    /// * `sprite` - Texture and size, None for an invisible wall
+    pub fn obstacle(position: Vec2, collision_radius: f32, sprite: Option<Sprite>) -> Self {
+        Self {
+            object_type: ObjectType::Obstacle,
+            collision_radius: collision_radius.max(0.0),
+            ..Self::new(position, sprite)
+        }
+    }
+
+    /// Returns the position in world space
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Sets the position in world space
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+
+    /// Returns the heading in radians
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Sets the heading in radians
+    pub fn set_rotation(&mut self, rotation: f32) {
+        self.rotation = rotation;
+    }
+
+    /// Returns the sprite, if the object has one
+    pub fn sprite(&self) -> Option<&Sprite> {
+        self.sprite.as_ref()
+    }
+
+    /// Sets the sprite, None makes the object invisible
+    pub fn set_sprite(&mut self, sprite: Option<Sprite>) {
+        self.sprite = sprite;
+    }
+
+    /// Returns how cars interact with the object
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    /// Sets how cars interact with the object
+    pub fn set_object_type(&mut self, object_type: ObjectType) {
+        self.object_type = object_type;
+    }
+
+    /// Returns the radius of the solid circle used for obstacles
+    pub fn collision_radius(&self) -> f32 {
+        self.collision_radius
+    }
+
+    /// Sets the radius of the solid circle, negative is treated as 0
+    pub fn set_collision_radius(&mut self, radius: f32) {
+        self.collision_radius = radius.max(0.0);
+    }
+
+    /// Returns whether cars currently bump into the object
+    pub fn is_solid(&self) -> bool {
+        self.active && self.object_type == ObjectType::Obstacle && self.collision_radius > 0.0
+    }
+
+    /// Returns whether the object currently takes part in the world
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Enables or disables the object
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    /// Returns whether the renderer draws the object (active with a sprite)
+    pub fn is_visible(&self) -> bool {
+        self.active && self.sprite.is_some()
+    }
+}
+
+impl Renderable for WorldObject {
+    fn position(&self) -> Vec2 {
+        self.position()
+    }
+
+    /// Heading plus the sprite's own turn
+    fn rotation(&self) -> f32 {
+        self.rotation + self.sprite.as_ref().map_or(0.0, Sprite::rotation)
+    }
+
+    /// Larger side of the scaled sprite, 0.0 without one
+    fn base_size(&self) -> f32 {
+        self.size().max_element()
+    }
+
+    /// Scaled size of the sprite, zero without one
+    fn size(&self) -> Vec2 {
+        self.sprite.as_ref().map_or(Vec2::ZERO, Sprite::size)
+    }
+
+    /// Layer of the sprite, 0 without one
+    fn layer(&self) -> i32 {
+        self.sprite.as_ref().map_or(0, Sprite::layer)
+    }
+
+    /// Texture of the sprite, empty without one
+    fn texture_file(&self, _world: &World) -> &str {
+        self.sprite.as_ref().map_or("", Sprite::texture_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::rendering::Renderer;
+
+    #[test]
+    fn only_active_objects_are_rendered() {
+        let mut world = World::new(1);
+        let sprite = Some(Sprite::new("tree.png", 16.0, 16.0));
+        let shown = world.spawn_object(WorldObject::new(Vec2::new(100.0, 0.0), sprite.clone()));
+        let hidden = world.spawn_object(WorldObject::new(Vec2::new(200.0, 0.0), sprite));
+        world.object_mut(hidden).unwrap().set_active(false);
+
+        let positions: Vec<_> = Renderer::renderables(&world)
+            .iter()
+            .map(|renderable| renderable.position())
+            .collect();
+        assert_eq!(positions.len(), 2);
+        assert!(positions.contains(&world.object(shown).unwrap().position()));
+        assert!(!positions.contains(&Vec2::new(200.0, 0.0)));
+
+        // Inactive obstacles don't block cars either
+        let mut obstacle = WorldObject::obstacle(Vec2::ZERO, 10.0, None);
+        assert!(obstacle.is_solid());
+        obstacle.set_active(false);
+        assert!(!obstacle.is_solid() && !obstacle.is_visible());
+    }
+}
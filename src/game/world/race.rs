@@ -0,0 +1,128 @@
+use super::World;
+
+/// Race clock and finishing order for a race over a fixed number of laps
+///
+/// Race provides:
+/// * Elapsed race time, advanced once per world step
+/// * The finish time of every player, down to the fraction of a step
+/// * The winner, the player with the earliest finish time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Race {
+    /// Laps needed to finish
+    laps: u32,
+    /// Seconds since the start
+    time: f32,
+    /// Race time at which each player finished, if they did
+    finish_times: Vec<Option<f32>>,
+}
+
+impl Race {
+    /// Starts a race
+    ///
+    /// # Arguments
+    ///
+    /// * `laps` - Laps needed to finish
+    /// * `player_count` - Number of players racing
+    pub fn new(laps: u32, player_count: usize) -> Self {
+        Self {
+            laps,
+            time: 0.0,
+            finish_times: vec![None; player_count],
+        }
+    }
+
+    /// Returns the laps needed to finish
+    pub fn laps(&self) -> u32 {
+        self.laps
+    }
+
+    /// Returns the seconds since the start
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Returns the race time at which a player finished, if they did
+    pub fn finish_time(&self, player: usize) -> Option<f32> {
+        self.finish_times.get(player).copied().flatten()
+    }
+
+    /// Records the outcome of one world step
+    ///
+    /// Players completing their last lap get a finish time interpolated
+    /// within the step (see [`World::lap_completed`]), so two players
+    /// finishing in the same step are still told apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - World right after the step
+    /// * `dt` - Length of the step in seconds
+    pub fn record_step(&mut self, world: &World, dt: f32) {
+        let start = self.time;
+        self.time += dt;
+
+        for (player, finish) in self.finish_times.iter_mut().enumerate() {
+            if finish.is_some() || world.lap_count(player) < self.laps {
+                continue;
+            }
+            if let Some(fraction) = world.lap_completed(player) {
+                *finish = Some(start + fraction * dt);
+            }
+        }
+    }
+
+    /// Returns the player with the earliest finish time, if anyone finished
+    ///
+    /// Ties go to the lower player index.
+    pub fn winner(&self) -> Option<usize> {
+        self.finish_times
+            .iter()
+            .enumerate()
+            .filter_map(|(player, time)| time.map(|time| (player, time)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(player, _)| player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::FIXED_DT;
+    use crate::game::track::Checkpoint;
+    use glam::Vec2;
+
+    /// Builds a world whose cars lap by moving between two checkpoints
+    fn world() -> World {
+        let mut world = World::new(2);
+        world.set_checkpoints(vec![
+            Checkpoint::new(Vec2::new(200.0, 200.0), 50.0),
+            Checkpoint::new(Vec2::new(800.0, 800.0), 50.0),
+        ]);
+        world
+    }
+
+    fn drive_to(world: &mut World, race: &mut Race, player: usize, position: Vec2) {
+        world.cars[player].reset(position.x, position.y);
+        world.step_fixed(&[]);
+        race.record_step(world, FIXED_DT);
+    }
+
+    #[test]
+    fn first_finisher_wins() {
+        let mut world = world();
+        let mut race = Race::new(1, 2);
+        let (a, b) = (Vec2::new(200.0, 200.0), Vec2::new(800.0, 800.0));
+
+        for player in [1, 0] {
+            drive_to(&mut world, &mut race, player, a);
+            drive_to(&mut world, &mut race, player, b);
+        }
+        assert_eq!(race.winner(), None);
+
+        drive_to(&mut world, &mut race, 1, a);
+        drive_to(&mut world, &mut race, 0, a);
+        assert_eq!(race.winner(), Some(1));
+        assert!(race.finish_time(1).unwrap() < race.finish_time(0).unwrap());
+        assert!(race.finish_time(0).unwrap() <= race.time());
+        assert_eq!(race.finish_time(5), None);
+    }
+}
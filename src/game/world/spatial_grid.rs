@@ -0,0 +1,188 @@
+//! Uniform grid for finding objects near a position
+
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Spatial hash bucketing ids by the grid cell their position falls in
+///
+/// Radius queries only look at the cells the search circle overlaps, so
+/// they stay cheap however many objects are far away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialGrid {
+    /// Side length of a cell in world units
+    cell_size: f32,
+    /// Ids and positions in each non-empty cell
+    cells: HashMap<(i32, i32), Vec<(u64, Vec2)>>,
+    /// Number of inserted ids
+    len: usize,
+}
+
+impl SpatialGrid {
+    /// Creates an empty grid
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_size` - Side length of a cell in world units, about the
+    ///   usual query radius works best. Values that aren't positive are
+    ///   treated as 1.0
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: if cell_size > 0.0 { cell_size } else { 1.0 },
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the side length of a cell in world units
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the number of inserted ids
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no ids are inserted
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds an id at a position
+    ///
+    /// Inserting the same id twice keeps both entries, remove objects
+    /// that move and insert them again.
+    pub fn insert(&mut self, id: u64, position: Vec2) {
+        self.cells
+            .entry(self.cell(position))
+            .or_default()
+            .push((id, position));
+        self.len += 1;
+    }
+
+    /// Removes every entry of an id
+    ///
+    /// # Returns
+    ///
+    /// Whether the id was in the grid
+    pub fn remove(&mut self, id: u64) -> bool {
+        let before = self.len;
+        self.cells.retain(|_, entries| {
+            entries.retain(|&(entry, _)| entry != id);
+            !entries.is_empty()
+        });
+        self.len = self.cells.values().map(Vec::len).sum();
+        self.len != before
+    }
+
+    /// Removes all ids
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+    }
+
+    /// Returns the ids within a distance of a position
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Center of the search circle
+    /// * `radius` - Radius of the search circle, edges inclusive
+    ///
+    /// # Returns
+    ///
+    /// Matching ids in ascending order
+    pub fn query_radius(&self, position: Vec2, radius: f32) -> Vec<u64> {
+        if radius.is_nan() || radius < 0.0 || !position.is_finite() {
+            return Vec::new();
+        }
+
+        let (min_x, min_y) = self.cell(position - radius);
+        let (max_x, max_y) = self.cell(position + radius);
+        let mut ids = Vec::new();
+
+        // Far fewer cells are occupied than a huge radius covers
+        let cell_count = ((max_x as i64 - min_x as i64 + 1) as u64)
+            .saturating_mul((max_y as i64 - min_y as i64 + 1) as u64);
+        if cell_count > self.cells.len() as u64 {
+            for (&(x, y), entries) in &self.cells {
+                if (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y) {
+                    Self::collect_within(entries, position, radius, &mut ids);
+                }
+            }
+        } else {
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    if let Some(entries) = self.cells.get(&(x, y)) {
+                        Self::collect_within(entries, position, radius, &mut ids);
+                    }
+                }
+            }
+        }
+
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Pushes the ids of the entries inside the search circle
+    fn collect_within(entries: &[(u64, Vec2)], position: Vec2, radius: f32, ids: &mut Vec<u64>) {
+        ids.extend(
+            entries
+                .iter()
+                .filter(|(_, entry)| entry.distance_squared(position) <= radius * radius)
+                .map(|&(id, _)| id),
+        );
+    }
+
+    /// Returns the cell a position falls in, saturating far outside the grid
+    fn cell(&self, position: Vec2) -> (i32, i32) {
+        let cell = (position / self.cell_size).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_finds_ids_within_radius() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.insert(3, Vec2::new(5.0, 5.0));
+        grid.insert(1, Vec2::new(14.0, 5.0));
+        grid.insert(2, Vec2::new(-30.0, 5.0));
+
+        assert_eq!(grid.query_radius(Vec2::new(5.0, 5.0), 9.0), vec![1, 3]);
+        assert_eq!(grid.query_radius(Vec2::new(5.0, 5.0), 8.9), vec![3]);
+        assert_eq!(grid.query_radius(Vec2::new(-25.0, 5.0), 5.0), vec![2]);
+        assert_eq!(grid.query_radius(Vec2::ZERO, 1000.0), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn query_rejects_bad_input() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.insert(0, Vec2::ZERO);
+        assert!(grid.query_radius(Vec2::ZERO, -1.0).is_empty());
+        assert!(grid.query_radius(Vec2::ZERO, f32::NAN).is_empty());
+        assert!(grid.query_radius(Vec2::NAN, 1.0).is_empty());
+        assert_eq!(grid.query_radius(Vec2::ZERO, f32::INFINITY), vec![0]);
+    }
+
+    #[test]
+    fn remove_drops_every_entry() {
+        let mut grid = SpatialGrid::new(0.0);
+        assert_eq!(grid.cell_size(), 1.0);
+
+        grid.insert(7, Vec2::ZERO);
+        grid.insert(7, Vec2::new(50.0, 0.0));
+        grid.insert(8, Vec2::ZERO);
+        assert_eq!(grid.len(), 3);
+
+        assert!(grid.remove(7));
+        assert!(!grid.remove(7));
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.query_radius(Vec2::ZERO, 100.0), vec![8]);
+
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Starting positions of the cars
+
+use glam::Vec2;
+use std::f32::consts::PI;
+
+/// Layout of the cars at the start of a race
+///
+/// Cars are placed in rows of `lanes` cars side by side, all facing the
+/// same direction. The first row is centered on `origin`, later rows
+/// line up behind it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StartGrid {
+    /// Center of the first row
+    origin: Vec2,
+    /// Heading of every car in radians (counterclockwise from vertical)
+    angle: f32,
+    /// Distance between two cars in the same row
+    lane_spacing: f32,
+    /// Distance between two rows
+    row_spacing: f32,
+    /// Cars per row
+    lanes: usize,
+}
+
+impl Default for StartGrid {
+    /// Creates the grid of the default track
+    ///
+    /// * Two lanes 50 units apart, centered on the road at (845, 720),
+    ///   just behind the first checkpoint so lap 1 is a full lap
+    /// * Rows 70 units apart
+    /// * Facing up the right straight towards the top of the map
+    fn default() -> Self {
+        Self::new(Vec2::new(845.0, 720.0), PI, 50.0, 70.0, 2)
+    }
+}
+
+impl StartGrid {
+    /// Creates a starting grid
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Center of the first row
+    /// * `angle` - Heading of every car in radians
+    /// * `lane_spacing` - Distance between two cars in the same row
+    /// * `row_spacing` - Distance between two rows
+    /// * `lanes` - Cars per row, 0 is treated as 1
+    pub fn new(
+        origin: Vec2,
+        angle: f32,
+        lane_spacing: f32,
+        row_spacing: f32,
+        lanes: usize,
+    ) -> Self {
+        Self {
+            origin,
+            angle,
+            lane_spacing,
+            row_spacing,
+            lanes: lanes.max(1),
+        }
+    }
+
+    /// Returns the center of the first row
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Returns the heading of every car in radians
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Returns the starting position and heading of a car
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the car, lower indices start further ahead
+    ///   and further left within a row
+    pub fn slot(&self, index: usize) -> (Vec2, f32) {
+        let forward = Vec2::new(-self.angle.sin(), self.angle.cos());
+        let left = forward.perp();
+
+        let row = (index / self.lanes) as f32;
+        let lane = (index % self.lanes) as f32;
+        let lane_offset = lane - (self.lanes - 1) as f32 / 2.0;
+
+        let position =
+            self.origin - forward * row * self.row_spacing - left * lane_offset * self.lane_spacing;
+        (position, self.angle)
+    }
+
+    /// Returns the starting positions and headings of the first `count` cars
+    pub fn slots(&self, count: usize) -> Vec<(Vec2, f32)> {
+        (0..count).map(|index| self.slot(index)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::track::TrackDef;
+
+    #[test]
+    fn slots_line_up_behind_the_origin() {
+        let grid = StartGrid::new(Vec2::ZERO, 0.0, 50.0, 70.0, 2);
+        let slots = grid.slots(4);
+
+        // Facing +y, the first row is centered on the origin
+        assert_eq!(slots[0].0, Vec2::new(-25.0, 0.0));
+        assert_eq!(slots[1].0, Vec2::new(25.0, 0.0));
+        // The second row is one row spacing behind
+        assert_eq!(slots[2].0, Vec2::new(-25.0, -70.0));
+        assert_eq!(slots[3].0, Vec2::new(25.0, -70.0));
+        assert!(slots.iter().all(|&(_, angle)| angle == 0.0));
+    }
+
+    #[test]
+    fn zero_lanes_is_one_lane() {
+        let grid = StartGrid::new(Vec2::ZERO, 0.0, 50.0, 70.0, 0);
+        assert_eq!(grid.slot(0).0, Vec2::ZERO);
+        assert_eq!(grid.slot(1).0, Vec2::new(0.0, -70.0));
+    }
+
+    #[test]
+    fn default_grid_starts_just_behind_the_first_checkpoint() {
+        let track = TrackDef::default();
+        let first = track.checkpoints[0];
+        for (position, angle) in track.grid.slots(4) {
+            // Outside the trigger area, but heading straight into it
+            assert!(!first.check_trigger(position));
+            let forward = Vec2::new(-angle.sin(), angle.cos());
+            let ahead = position + forward * (position.distance(first.position()) + 1.0);
+            assert!(first.entry_fraction(position, ahead) < 1.0);
+        }
+    }
+}
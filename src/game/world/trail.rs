@@ -0,0 +1,136 @@
+//! Fading skid marks left behind by a car
+
+use glam::Vec2;
+use std::collections::VecDeque;
+
+/// Opacity lost per second by every trail point
+const FADE_PER_SECOND: f32 = 0.25;
+
+/// A single skid mark on the ground
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrailPoint {
+    /// World position of the mark
+    pub position: Vec2,
+    /// Opacity from 1.0 (fresh) to 0.0 (gone)
+    pub alpha: f32,
+}
+
+/// Ring buffer of the most recent skid marks of one car
+///
+/// Pushing onto a full trail drops the oldest point, and points are
+/// removed once they have faded out completely.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trail {
+    /// Points from oldest to newest
+    points: VecDeque<TrailPoint>,
+    /// Maximum number of points kept
+    capacity: usize,
+}
+
+impl Trail {
+    /// Creates an empty trail
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of points kept
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the points from oldest to newest
+    pub fn points(&self) -> impl Iterator<Item = &TrailPoint> {
+        self.points.iter()
+    }
+
+    /// Returns the number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns whether the trail has no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the maximum number of points kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Adds a fresh mark, dropping the oldest one if the trail is full
+    pub fn push(&mut self, position: Vec2) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+
+        self.points.push_back(TrailPoint {
+            position,
+            alpha: 1.0,
+        });
+    }
+
+    /// Fades all marks and removes the ones that are gone
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Delta time in seconds
+    pub fn update(&mut self, dt: f32) {
+        for point in &mut self.points {
+            point.alpha -= FADE_PER_SECOND * dt;
+        }
+
+        // Older points are always fainter, so they are all at the front
+        while self.points.front().is_some_and(|point| point.alpha <= 0.0) {
+            self.points.pop_front();
+        }
+    }
+
+    /// Removes all marks
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_points_are_dropped_at_capacity() {
+        let mut trail = Trail::new(2);
+        for x in 0..3 {
+            trail.push(Vec2::new(x as f32, 0.0));
+        }
+        let xs: Vec<f32> = trail.points().map(|point| point.position.x).collect();
+        assert_eq!(xs, vec![1.0, 2.0]);
+
+        let mut empty = Trail::new(0);
+        empty.push(Vec2::ZERO);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn points_fade_and_disappear() {
+        let mut trail = Trail::new(10);
+        trail.push(Vec2::ZERO);
+        trail.update(2.0);
+        trail.push(Vec2::ONE);
+        assert_eq!(trail.points().next().unwrap().alpha, 0.5);
+
+        trail.update(2.0);
+        assert_eq!(trail.len(), 1);
+        assert_eq!(trail.points().next().unwrap().position, Vec2::ONE);
+
+        trail.clear();
+        assert!(trail.is_empty());
+        assert_eq!(trail.capacity(), 10);
+    }
+}
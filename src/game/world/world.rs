@@ -1,35 +1,535 @@
 //! Game world state and update logic
 
 use super::super::input::Inputs;
-use super::Car;
+use super::super::track::{
+    Checkpoint, LapTracker, OffTrackSurface, RacingLine, TileMap, TileType, TrackDef,
+};
+use super::{
+    confine_car, resolve_car_collision, resolve_obstacle_collision, Car, CarInput, SpatialGrid,
+    StartGrid, Trail, WorldObject, CAR_RESTITUTION,
+};
+use crate::consts::FIXED_DT;
+use glam::Vec2;
+use std::f32::consts::PI;
+
+/// Number of cars in the default split-screen race
+const DEFAULT_PLAYER_COUNT: usize = 2;
+
+/// Size of the default playfield, matching the track texture
+const WORLD_SIZE: f32 = 1024.0;
+
+/// Seconds a car has to drive against the racing line before it counts as wrong way
+const WRONG_WAY_DELAY: f32 = 1.5;
+
+/// Speed below which a car never counts as driving the wrong way
+const WRONG_WAY_MIN_SPEED: f32 = 20.0;
+
+/// Racing line points between two default checkpoints
+const CHECKPOINT_SPACING: usize = 3;
+
+/// Trigger radius of the default checkpoints, roughly the road width
+const CHECKPOINT_RADIUS: f32 = 100.0;
+
+/// Max speed and acceleration factor given by driving over a boost tile
+const BOOST_MULTIPLIER: f32 = 1.5;
+
+/// Seconds a boost tile keeps a car boosted after leaving it
+const BOOST_SECONDS: f32 = 1.5;
+
+/// Skid marks kept per car, 4 seconds of skidding at the fixed step
+const TRAIL_CAPACITY: usize = 480;
+
+/// Speed below which a car never leaves skid marks
+const SKID_MIN_SPEED: f32 = 40.0;
+
+/// Brake input above which a moving car leaves skid marks
+const SKID_BRAKE: f32 = 0.5;
+
+/// Angle between heading and velocity above which a car leaves skid marks
+const SKID_SLIP_ANGLE: f32 = 0.3;
+
+/// Side length of a cell of the obstacle lookup grid in world units
+const OBSTACLE_GRID_CELL_SIZE: f32 = 128.0;
 
 /// The main game world containing all dynamic game entities
 ///
-/// The World struct manages a two-player racing game with two cars. Each car:
+/// The World struct manages a racing game with one car per player. Each car:
 /// * Has independent physics and controls
-/// * Updates based on player inputs (WASD or Arrow keys)
+/// * Updates based on its player's inputs
 /// * Maintains its own position and state
 ///
 /// All world updates are frame-rate independent through delta time scaling.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct World {
-    /// Array of two cars for the racing game
+    /// One car per player, indexed by player
     /// Index 0: Player 1 (WASD controls)
     /// Index 1: Player 2 (Arrow controls)
-    pub cars: [Car; 2],
+    pub cars: Vec<Car>,
+    /// Objects placed on the track, e.g. decorations and obstacles
+    objects: Vec<WorldObject>,
+    /// Id of each object, in the same order as `objects`
+    #[cfg_attr(feature = "serde", serde(default))]
+    object_ids: Vec<u64>,
+    /// Id handed to the next spawned object
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_object_id: u64,
+    /// Solid objects by position and the largest collision radius among
+    /// them, None until rebuilt after objects changed
+    #[cfg_attr(feature = "serde", serde(skip))]
+    obstacle_grid: Option<(SpatialGrid, f32)>,
+    /// Optional tile map providing surface properties
+    track: Option<TileMap>,
+    /// Optional ground texture colors that slow cars down off-track
+    #[cfg_attr(feature = "serde", serde(skip))]
+    off_track: Option<OffTrackSurface>,
+    /// Checkpoints defining the intended driving direction
+    racing_line: RacingLine,
+    /// Seconds each car has been driving against the racing line
+    wrong_way_time: Vec<f32>,
+    /// Checkpoints that have to be driven through in order to complete a lap
+    checkpoints: Vec<Checkpoint>,
+    /// Lap progress of each car
+    laps: Vec<LapTracker>,
+    /// Fraction of the last step at which each car completed a lap, if it did
+    lap_fractions: Vec<Option<f32>>,
+    /// Velocity change from collisions of each car in the last step
+    impacts: Vec<f32>,
+    /// Skid marks left by each car
+    trails: Vec<Trail>,
+    /// Starting position and angle of each car, restored by `reset`
+    starts: Vec<(Vec2, f32)>,
+    /// Minimum and maximum corner of the playfield cars are kept inside
+    bounds: (Vec2, Vec2),
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new_two_player()
+    }
 }
 
 impl World {
-    /// Creates a new game world with two cars at default positions
+    /// Creates a new game world with one car per player at default positions
+    ///
+    /// # Arguments
+    ///
+    /// * `player_count` - Number of cars to create
     ///
     /// # Returns
     ///
     /// A new World instance with:
-    /// * Two cars
-    pub fn new() -> Self {
-        let car1 = Car::new(1024.0 / 3.0, 1024.0 / 3.0);
-        let car2 = Car::new(1024.0 / 3.3, 1024.0 / 3.3);
+    /// * `player_count` cars on the default [`StartGrid`], two side by side
+    ///   per row, facing down the track
+    /// * A 1024x1024 playfield matching the track texture
+    pub fn new(player_count: usize) -> Self {
+        Self::with_bounds(player_count, Vec2::ZERO, Vec2::splat(WORLD_SIZE))
+    }
+
+    /// Creates a new game world for the default two-player split-screen race
+    pub fn new_two_player() -> Self {
+        Self::new(DEFAULT_PLAYER_COUNT)
+    }
+
+    /// Creates a new game world with a custom playfield
+    ///
+    /// # Arguments
+    ///
+    /// * `player_count` - Number of cars to create
+    /// * `min` - Minimum corner of the playfield
+    /// * `max` - Maximum corner of the playfield
+    ///
+    /// # Returns
+    ///
+    /// A new World instance like [`World::new`] whose cars are kept
+    /// inside `min..max`
+    pub fn with_bounds(player_count: usize, min: Vec2, max: Vec2) -> Self {
+        Self::with_grid(player_count, &StartGrid::default(), min, max)
+    }
+
+    /// Creates a new game world with a custom playfield and starting grid
+    ///
+    /// # Arguments
+    ///
+    /// * `player_count` - Number of cars to create
+    /// * `grid` - Starting position and heading of every car
+    /// * `min` - Minimum corner of the playfield
+    /// * `max` - Maximum corner of the playfield
+    pub fn with_grid(player_count: usize, grid: &StartGrid, min: Vec2, max: Vec2) -> Self {
+        let starts = grid.slots(player_count);
+        let cars = starts
+            .iter()
+            .enumerate()
+            .map(|(i, (position, angle))| {
+                let mut car = Car::new_with_angle(position.x, position.y, *angle);
+                car.set_player_index(i);
+                car
+            })
+            .collect();
+
+        let racing_line = RacingLine::default_track();
+        let checkpoints = Checkpoint::along(&racing_line, CHECKPOINT_SPACING, CHECKPOINT_RADIUS);
+
+        Self {
+            cars,
+            objects: Vec::new(),
+            object_ids: Vec::new(),
+            next_object_id: 0,
+            obstacle_grid: None,
+            track: None,
+            off_track: None,
+            racing_line,
+            wrong_way_time: vec![0.0; player_count],
+            checkpoints,
+            laps: vec![LapTracker::new(); player_count],
+            lap_fractions: vec![None; player_count],
+            impacts: vec![0.0; player_count],
+            trails: vec![Trail::new(TRAIL_CAPACITY); player_count],
+            starts,
+            bounds: (min, max),
+        }
+    }
+
+    /// Creates a two-player world for a track description
+    ///
+    /// The cars start on the track's grid inside its playfield, with its
+    /// checkpoints and racing line. The ground texture and lap count are
+    /// up to the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the track fails [`TrackDef::validate`], e.g. it has no checkpoints
+    pub fn from_track(track: &TrackDef) -> anyhow::Result<Self> {
+        let racing_line = track.racing_line()?;
+
+        let mut world = Self::with_grid(DEFAULT_PLAYER_COUNT, &track.grid, Vec2::ZERO, track.size);
+        world.set_racing_line(racing_line);
+        world.set_checkpoints(track.checkpoints.clone());
+        Ok(world)
+    }
+
+    /// Moves every car's starting position onto a different grid
+    ///
+    /// Takes effect on the next [`World::reset`].
+    pub fn set_start_grid(&mut self, grid: &StartGrid) {
+        self.starts = grid.slots(self.cars.len());
+    }
+
+    /// Returns the number of cars in the world
+    pub fn player_count(&self) -> usize {
+        self.cars.len()
+    }
+
+    /// Puts the world back into its starting state for a new race
+    ///
+    /// All cars return to their starting positions at rest and all
+    /// lap and wrong-way progress is cleared. Car tuning is kept.
+    pub fn reset(&mut self) {
+        for (car, (position, angle)) in self.cars.iter_mut().zip(&self.starts) {
+            car.reset_with_angle(position.x, position.y, *angle);
+        }
+        self.laps.fill(LapTracker::new());
+        self.lap_fractions.fill(None);
+        self.wrong_way_time.fill(0.0);
+        self.impacts.fill(0.0);
+        self.trails.iter_mut().for_each(Trail::clear);
+    }
+
+    /// Returns how hard a car crashed during the last step
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car to check
+    ///
+    /// # Returns
+    ///
+    /// Velocity change caused by car and wall collisions in units/s,
+    /// 0.0 if the car didn't hit anything (or doesn't exist)
+    pub fn impact(&self, car_index: usize) -> f32 {
+        self.impacts.get(car_index).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the skid marks of a car, None if the car doesn't exist
+    pub fn trail(&self, car_index: usize) -> Option<&Trail> {
+        self.trails.get(car_index)
+    }
+
+    /// Returns the skid marks of all cars, indexed by car
+    pub fn trails(&self) -> &[Trail] {
+        &self.trails
+    }
+
+    /// Writes the complete world state to a JSON file
+    ///
+    /// # Errors
+    ///
+    /// If the world can't be serialized or the file can't be written
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        use anyhow::Context as _;
+
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write world to {}", path.display()))
+    }
+
+    /// Reads a world state written by [`World::save`]
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be read, doesn't describe a world, holds a
+    /// different amount of per-car state than cars or contains an
+    /// invalid track
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        use anyhow::{ensure, Context as _};
+
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read world from {}", path.display()))?;
+        let mut world: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid world file {}", path.display()))?;
+
+        // Worlds saved before objects had ids get them in order
+        if world.object_ids.is_empty() && !world.objects.is_empty() {
+            world.object_ids = (0..world.objects.len() as u64).collect();
+            world.next_object_id = world.objects.len() as u64;
+        }
+
+        let count = world.cars.len();
+        ensure!(
+            [
+                world.wrong_way_time.len(),
+                world.laps.len(),
+                world.lap_fractions.len(),
+                world.impacts.len(),
+                world.trails.len(),
+                world.starts.len()
+            ]
+            .iter()
+            .all(|&len| len == count),
+            "Per-car state doesn't match the {} cars",
+            count
+        );
+        ensure!(
+            world.racing_line.points().len() >= 2,
+            "Racing line needs at least 2 checkpoints"
+        );
+        ensure!(
+            world.track.as_ref().is_none_or(TileMap::is_consistent),
+            "Track tiles don't match the track size or have no size"
+        );
+        ensure!(
+            world.object_ids.len() == world.objects.len()
+                && world.object_ids.windows(2).all(|ids| ids[0] < ids[1])
+                && world
+                    .object_ids
+                    .last()
+                    .is_none_or(|&id| id < world.next_object_id),
+            "Object ids don't match the objects"
+        );
+
+        Ok(world)
+    }
+
+    /// Returns the objects placed in the world, including inactive ones
+    pub fn objects(&self) -> &[WorldObject] {
+        &self.objects
+    }
+
+    /// Returns the objects placed in the world for changing them
+    pub fn objects_mut(&mut self) -> &mut [WorldObject] {
+        self.obstacle_grid = None;
+        &mut self.objects
+    }
+
+    /// Places an object in the world, see [`World::spawn_object`]
+    pub fn add_object(&mut self, object: WorldObject) {
+        self.spawn_object(object);
+    }
+
+    /// Places an object in the world at runtime
+    ///
+    /// # Returns
+    ///
+    /// Id to look the object up with or [`World::despawn`] it, ids are
+    /// never reused
+    pub fn spawn_object(&mut self, object: WorldObject) -> u64 {
+        let id = self.next_object_id;
+        self.next_object_id += 1;
+        self.objects.push(object);
+        self.object_ids.push(id);
+        self.obstacle_grid = None;
+        id
+    }
+
+    /// Removes an object from the world, e.g. a collected pickup
+    ///
+    /// # Returns
+    ///
+    /// * `true` - The object was removed
+    /// * `false` - No object has this id, e.g. it was already despawned
+    pub fn despawn(&mut self, id: u64) -> bool {
+        let Some(index) = self.object_index(id) else {
+            return false;
+        };
+
+        self.objects.remove(index);
+        self.object_ids.remove(index);
+        self.obstacle_grid = None;
+        true
+    }
+
+    /// Returns the object with an id, if it still exists
+    pub fn object(&self, id: u64) -> Option<&WorldObject> {
+        self.object_index(id).map(|index| &self.objects[index])
+    }
+
+    /// Returns the object with an id for changing it, if it still exists
+    pub fn object_mut(&mut self, id: u64) -> Option<&mut WorldObject> {
+        let index = self.object_index(id)?;
+        self.obstacle_grid = None;
+        Some(&mut self.objects[index])
+    }
+
+    /// Returns the position of an object in `objects`
+    fn object_index(&self, id: u64) -> Option<usize> {
+        // Ids only grow and removal keeps the order, so they stay sorted
+        self.object_ids.binary_search(&id).ok()
+    }
+
+    /// Returns the minimum and maximum corner of the playfield
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.bounds
+    }
+
+    /// Returns the racing line defining the driving direction
+    pub fn racing_line(&self) -> &RacingLine {
+        &self.racing_line
+    }
+
+    /// Sets the racing line defining the driving direction
+    pub fn set_racing_line(&mut self, racing_line: RacingLine) {
+        self.racing_line = racing_line;
+        self.wrong_way_time.fill(0.0);
+    }
+
+    /// Returns the checkpoints in the order they have to be driven
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Sets the checkpoints and resets the lap progress of all cars
+    pub fn set_checkpoints(&mut self, checkpoints: Vec<Checkpoint>) {
+        self.checkpoints = checkpoints;
+        self.laps.fill(LapTracker::new());
+        self.lap_fractions.fill(None);
+    }
+
+    /// Returns the number of laps a player has completed
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - Index of the player's car
+    ///
+    /// # Returns
+    ///
+    /// Completed laps, 0 for an unknown player
+    pub fn lap_count(&self, player: usize) -> u32 {
+        self.laps.get(player).map_or(0, LapTracker::laps)
+    }
+
+    /// Returns when during the last step a player completed a lap
+    ///
+    /// # Returns
+    ///
+    /// Fraction of the step (0.0 to 1.0) at which the player's car
+    /// entered the finishing checkpoint, None if it completed no lap
+    pub fn lap_completed(&self, player: usize) -> Option<f32> {
+        self.lap_fractions.get(player).copied().flatten()
+    }
+
+    /// Returns the lap progress of a player, if the player exists
+    pub fn lap_tracker(&self, player: usize) -> Option<&LapTracker> {
+        self.laps.get(player)
+    }
+
+    /// Returns a player's place in the race, 1 being in the lead
+    ///
+    /// Cars that passed more checkpoints are ahead; between cars on the
+    /// same stretch the one closer to its next checkpoint is ahead. Cars
+    /// on exactly the same spot share a place.
+    ///
+    /// # Returns
+    ///
+    /// Place from 1 to the number of cars, None for an unknown player.
+    /// Cars without lap progress, e.g. pushed onto `cars` after the world
+    /// was created, are left out.
+    pub fn race_position(&self, player: usize) -> Option<usize> {
+        let progress = |player: usize| {
+            let (laps, car) = (self.laps.get(player)?, self.cars.get(player)?);
+            let passed = laps.checkpoints_passed(self.checkpoints.len());
+            let distance = self
+                .checkpoints
+                .get(laps.next_checkpoint())
+                .map_or(0.0, |checkpoint| {
+                    checkpoint.position().distance(car.position())
+                });
+            Some((passed, distance))
+        };
+
+        let (passed, distance) = progress(player)?;
+        let ahead = (0..self.cars.len())
+            .filter_map(progress)
+            .filter(|&(other_passed, other_distance)| {
+                other_passed > passed || (other_passed == passed && other_distance < distance)
+            })
+            .count();
+        Some(ahead + 1)
+    }
 
-        Self { cars: [car1, car2] }
+    /// Returns whether a car is driving against the racing line
+    ///
+    /// Only reports true after the car has been moving the wrong way for
+    /// a while, so briefly reversing (e.g. after a crash) doesn't trigger it.
+    ///
+    /// # Arguments
+    ///
+    /// * `car_index` - Index of the car to check
+    pub fn is_wrong_way(&self, car_index: usize) -> bool {
+        self.wrong_way_time
+            .get(car_index)
+            .is_some_and(|time| *time >= WRONG_WAY_DELAY)
+    }
+
+    /// Returns the tile map of the track, if any
+    pub fn track(&self) -> Option<&TileMap> {
+        self.track.as_ref()
+    }
+
+    /// Sets the tile map used for surface effects
+    pub fn set_track(&mut self, track: Option<TileMap>) {
+        self.track = track;
+    }
+
+    /// Returns the off-track detection, if any
+    pub fn off_track(&self) -> Option<&OffTrackSurface> {
+        self.off_track.as_ref()
+    }
+
+    /// Sets the ground texture colors that slow cars down off-track
+    pub fn set_off_track(&mut self, off_track: Option<OffTrackSurface>) {
+        self.off_track = off_track;
+    }
+
+    /// Returns whether a car is on off-track ground
+    ///
+    /// Always false without an off-track surface (see [`World::set_off_track`]).
+    pub fn is_off_track(&self, car_index: usize) -> bool {
+        match (&self.off_track, self.cars.get(car_index)) {
+            (Some(surface), Some(car)) => surface.is_off_track(car.position()),
+            _ => false,
+        }
     }
 
     /// Updates the state of all entities in the world
@@ -39,23 +539,474 @@ impl World {
     /// * `inputs` - Current state of player inputs
     /// * `dt` - Delta time in seconds
     ///
-    /// Updates both cars' physics and positions based on their
+    /// Updates all cars' physics and positions based on their
     /// respective player inputs and the time step.
     pub fn update(&mut self, inputs: &Inputs, dt: f32) {
-        let [car1, car2] = &mut self.cars;
-        let [car1_input, car2_input] = inputs.get_car_inputs();
+        self.step(&inputs.get_car_inputs(), dt);
+    }
 
-        car1.update(
-            dt,
-            car1_input.throttle(),
-            car1_input.brake(),
-            car1_input.turn(),
-        );
-        car2.update(
+    /// Advances the world by one fixed time step
+    ///
+    /// Uses `FIXED_DT` so the same input sequence always produces
+    /// the same world state, independent of the frame rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `car_inputs` - Control inputs for each car
+    pub fn step_fixed(&mut self, car_inputs: &[CarInput]) {
+        self.step(car_inputs, FIXED_DT);
+    }
+
+    /// Advances the world by `dt` using the given car inputs
+    ///
+    /// # Arguments
+    ///
+    /// * `car_inputs` - Control inputs for each car, cars without an
+    ///   input coast with all controls released
+    /// * `dt` - Delta time in seconds
+    ///
+    /// When a track is set, each car picks up the friction of the
+    /// tile it is on (plus the off-track friction when an off-track
+    /// surface is set) before its physics are updated, and boost tiles
+    /// boost the car driving over them (see [`Car::apply_boost`]).
+    /// Afterwards overlapping cars are pushed apart and bounced off each other,
+    /// cars are pushed out of solid obstacles (see [`WorldObject::is_solid`])
+    /// and kept inside the playfield bounds, recording how hard
+    /// each car hit something (see [`World::impact`]). Finally the
+    /// checkpoints are checked to advance each car's lap progress.
+    ///
+    /// Cars braking hard or sliding sideways at speed leave a skid mark
+    /// at their position, and older skid marks fade.
+    pub fn step(&mut self, car_inputs: &[CarInput], dt: f32) {
+        let previous: Vec<_> = self.cars.iter().map(Car::position).collect();
+        for index in 0..self.cars.len() {
+            let input = car_inputs.get(index).copied().unwrap_or_default();
+            self.update_car(index, input, dt);
+        }
+
+        let velocities: Vec<_> = self.cars.iter().map(Car::velocity).collect();
+        for i in 1..self.cars.len() {
+            let (before, rest) = self.cars.split_at_mut(i);
+            for other in before {
+                resolve_car_collision(other, &mut rest[0], CAR_RESTITUTION);
+            }
+        }
+
+        self.resolve_obstacle_collisions();
+
+        let (min, max) = self.bounds;
+        for car in &mut self.cars {
+            confine_car(car, min, max);
+        }
+
+        for ((impact, car), before) in self.impacts.iter_mut().zip(&self.cars).zip(velocities) {
+            *impact = (car.velocity() - before).length();
+        }
+
+        self.update_wrong_way(dt);
+        self.update_laps(&previous);
+        self.update_trails(car_inputs, dt);
+    }
+
+    /// Pushes every car out of the solid objects it overlaps
+    ///
+    /// Only obstacles in the grid cells around a car are tested, in the
+    /// order they were spawned.
+    fn resolve_obstacle_collisions(&mut self) {
+        let (grid, max_radius) = self.obstacle_grid.get_or_insert_with(|| {
+            let mut grid = SpatialGrid::new(OBSTACLE_GRID_CELL_SIZE);
+            let mut max_radius = 0.0f32;
+            for (object, &id) in self.objects.iter().zip(&self.object_ids) {
+                if object.is_solid() {
+                    grid.insert(id, object.position());
+                    max_radius = max_radius.max(object.collision_radius());
+                }
+            }
+            (grid, max_radius)
+        });
+        if grid.is_empty() {
+            return;
+        }
+
+        for car in &mut self.cars {
+            let radius = car.collision_radius() + *max_radius;
+            for id in grid.query_radius(car.position(), radius) {
+                if let Ok(index) = self.object_ids.binary_search(&id) {
+                    let object = &self.objects[index];
+                    resolve_obstacle_collision(car, object.position(), object.collision_radius());
+                }
+            }
+        }
+    }
+
+    /// Advances the physics of a single car, leaving all other cars as they are
+    ///
+    /// Picks up the surface under the car (see [`World::step`]) and
+    /// moves it by its input. Collisions, playfield bounds, laps and skid
+    /// marks are only handled by `step`, which calls this for every car.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the car, out of range indices are ignored
+    /// * `input` - Controls of the car, e.g. from a player, AI or replay
+    /// * `dt` - Delta time in seconds
+    pub fn update_car(&mut self, index: usize, input: CarInput, dt: f32) {
+        let Some(car) = self.cars.get_mut(index) else {
+            return;
+        };
+
+        if self.track.is_some() || self.off_track.is_some() {
+            let tile = self
+                .track
+                .as_ref()
+                .and_then(|track| track.tile_at(car.position()));
+            let off_track = self
+                .off_track
+                .as_ref()
+                .map_or(0.0, |surface| surface.friction_at(car.position()));
+            car.set_surface_friction(tile.map_or(0.0, |tile| tile.friction()) + off_track);
+            if tile == Some(TileType::Boost) {
+                car.apply_boost(BOOST_MULTIPLIER, BOOST_SECONDS);
+            }
+        }
+
+        car.update(
             dt,
-            car2_input.throttle(),
-            car2_input.brake(),
-            car2_input.turn(),
+            input.throttle(),
+            input.brake(),
+            input.turn(),
+            input.handbrake(),
         );
     }
+
+    /// Fades the skid marks and adds new ones under skidding cars
+    fn update_trails(&mut self, car_inputs: &[CarInput], dt: f32) {
+        for (i, (trail, car)) in self.trails.iter_mut().zip(&self.cars).enumerate() {
+            trail.update(dt);
+
+            let input = car_inputs.get(i).copied().unwrap_or_default();
+            let braking = input.brake() > SKID_BRAKE;
+            // Reversing straight back isn't sliding
+            let slip = car.slip_angle().abs();
+            let sliding = slip > SKID_SLIP_ANGLE && slip < PI - SKID_SLIP_ANGLE;
+            if car.speed() > SKID_MIN_SPEED && (braking || sliding) {
+                trail.push(car.position());
+            }
+        }
+    }
+
+    /// Advances the lap progress of cars inside their next checkpoint
+    ///
+    /// Only a car's next checkpoint can advance its progress, so only
+    /// that one is tested. A car inside several overlapping checkpoints
+    /// passes them in order, but never wraps around twice in one step.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - Car positions at the start of the step, used to
+    ///   find when during the step a lap was completed
+    fn update_laps(&mut self, previous: &[Vec2]) {
+        let count = self.checkpoints.len();
+        let cars = self
+            .cars
+            .iter()
+            .zip(&mut self.laps)
+            .zip(&mut self.lap_fractions);
+        for (player, ((car, laps), fraction)) in cars.enumerate() {
+            *fraction = None;
+            while let Some(checkpoint) = self.checkpoints.get(laps.next_checkpoint()) {
+                let index = laps.next_checkpoint();
+                if !checkpoint.check_trigger(car.position()) {
+                    break;
+                }
+                if laps.trigger(index, count) {
+                    log::info!("Player {} completed lap {}", player + 1, laps.laps());
+                    *fraction = Some(checkpoint.entry_fraction(previous[player], car.position()));
+                }
+                if laps.next_checkpoint() <= index {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Accumulates how long each car has been driving the wrong way
+    fn update_wrong_way(&mut self, dt: f32) {
+        for (car, time) in self.cars.iter().zip(&mut self.wrong_way_time) {
+            let expected = self.racing_line.direction_at(car.position());
+            let against = car.velocity().dot(expected) < 0.0;
+
+            if against && car.speed() > WRONG_WAY_MIN_SPEED {
+                *time += dt;
+            } else {
+                *time = 0.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps once with released controls after placing a car
+    fn place(world: &mut World, car: usize, position: Vec2) {
+        world.cars[car].reset(position.x, position.y);
+        world.step_fixed(&[]);
+    }
+
+    #[test]
+    fn obstacles_push_cars_out() {
+        let mut world = World::new(1);
+        let position = Vec2::new(500.0, 500.0);
+        world.spawn_object(WorldObject::obstacle(position, 10.0, None));
+        place(&mut world, 0, position + Vec2::new(5.0, 0.0));
+
+        let car = &world.cars[0];
+        let distance = car.position().distance(position);
+        assert!(distance >= car.collision_radius() + 10.0 - 1e-3);
+    }
+
+    #[test]
+    fn obstacle_radius_follows_spawns_and_despawns() {
+        let mut world = World::new(1);
+        let small = world.spawn_object(WorldObject::obstacle(Vec2::new(100.0, 100.0), 5.0, None));
+        world.step_fixed(&[]);
+
+        // A far bigger obstacle spawned later still reaches the car
+        let position = Vec2::new(500.0, 500.0);
+        let big = world.spawn_object(WorldObject::obstacle(position, 200.0, None));
+        place(&mut world, 0, position + Vec2::new(150.0, 0.0));
+        assert!(world.cars[0].position().distance(position) >= 200.0);
+
+        assert!(world.despawn(big));
+        assert!(world.despawn(small));
+        assert!(!world.despawn(small));
+        place(&mut world, 0, position + Vec2::new(150.0, 0.0));
+        assert_eq!(world.cars[0].position(), position + Vec2::new(150.0, 0.0));
+    }
+
+    #[test]
+    fn laps_count_checkpoints_in_order() {
+        let mut world = World::new(1);
+        let checkpoints: Vec<Vec2> = world.checkpoints().iter().map(|c| c.position()).collect();
+
+        // Skipping ahead doesn't count
+        place(&mut world, 0, checkpoints[2]);
+        assert_eq!(world.lap_tracker(0).unwrap().next_checkpoint(), 0);
+
+        for lap in 0..2 {
+            for &position in &checkpoints {
+                place(&mut world, 0, position);
+            }
+            assert_eq!(world.lap_count(0), lap);
+        }
+        place(&mut world, 0, checkpoints[0]);
+        assert_eq!(world.lap_count(0), 2);
+        assert!(world.lap_completed(0).is_some());
+
+        // Staying inside the finishing checkpoint doesn't count again
+        place(&mut world, 0, checkpoints[0]);
+        assert_eq!(world.lap_count(0), 2);
+        assert!(world.lap_completed(0).is_none());
+    }
+
+    #[test]
+    fn single_checkpoint_never_completes_laps() {
+        let mut world = World::new(1);
+        world.set_checkpoints(vec![Checkpoint::new(Vec2::new(500.0, 500.0), 100.0)]);
+        for _ in 0..10 {
+            place(&mut world, 0, Vec2::new(500.0, 500.0));
+        }
+        assert_eq!(world.lap_count(0), 0);
+    }
+
+    /// Saves a world, lets `edit` change the JSON and loads it back
+    #[cfg(feature = "serde")]
+    fn reload(
+        world: &World,
+        name: &str,
+        edit: impl FnOnce(&mut serde_json::Value),
+    ) -> anyhow::Result<World> {
+        let path =
+            std::env::temp_dir().join(format!("modeseven-{}-{}.json", name, std::process::id()));
+        world.save(&path)?;
+        let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        edit(&mut json);
+        std::fs::write(&path, json.to_string())?;
+
+        let loaded = World::load(&path);
+        std::fs::remove_file(&path)?;
+        loaded
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut world = World::new(3);
+        world.spawn_object(WorldObject::obstacle(Vec2::new(300.0, 300.0), 10.0, None));
+        for _ in 0..30 {
+            world.step_fixed(&[CarInput::new(1.0, 0.5, 0.0), CarInput::new(0.5, -1.0, 0.0)]);
+        }
+
+        let loaded = reload(&world, "round-trip", |_| {}).unwrap();
+        assert_eq!(loaded.cars.len(), 3);
+        for (loaded, car) in loaded.cars.iter().zip(&world.cars) {
+            assert_eq!(loaded.position(), car.position());
+            assert_eq!(loaded.velocity(), car.velocity());
+        }
+        assert_eq!(loaded.objects().len(), 1);
+        assert_eq!(loaded.checkpoints(), world.checkpoints());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn load_rejects_mismatched_per_car_state() {
+        let world = World::new(2);
+        for field in [
+            "wrong_way_time",
+            "laps",
+            "lap_fractions",
+            "impacts",
+            "trails",
+            "starts",
+        ] {
+            let result = reload(&world, field, |json| {
+                json[field].as_array_mut().unwrap().pop();
+            });
+            assert!(result.is_err(), "{field}");
+        }
+
+        let result = reload(&world, "cars", |json| {
+            json["cars"].as_array_mut().unwrap().pop();
+        });
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn load_rejects_mismatched_object_ids() {
+        let mut world = World::new(1);
+        world.spawn_object(WorldObject::new(Vec2::ZERO, None));
+        world.spawn_object(WorldObject::new(Vec2::ONE, None));
+
+        let result = reload(&world, "ids", |json| {
+            json["object_ids"] = serde_json::json!([1, 0]);
+        });
+        assert!(result.is_err());
+
+        // Saves without ids get them assigned in order
+        let loaded = reload(&world, "no-ids", |json| {
+            json["object_ids"] = serde_json::json!([]);
+        })
+        .unwrap();
+        assert!(loaded.object(0).is_some() && loaded.object(1).is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn load_rejects_invalid_tile_maps() {
+        let mut world = World::new(1);
+        world.set_track(Some(TileMap::new(4, 4, 16.0, TileType::Road).unwrap()));
+        assert!(reload(&world, "tiles", |_| {}).unwrap().track().is_some());
+
+        let result = reload(&world, "tile-size", |json| {
+            json["track"]["tile_size"] = serde_json::json!(0.0);
+        });
+        assert!(result.is_err());
+
+        let result = reload(&world, "tile-count", |json| {
+            json["track"]["width"] = serde_json::json!(5);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reset_clears_progress() {
+        let mut world = World::new(2);
+        let start = world.cars[0].position();
+        let first = world.checkpoints()[0].position();
+        place(&mut world, 0, first);
+        assert_eq!(world.lap_tracker(0).unwrap().next_checkpoint(), 1);
+
+        world.reset();
+        assert_eq!(world.cars[0].position(), start);
+        assert_eq!(world.lap_tracker(0), Some(&LapTracker::new()));
+    }
+
+    #[test]
+    fn wrong_way_is_reported_after_a_delay() {
+        // Seconds of full throttle until the car first counts as wrong way
+        let drive = |position: Vec2, angle: f32| {
+            let mut world = World::new(1);
+            world.cars[0].reset_with_angle(position.x, position.y, angle);
+            (1..=(2.5 / FIXED_DT) as u32).find_map(|step| {
+                world.step_fixed(&[CarInput::new(1.0, 0.0, 0.0)]);
+                world.is_wrong_way(0).then_some(step as f32 * FIXED_DT)
+            })
+        };
+
+        // The default racing line runs up the right side of the track
+        let backward = drive(Vec2::new(850.0, 300.0), 0.0).unwrap();
+        assert!(backward >= WRONG_WAY_DELAY);
+        assert!(backward < WRONG_WAY_DELAY + 0.1);
+        assert_eq!(drive(Vec2::new(850.0, 780.0), PI), None);
+    }
+
+    #[test]
+    fn four_players_all_drive() {
+        let mut world = World::new(4);
+        let starts: Vec<_> = world.cars.iter().map(Car::position).collect();
+        for _ in 0..60 {
+            world.step_fixed(&[CarInput::new(1.0, 0.0, 0.0); 4]);
+        }
+
+        assert_eq!(world.player_count(), 4);
+        for (car, start) in world.cars.iter().zip(starts) {
+            assert!(car.position().distance(start) > 10.0);
+        }
+        let mut places: Vec<_> = (0..4).map(|i| world.race_position(i).unwrap()).collect();
+        places.sort();
+        assert_eq!(places, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn race_position_skips_cars_without_progress() {
+        let mut world = World::new(2);
+        world.cars.push(Car::new(0.0, 0.0));
+        assert_eq!(world.race_position(2), None);
+        assert!(world.race_position(0).unwrap() <= 2);
+
+        // Stepping with the extra car doesn't panic either
+        world.step_fixed(&[]);
+    }
+
+    #[test]
+    fn update_car_leaves_other_cars_alone() {
+        let mut world = World::new(2);
+        let other = world.cars[1].clone();
+        let start = world.cars[0].position();
+        for _ in 0..30 {
+            world.update_car(0, CarInput::new(1.0, 0.0, 0.0), FIXED_DT);
+        }
+        world.update_car(2, CarInput::new(1.0, 0.0, 0.0), FIXED_DT);
+
+        assert!(world.cars[0].position().distance(start) > 1.0);
+        assert_eq!(world.cars[1], other);
+    }
+
+    #[test]
+    fn boost_tiles_speed_cars_up() {
+        let speed_on = |tile: TileType| {
+            let mut world = World::new(1);
+            world.set_track(Some(TileMap::new(4, 4, 256.0, tile).unwrap()));
+            for _ in 0..60 {
+                world.step_fixed(&[CarInput::new(1.0, 0.0, 0.0)]);
+            }
+            assert_eq!(world.cars[0].is_boosted(), tile == TileType::Boost);
+            world.cars[0].speed()
+        };
+
+        assert!(speed_on(TileType::Boost) > speed_on(TileType::Road));
+    }
 }
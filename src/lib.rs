@@ -0,0 +1,22 @@
+// allow to have for example world.rs in module `world` `/src/world/world.rs`
+#![allow(clippy::module_inception)]
+// #![forbid(missing_docs)] // TODO: Enable this when we have documentation
+
+//! modeseven
+//!
+//! A 2D racing game inspired by Super Nintendo's Mode 7.
+//!
+//! The game is a two-player split-screen racing game with a split-screen
+//! view of the 2 players' cars. The game is rendered in split-screen mode
+//! with a top view and a bottom view. The top view is player 1's view, the
+//! bottom view is player 2's view.
+
+// TODO: Remove magic numbers etc by wrapping in types ThingId(usize)
+pub mod app;
+pub mod assets;
+pub mod audio;
+pub mod consts;
+pub mod game;
+pub mod menu;
+pub mod settings;
+pub mod state;
@@ -1,5 +1,7 @@
+use super::text::{draw_text, text_metrics, TEXT_SCALE};
+use crate::game::utils::put_pixel;
 use glam::Vec2;
-use rusttype::{point, Font, Scale};
+use rusttype::Font;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ElementState {
@@ -34,6 +36,8 @@ pub struct MenuItem {
     text: String,
     state: ElementState,
     action: MenuAction,
+    /// Whether the item can be focused and activated
+    enabled: bool,
 }
 
 impl MenuItem {
@@ -44,12 +48,36 @@ impl MenuItem {
             text: text.into(),
             state: ElementState::Normal,
             action,
+            enabled: true,
         }
     }
 
     pub fn text(&self) -> &str {
         &self.text
     }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+
+    pub fn set_action(&mut self, action: MenuAction) {
+        self.action = action;
+    }
+
+    /// Returns whether the item can be focused and activated
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the item, disabled items are always drawn as disabled
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.state = ElementState::Disabled;
+        } else if self.state == ElementState::Disabled {
+            self.state = ElementState::Normal;
+        }
+    }
 }
 
 impl MenuElement for MenuItem {
@@ -66,14 +94,26 @@ impl MenuElement for MenuItem {
     }
 
     fn update(&mut self, state: ElementState) {
-        self.state = state;
+        self.state = if self.enabled {
+            state
+        } else {
+            ElementState::Disabled
+        };
     }
 
     fn render(&self, frame: &mut [u8], width: u32, height: u32, font: &Font) {
-        let color = match self.state {
-            ElementState::Normal => [100, 100, 100, 255],
-            ElementState::Focused => [200, 200, 200, 255],
-            ElementState::Disabled => [50, 50, 50, 255],
+        let (background, border, text_color) = match self.state {
+            ElementState::Normal => (
+                [100, 100, 100, 255],
+                [160, 160, 160, 255],
+                [255, 255, 255, 255],
+            ),
+            ElementState::Focused => (
+                [200, 200, 200, 255],
+                [255, 255, 255, 255],
+                [20, 20, 20, 255],
+            ),
+            ElementState::Disabled => ([50, 50, 50, 255], [80, 80, 80, 255], [120, 120, 120, 255]),
         };
 
         let x = self.position.x as u32;
@@ -81,69 +121,55 @@ impl MenuElement for MenuItem {
         let w = self.dimensions.x as u32;
         let h = self.dimensions.y as u32;
 
-        // Draw menu item background
+        // Draw menu item background with a 1 px border around it
         for py in y..y + h {
             for px in x..x + w {
-                if px < width && py < height {
-                    let idx = ((py * width + px) * 4) as usize;
-                    frame[idx..idx + 4].copy_from_slice(&color);
-                }
+                let edge = py == y || py == y + h - 1 || px == x || px == x + w - 1;
+                let color = if edge { border } else { background };
+                put_pixel(frame, width, height, px, py, color);
             }
         }
 
-        // Draw text
-
-        let text = self.text();
-        let height = font.v_metrics(Scale::uniform(16.0)).ascent;
-
-        let x = self.position.x - self.dimensions.x / 1.5;
-        let y = self.position.y - self.dimensions.y / 1.5;
-        // Step 3: Render text
-        let glyphs: Vec<_> = font
-            .layout(text, Scale::uniform(20.0), point(0.0, height))
-            .collect();
-        for (i, glyph) in glyphs.iter().enumerate() {
-            let x = x + (self.dimensions.x as f32)
-                - (glyph.unpositioned().h_metrics().advance_width)
-                + (i as f32) * height;
-            let y = y + (self.dimensions.y as f32) - height + glyph.position().y;
-            let color = match self.state {
-                ElementState::Normal => [255, 255, 255, 255],
-                ElementState::Focused => [200, 200, 200, 255],
-                ElementState::Disabled => [50, 50, 50, 255],
-            };
-            glyph.draw(|gx, gy, v| {
-                // Calculate position relative to the menu item box
-                let px = x as u32 + gx;
-                let py = y as u32 + gy;
-                let idx = ((py * width as u32 + px) * 4) as usize;
-                // Blend the color with alpha from the glyph
-                let alpha = (v * 255.0) as u8;
-                frame[idx..idx + 4].copy_from_slice(&[color[0], color[1], color[2], alpha]);
-            });
-        }
-        // Step 4: Draw border
-        let color = match self.state {
-            ElementState::Normal => [100, 100, 100, 255],
-            ElementState::Focused => [200, 200, 200, 255],
-            ElementState::Disabled => [50, 50, 50, 255],
-        };
-        let x = self.position.x as u32;
-        let y = self.position.y as u32;
-        let w = self.dimensions.x as u32;
-        let h = self.dimensions.y as u32;
-        // Draw menu item background
-        for py in y..y + h {
-            for px in x..x + w {
-                if px < width && py < height as u32 {
-                    let idx = ((py * width + px) * 4) as usize;
-                    frame[idx..idx + 4].copy_from_slice(&color);
-                }
-            }
-        }
+        // Draw text last, centered in the item
+        let (text_width, ascent) = text_metrics(font, self.text(), TEXT_SCALE);
+        let pos = self.position + (self.dimensions - Vec2::new(text_width, ascent)) / 2.0;
+        draw_text(frame, width, height, font, self.text(), pos, text_color);
     }
 
     fn action(&self) -> MenuAction {
         self.action.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetManager;
+
+    #[test]
+    fn glyphs_survive_the_item_box() {
+        let (width, height) = (240, 60);
+        let mut item = MenuItem::new("Play", MenuAction::StartGame);
+        item.set_position(Vec2::new(20.0, 10.0));
+        let mut frame = vec![0; width * height * 4];
+        item.render(
+            &mut frame,
+            width as u32,
+            height as u32,
+            AssetManager::new().get_font(),
+        );
+
+        // Only the white label is brighter than the gray box and border
+        let inside = |x: usize, y: usize| (21..219).contains(&x) && (11..49).contains(&y);
+        let glyphs: Vec<usize> = frame
+            .chunks_exact(4)
+            .enumerate()
+            .filter(|(_, pixel)| pixel[0] > 160)
+            .map(|(index, _)| index)
+            .collect();
+        assert!(!glyphs.is_empty());
+        assert!(glyphs
+            .iter()
+            .all(|&index| inside(index % width, index / width)));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::assets::AssetManager;
-use crate::consts::{PIXELS_HEIGHT, PIXELS_WIDTH};
+use crate::game::rendering::RenderConfig;
 use crate::menu::element::{ElementState, MenuAction, MenuElement, MenuItem};
+use crate::menu::{draw_text_scaled, text_metrics, TEXT_SCALE};
 use glam::Vec2;
 use std::collections::HashMap;
 
@@ -13,22 +14,21 @@ pub struct Menu {
 
 impl Menu {
     fn new(name: String, items: Vec<MenuItem>) -> Self {
-        let mut menu = Self {
+        Self {
             name,
             items,
             selected_item: 0,
-        };
-        menu.layout_items();
-        menu
+        }
     }
 
-    fn layout_items(&mut self) {
+    /// Centers the items on a screen of the given size
+    fn layout_items(&mut self, config: &RenderConfig) {
         let menu_height = self.items.len() as f32 * 50.0;
-        let start_y = (PIXELS_HEIGHT as f32 - menu_height) / 2.0;
+        let start_y = (config.height as f32 - menu_height) / 2.0;
 
         for (i, item) in self.items.iter_mut().enumerate() {
             item.set_position(Vec2::new(
-                (PIXELS_WIDTH as f32 - item.dimensions().x) / 2.0,
+                (config.width as f32 - item.dimensions().x) / 2.0,
                 start_y + i as f32 * 50.0,
             ));
         }
@@ -38,12 +38,31 @@ impl Menu {
         self.items.len()
     }
 
+    /// Returns the action of the selected item, `Nothing` if it is disabled
     fn selected_action(&self) -> MenuAction {
-        self.items[self.selected_item].action()
+        let item = &self.items[self.selected_item];
+        if item.is_enabled() {
+            item.action()
+        } else {
+            MenuAction::Nothing
+        }
     }
 
-    fn selected_item(&self) -> usize {
-        self.selected_item
+    /// Finds the closest enabled item in a direction, wrapping around
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Index to start looking from, excluded until the full lap
+    /// * `step` - Direction to look in, -1 (up) or 1 (down)
+    ///
+    /// # Returns
+    ///
+    /// The index of the item, None if all items are disabled
+    fn next_enabled(&self, from: usize, step: isize) -> Option<usize> {
+        let count = self.item_count() as isize;
+        (1..=count)
+            .map(|offset| (from as isize + step * offset).rem_euclid(count) as usize)
+            .find(|&index| self.items[index].is_enabled())
     }
 
     fn selected_text(&self) -> Option<&str> {
@@ -55,14 +74,40 @@ impl Menu {
     }
 }
 
+/// Title drawn at the top of every menu
+const TITLE: &str = "MODESEVEN";
+
+/// Font size of the title relative to the menu items
+const TITLE_SCALE: f32 = 3.0;
+
+/// Distance from the top of the screen to the title in pixels
+const TITLE_TOP: f32 = 80.0;
+
+/// Color of the title
+const TITLE_COLOR: [u8; 4] = [255, 200, 60, 255];
+
+/// Fill used when no background texture is drawn
+const BACKGROUND_COLOR: [u8; 4] = [30, 30, 30, 255];
+
 pub struct MenuRenderer {
     menus: HashMap<String, Menu>,
     current_menu: String,
     menu_stack: Vec<String>, // Tracks menu navigation history
+    /// Asset name of the background texture, None for a solid fill
+    background: Option<String>,
+    /// Screen size the menus are laid out for
+    config: RenderConfig,
+}
+
+impl Default for MenuRenderer {
+    fn default() -> Self {
+        Self::new(RenderConfig::default())
+    }
 }
 
 impl MenuRenderer {
-    pub fn new() -> Self {
+    /// Creates all menus, centered on a screen of the configured size
+    pub fn new(config: RenderConfig) -> Self {
         let mut menus = HashMap::new();
 
         // Main Menu
@@ -92,6 +137,10 @@ impl MenuRenderer {
                         "Difficulty: Normal",
                         MenuAction::ToggleSetting("difficulty".to_string()),
                     ),
+                    MenuItem::new(
+                        "CPU Opponent: Off",
+                        MenuAction::ToggleSetting("cpu_opponent".to_string()),
+                    ),
                     MenuItem::new(
                         "Fullscreen: Off",
                         MenuAction::ToggleSetting("fullscreen".to_string()),
@@ -256,8 +305,12 @@ impl MenuRenderer {
             ),
         );
 
+        for menu in menus.values_mut() {
+            menu.layout_items(&config);
+        }
+
         // Some dirty runtime checks to ensure menus are valid
-        for (_, menu) in &menus {
+        for menu in menus.values() {
             if menu.item_count() == 0 {
                 panic!("A menu must have at least one item");
             }
@@ -281,38 +334,129 @@ impl MenuRenderer {
             menus,
             current_menu: "main".to_string(),
             menu_stack: Vec::new(),
+            background: None,
+            config,
         }
     }
 
+    /// Returns the screen size the menus are laid out for
+    pub fn config(&self) -> &RenderConfig {
+        &self.config
+    }
+
+    /// Sets the texture stretched over the screen behind the menus
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - Asset name of the texture, None for a solid fill.
+    ///   Names the asset manager doesn't know also fall back to the fill.
+    pub fn set_background(&mut self, background: Option<&str>) {
+        self.background = background.map(String::from);
+    }
+
+    /// Returns the asset name of the background texture, if set
+    pub fn background(&self) -> Option<&str> {
+        self.background.as_deref()
+    }
+
     pub fn render(&mut self, frame: &mut [u8], assets: &AssetManager) -> anyhow::Result<()> {
         let font = assets.get_font();
 
-        // Clear screen with dark background
-        for pixel in frame.chunks_exact_mut(4) {
-            pixel.copy_from_slice(&[30, 30, 30, 255]);
-        }
+        self.render_background(frame, assets);
+
+        // Title centered at the top
+        let title_size = TEXT_SCALE * TITLE_SCALE;
+        let (title_width, _) = text_metrics(font, TITLE, title_size);
+        draw_text_scaled(
+            frame,
+            self.config.width,
+            self.config.height,
+            font,
+            TITLE,
+            Vec2::new((self.config.width as f32 - title_width) / 2.0, TITLE_TOP),
+            title_size,
+            TITLE_COLOR,
+        );
 
         if let Some(menu) = self.menus.get_mut(&self.current_menu) {
             for (i, item) in menu.items.iter_mut().enumerate() {
-                let _ = item.update(if i == menu.selected_item {
+                item.update(if i == menu.selected_item {
                     ElementState::Focused
                 } else {
                     ElementState::Normal
                 });
-                item.render(frame, PIXELS_WIDTH, PIXELS_HEIGHT, &font);
+                item.render(frame, self.config.width, self.config.height, font);
             }
         }
 
         Ok(())
     }
 
+    /// Fills the screen with the background texture or the solid fill
+    fn render_background(&self, frame: &mut [u8], assets: &AssetManager) {
+        let texture = self
+            .background
+            .as_deref()
+            .and_then(|name| assets.try_get_texture(name));
+
+        let Some(texture) = texture else {
+            for pixel in frame.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&BACKGROUND_COLOR);
+            }
+            return;
+        };
+
+        // Stretch the texture over the whole screen
+        let (width, height) = (self.config.width, self.config.height);
+        let scale_x = texture.width() as f32 / width as f32;
+        let scale_y = texture.height() as f32 / height as f32;
+        for (i, pixel) in frame.chunks_exact_mut(4).enumerate() {
+            let x = (i as u32 % width) as f32 * scale_x;
+            let y = (i as u32 / width) as f32 * scale_y;
+            let mut color = texture.sample(x, y, BACKGROUND_COLOR);
+            color[3] = 255;
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    /// Moves the selection by `delta` enabled items, skipping disabled ones
+    ///
+    /// The selection stays put if every item is disabled.
     pub fn move_selection(&mut self, delta: isize) {
         if let Some(menu) = self.menus.get_mut(&self.current_menu) {
-            menu.selected_item = (menu.selected_item as isize + delta)
-                .rem_euclid(menu.item_count() as isize) as usize;
+            for _ in 0..delta.unsigned_abs() {
+                match menu.next_enabled(menu.selected_item, delta.signum()) {
+                    Some(next) => menu.selected_item = next,
+                    None => break,
+                }
+            }
         }
     }
 
+    /// Enables or disables an item of a menu
+    ///
+    /// # Arguments
+    ///
+    /// * `menu` - Key of the menu, e.g. `"main"`
+    /// * `index` - Index of the item, top to bottom
+    /// * `enabled` - Whether the item can be focused and activated
+    ///
+    /// # Returns
+    ///
+    /// Whether the item exists
+    pub fn set_item_enabled(&mut self, menu: &str, index: usize, enabled: bool) -> bool {
+        let Some(item) = self
+            .menus
+            .get_mut(menu)
+            .and_then(|menu| menu.items.get_mut(index))
+        else {
+            return false;
+        };
+
+        item.set_enabled(enabled);
+        true
+    }
+
     pub fn handle_input(&mut self) -> MenuAction {
         if let Some(menu) = self.menus.get(&self.current_menu) {
             let action = menu.selected_action();
@@ -336,6 +480,37 @@ impl MenuRenderer {
         }
     }
 
+    /// Moves the value of the selected slider item by `step`
+    ///
+    /// Slider items are `SetValue` items with a numeric value, e.g. the
+    /// volumes in the sound menu. The value is clamped to 0..=100.
+    ///
+    /// # Returns
+    ///
+    /// The updated `SetValue` action, `Nothing` if the selected item
+    /// is not a slider
+    pub fn adjust_selected(&mut self, step: i32) -> MenuAction {
+        let Some(item) = self
+            .menus
+            .get_mut(&self.current_menu)
+            .and_then(|menu| menu.items.get_mut(menu.selected_item))
+            .filter(|item| item.is_enabled())
+        else {
+            return MenuAction::Nothing;
+        };
+
+        let MenuAction::SetValue(key, value) = item.action() else {
+            return MenuAction::Nothing;
+        };
+        let Ok(value) = value.parse::<i32>() else {
+            return MenuAction::Nothing;
+        };
+
+        let action = MenuAction::SetValue(key, (value + step).clamp(0, 100).to_string());
+        item.set_action(action.clone());
+        action
+    }
+
     pub fn current_menu(&self) -> &str {
         &self.current_menu
     }
@@ -345,4 +520,38 @@ impl MenuRenderer {
             .get(&self.current_menu)
             .and_then(|menu| menu.selected_text().map(String::from))
     }
+
+    /// Replaces the label of the selected item in the current menu
+    pub fn set_selected_text(&mut self, text: impl Into<String>) {
+        if let Some(item) = self
+            .menus
+            .get_mut(&self.current_menu)
+            .and_then(|menu| menu.items.get_mut(menu.selected_item))
+        {
+            item.set_text(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_are_centered_for_the_configured_size() {
+        let config = RenderConfig::new(640, 480);
+        let renderer = MenuRenderer::new(config);
+        assert_eq!(renderer.config(), &config);
+
+        for menu in renderer.menus.values() {
+            // The 50 px item slots sit in the middle of the screen
+            let top = menu.items[0].position().y;
+            let block = menu.items.len() as f32 * 50.0;
+            assert_eq!(top, (480.0 - block) / 2.0, "{}", menu.name());
+            for item in &menu.items {
+                let center = item.position().x + item.dimensions().x / 2.0;
+                assert_eq!(center, 320.0, "{}", item.text());
+            }
+        }
+    }
 }
@@ -2,7 +2,9 @@
 
 pub(crate) mod element;
 mod menu_renderer;
+mod text;
 
 pub use menu_renderer::MenuRenderer;
 
 pub use element::{MenuAction, MenuElement};
+pub use text::{draw_text, draw_text_scaled, text_metrics, TEXT_SCALE};
@@ -0,0 +1,111 @@
+//! Text rendering helpers shared by menus and in-game overlays
+
+use crate::game::utils::blend_pixel_at;
+use glam::Vec2;
+use rusttype::{point, Font, Scale};
+
+/// Default font scale used for UI text
+pub const TEXT_SCALE: f32 = 20.0;
+
+/// Draws a line of text into an RGBA frame buffer
+///
+/// Glyph coverage is used as alpha to blend the text color over the
+/// existing pixels. Pixels outside the frame are skipped.
+///
+/// # Arguments
+///
+/// * `frame` - RGBA pixel buffer (width * height * 4 bytes)
+/// * `width` - Frame width in pixels
+/// * `height` - Frame height in pixels
+/// * `font` - Font used to lay out the glyphs
+/// * `text` - Text to draw
+/// * `pos` - Top-left corner of the text in pixels
+/// * `color` - RGBA text color
+pub fn draw_text(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &Font,
+    text: &str,
+    pos: Vec2,
+    color: [u8; 4],
+) {
+    draw_text_scaled(frame, width, height, font, text, pos, TEXT_SCALE, color);
+}
+
+/// Draws a line of text at a custom font size
+///
+/// Same as [`draw_text`] but with an explicit pixel height.
+///
+/// # Arguments
+///
+/// * `frame` - RGBA pixel buffer (width * height * 4 bytes)
+/// * `width` - Frame width in pixels
+/// * `height` - Frame height in pixels
+/// * `font` - Font used to lay out the glyphs
+/// * `text` - Text to draw
+/// * `pos` - Top-left corner of the text in pixels
+/// * `size` - Font size in pixels
+/// * `color` - RGBA text color
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_scaled(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    font: &Font,
+    text: &str,
+    pos: Vec2,
+    size: f32,
+    color: [u8; 4],
+) {
+    let scale = Scale::uniform(size);
+    let ascent = font.v_metrics(scale).ascent;
+
+    for glyph in font.layout(text, scale, point(pos.x, pos.y + ascent)) {
+        let Some(bounds) = glyph.pixel_bounding_box() else {
+            continue;
+        };
+
+        glyph.draw(|gx, gy, v| {
+            let px = bounds.min.x + gx as i32;
+            let py = bounds.min.y + gy as i32;
+            if px < 0 || py < 0 {
+                return;
+            }
+
+            let alpha = (v * color[3] as f32).round() as u8;
+            blend_pixel_at(
+                frame,
+                width,
+                height,
+                px as u32,
+                py as u32,
+                [color[0], color[1], color[2], alpha],
+            );
+        });
+    }
+}
+
+/// Measures the width and ascent of a line of text
+///
+/// # Arguments
+///
+/// * `font` - Font used to lay out the glyphs
+/// * `text` - Text to measure
+/// * `size` - Font size in pixels
+///
+/// # Returns
+///
+/// `(width, ascent)` in pixels, the ascent being the distance from the
+/// top of the text to its baseline
+pub fn text_metrics(font: &Font, text: &str, size: f32) -> (f32, f32) {
+    let scale = Scale::uniform(size);
+    let width = font
+        .layout(text, scale, point(0.0, 0.0))
+        .last()
+        .map_or(0.0, |glyph| {
+            glyph.position().x + glyph.unpositioned().h_metrics().advance_width
+        });
+
+    (width, font.v_metrics(scale).ascent)
+}
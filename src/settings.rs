@@ -0,0 +1,241 @@
+//! User configurable settings
+//!
+//! Stores the values behind the options menus, keyed by the same
+//! setting keys the menu actions use (e.g. `"fullscreen"`).
+
+use crate::game::ai::Difficulty;
+use std::collections::HashMap;
+
+/// The value of a single setting
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    /// On/off switch
+    Bool(bool),
+    /// One of a fixed list of options
+    Choice {
+        /// All selectable options in cycling order
+        options: &'static [&'static str],
+        /// Index of the selected option
+        index: usize,
+    },
+    /// Percentage from 0 to 100, e.g. a volume
+    Percent(u8),
+}
+
+impl SettingValue {
+    /// Flips a boolean or advances a choice to the next option (wrapping)
+    fn toggle(&mut self) {
+        match self {
+            SettingValue::Bool(value) => *value = !*value,
+            SettingValue::Choice { options, index } => *index = (*index + 1) % options.len(),
+            SettingValue::Percent(_) => {}
+        }
+    }
+
+    /// Human readable value shown in menus
+    pub fn display(&self) -> String {
+        match self {
+            SettingValue::Bool(true) => "On".to_string(),
+            SettingValue::Bool(false) => "Off".to_string(),
+            SettingValue::Choice { options, index } => options[*index].to_string(),
+            SettingValue::Percent(percent) => format!("{}%", percent),
+        }
+    }
+}
+
+/// A named setting
+#[derive(Debug, Clone, PartialEq)]
+struct Setting {
+    /// Name shown in menus
+    name: &'static str,
+    /// Current value
+    value: SettingValue,
+}
+
+/// Store of all user settings
+///
+/// Settings provides:
+/// * Default values matching the initial menu labels
+/// * Toggling booleans and cycling choices by key
+/// * Percentages clamped to 0..=100
+/// * Menu labels like `"Fullscreen: On"` or `"Master Volume: 70%"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Settings by key
+    values: HashMap<&'static str, Setting>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    /// Creates the settings store with default values
+    ///
+    /// # Returns
+    ///
+    /// Settings with:
+    /// * cpu_opponent: Off
+    /// * difficulty: Normal (Easy/Normal/Hard)
+    /// * fullscreen: Off
+    /// * master_volume: 100%
+    /// * music_volume: 80%
+    /// * quality: High (Low/Medium/High)
+    /// * sfx_volume: 90%
+    /// * vsync: On
+    pub fn new() -> Self {
+        let mut values = HashMap::new();
+
+        let mut insert = |key, name, value| {
+            values.insert(key, Setting { name, value });
+        };
+        insert("cpu_opponent", "CPU Opponent", SettingValue::Bool(false));
+        insert(
+            "difficulty",
+            "Difficulty",
+            SettingValue::Choice {
+                options: &["Easy", "Normal", "Hard"],
+                index: 1,
+            },
+        );
+        insert("fullscreen", "Fullscreen", SettingValue::Bool(false));
+        insert("master_volume", "Master Volume", SettingValue::Percent(100));
+        insert("music_volume", "Music Volume", SettingValue::Percent(80));
+        insert(
+            "quality",
+            "Quality",
+            SettingValue::Choice {
+                options: &["Low", "Medium", "High"],
+                index: 2,
+            },
+        );
+        insert("sfx_volume", "SFX Volume", SettingValue::Percent(90));
+        insert("vsync", "VSync", SettingValue::Bool(true));
+
+        Self { values }
+    }
+
+    /// Returns the value of a setting
+    pub fn get(&self, key: &str) -> Option<&SettingValue> {
+        self.values.get(key).map(|setting| &setting.value)
+    }
+
+    /// Returns the value of a boolean setting
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            SettingValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the selected option of a choice setting
+    pub fn get_choice(&self, key: &str) -> Option<&'static str> {
+        match self.get(key)? {
+            SettingValue::Choice { options, index } => Some(options[*index]),
+            _ => None,
+        }
+    }
+
+    /// Returns the selected difficulty, Normal if it can't be read
+    pub fn difficulty(&self) -> Difficulty {
+        self.get_choice("difficulty")
+            .and_then(Difficulty::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Returns the value of a percentage setting
+    pub fn get_percent(&self, key: &str) -> Option<u8> {
+        match self.get(key)? {
+            SettingValue::Percent(percent) => Some(*percent),
+            _ => None,
+        }
+    }
+
+    /// Sets a percentage setting, clamped to 0..=100
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Setting key
+    /// * `percent` - New value
+    ///
+    /// # Returns
+    ///
+    /// The stored value, None if the key is unknown or not a percentage
+    pub fn set_percent(&mut self, key: &str, percent: i32) -> Option<u8> {
+        match &mut self.values.get_mut(key)?.value {
+            SettingValue::Percent(value) => {
+                *value = percent.clamp(0, 100) as u8;
+                Some(*value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Flips a boolean setting or cycles a choice setting
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Setting key
+    ///
+    /// # Returns
+    ///
+    /// The new value, None if the key is unknown
+    pub fn toggle(&mut self, key: &str) -> Option<&SettingValue> {
+        let setting = self.values.get_mut(key)?;
+        setting.value.toggle();
+        Some(&setting.value)
+    }
+
+    /// Returns the menu label of a setting, e.g. `"Fullscreen: On"`
+    /// or `"Master Volume: 70%"`
+    pub fn label(&self, key: &str) -> Option<String> {
+        self.values
+            .get(key)
+            .map(|setting| format!("{}: {}", setting.name, setting.value.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_flips_bools_and_cycles_choices() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.get_bool("fullscreen"), Some(false));
+        settings.toggle("fullscreen");
+        assert_eq!(settings.get_bool("fullscreen"), Some(true));
+        assert_eq!(settings.label("fullscreen").unwrap(), "Fullscreen: On");
+
+        assert_eq!(settings.get_choice("quality"), Some("High"));
+        settings.toggle("quality");
+        assert_eq!(settings.get_choice("quality"), Some("Low"));
+
+        assert!(settings.toggle("unknown").is_none());
+    }
+
+    #[test]
+    fn percents_are_clamped() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.set_percent("music_volume", 150), Some(100));
+        assert_eq!(settings.set_percent("music_volume", -5), Some(0));
+        assert_eq!(settings.label("music_volume").unwrap(), "Music Volume: 0%");
+
+        // Toggling a percent changes nothing
+        settings.toggle("music_volume");
+        assert_eq!(settings.get_percent("music_volume"), Some(0));
+        assert_eq!(settings.set_percent("vsync", 50), None);
+    }
+
+    #[test]
+    fn difficulty_follows_the_choice() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.difficulty(), Difficulty::Normal);
+        settings.toggle("difficulty");
+        assert_eq!(settings.difficulty(), Difficulty::Hard);
+        settings.toggle("difficulty");
+        assert_eq!(settings.difficulty(), Difficulty::Easy);
+    }
+}
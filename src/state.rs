@@ -1,14 +1,63 @@
 /// TODO: Add docs
 use std::fmt;
 
+/// Seconds counted down before a race starts
+pub const COUNTDOWN_SECONDS: f32 = 3.0;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameState {
     Menu(MenuState),
+    /// Race about to start, holding the seconds left until it does
+    Countdown(f32),
     Playing,
     Paused,
+    /// Race over, showing the results
+    Finished {
+        /// Index of the player who finished first
+        winner: usize,
+    },
 }
 
 impl GameState {
+    /// Returns a full countdown before the race starts
+    pub fn countdown() -> Self {
+        Self::Countdown(COUNTDOWN_SECONDS)
+    }
+
+    pub fn is_counting_down(&self) -> bool {
+        matches!(self, GameState::Countdown(_))
+    }
+
+    /// Advances a countdown by `dt` seconds
+    ///
+    /// # Returns
+    ///
+    /// * `Countdown` with less time left while time remains
+    /// * `Playing` once the countdown reaches zero
+    /// * The state itself if it isn't a countdown
+    pub fn tick_countdown(self, dt: f32) -> Self {
+        match self {
+            GameState::Countdown(remaining) => {
+                let remaining = remaining - dt.max(0.0);
+                if remaining > 0.0 {
+                    GameState::Countdown(remaining)
+                } else {
+                    GameState::Playing
+                }
+            }
+            state => state,
+        }
+    }
+
+    /// Returns the number shown during a countdown, e.g. 3 with between
+    /// 2 and 3 seconds left, None outside of a countdown
+    pub fn countdown_number(&self) -> Option<u32> {
+        match self {
+            GameState::Countdown(remaining) => Some((remaining.ceil() as u32).max(1)),
+            _ => None,
+        }
+    }
+
     pub fn is_playing(&self) -> bool {
         matches!(self, GameState::Playing)
     }
@@ -42,3 +91,125 @@ impl fmt::Display for MenuState {
         }
     }
 }
+
+/// A fade to black and back in while switching to another state
+///
+/// The first half of the fade darkens the current state, the target
+/// state takes over at the midpoint and the second half fades it in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    /// State to switch to at the midpoint
+    target: GameState,
+    /// Total length of the fade in seconds
+    duration: f32,
+    /// Seconds since the fade started
+    elapsed: f32,
+    /// Whether the midpoint has been reached
+    switched: bool,
+}
+
+impl Transition {
+    /// Starts a fade towards `target` lasting `duration` seconds (clamped to ≥ 0)
+    pub fn new(target: GameState, duration: f32) -> Self {
+        Self {
+            target,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            switched: false,
+        }
+    }
+
+    /// Returns the state the fade switches to
+    pub fn target(&self) -> GameState {
+        self.target
+    }
+
+    /// Advances the fade
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Elapsed real time in seconds
+    ///
+    /// # Returns
+    ///
+    /// The target state exactly once, on the update that reaches the midpoint
+    pub fn update(&mut self, dt: f32) -> Option<GameState> {
+        self.elapsed = (self.elapsed + dt.max(0.0)).min(self.duration);
+        if self.switched || self.elapsed < self.duration / 2.0 {
+            return None;
+        }
+
+        self.switched = true;
+        Some(self.target)
+    }
+
+    /// Returns whether the target state has taken over
+    pub fn has_switched(&self) -> bool {
+        self.switched
+    }
+
+    /// Returns whether the fade is over
+    pub fn is_finished(&self) -> bool {
+        self.switched && self.elapsed >= self.duration
+    }
+
+    /// Returns how far the frame is faded to black
+    ///
+    /// Eases from 0.0 up to 1.0 at the midpoint and back down to 0.0,
+    /// using smoothstep on each half.
+    pub fn alpha(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+
+        let t = self.elapsed / self.duration;
+        let x = (1.0 - (2.0 * t - 1.0).abs()).clamp(0.0, 1.0);
+        x * x * (3.0 - 2.0 * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transition_fades_out_and_back_in() {
+        let mut transition = Transition::new(GameState::Playing, 1.0);
+        assert_eq!(transition.alpha(), 0.0);
+
+        let mut switches = Vec::new();
+        let mut peak = 0.0f32;
+        for step in 1..=10 {
+            if let Some(state) = transition.update(0.1) {
+                switches.push((step, state));
+            }
+            peak = peak.max(transition.alpha());
+        }
+
+        // The switch happens once, at the midpoint where the frame is black
+        assert_eq!(switches, [(5, GameState::Playing)]);
+        assert!((peak - 1.0).abs() < 1e-6);
+        assert!(transition.alpha() < 1e-6);
+        assert!(transition.is_finished());
+        assert_eq!(transition.update(0.1), None);
+    }
+
+    #[test]
+    fn countdown_shows_three_two_one_then_plays() {
+        let mut state = GameState::countdown();
+        let mut shown = Vec::new();
+        let mut elapsed = 0.0;
+        while let Some(number) = state.countdown_number() {
+            if shown.last().is_none_or(|&(last, _)| last != number) {
+                shown.push((number, elapsed));
+            }
+            state = state.tick_countdown(0.25);
+            elapsed += 0.25;
+        }
+
+        assert_eq!(shown, [(3, 0.0), (2, 1.0), (1, 2.0)]);
+        assert_eq!(elapsed, COUNTDOWN_SECONDS);
+        assert!(state.is_playing());
+        assert_eq!(GameState::Paused.tick_countdown(1.0), GameState::Paused);
+    }
+}